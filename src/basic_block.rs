@@ -134,7 +134,11 @@ impl BasicBlock {
         BasicBlock::new(bb)
     }
 
-    /// Prepends one `BasicBlock` before another.
+    /// Prepends one `BasicBlock` before another. Both `BasicBlock`s must belong to a `FunctionValue`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if either `BasicBlock` has no parent `FunctionValue`.
     ///
     /// # Example
     /// ```no_run
@@ -158,12 +162,19 @@ impl BasicBlock {
     /// ```
     // REVIEW: What happens if blocks are from different scopes?
     pub fn move_before(&self, basic_block: &BasicBlock) {
+        // This method is UB if either BasicBlock has no parent, so we must check for parents (or encode into type system)
+        assert!(self.get_parent().is_some() && basic_block.get_parent().is_some(), "Cannot move a BasicBlock without a parent FunctionValue");
+
         unsafe {
             LLVMMoveBasicBlockBefore(self.basic_block, basic_block.basic_block)
         }
     }
 
-    /// Appends one `BasicBlock` after another.
+    /// Appends one `BasicBlock` after another. Both `BasicBlock`s must belong to a `FunctionValue`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if either `BasicBlock` has no parent `FunctionValue`.
     ///
     /// # Example
     /// ```no_run
@@ -187,6 +198,9 @@ impl BasicBlock {
     /// ```
     // REVIEW: What happens if blocks are from different scopes?
     pub fn move_after(&self, basic_block: &BasicBlock) {
+        // This method is UB if either BasicBlock has no parent, so we must check for parents (or encode into type system)
+        assert!(self.get_parent().is_some() && basic_block.get_parent().is_some(), "Cannot move a BasicBlock without a parent FunctionValue");
+
         unsafe {
             LLVMMoveBasicBlockAfter(self.basic_block, basic_block.basic_block)
         }
@@ -292,6 +306,10 @@ impl BasicBlock {
 
     /// Obtains the terminating `InstructionValue` in this `BasicBlock`, if any. A `BasicBlock` must have a terminating instruction to be valid.
     ///
+    /// Unlike `get_last_instruction`, this returns `None` when the last instruction is not a terminator
+    /// (ie a return, branch, or switch), so it can be used to find unterminated blocks before verifying
+    /// or JITing a `FunctionValue`.
+    ///
     /// # Example
     /// ```no_run
     /// use inkwell::context::Context;
@@ -314,8 +332,6 @@ impl BasicBlock {
     /// ```
     // REVIEW: If we wanted the return type could be Option<Either<BasicValueEnum, InstructionValue>>
     // if getting a value over an instruction is preferable
-    // TODOC: Every BB must have a terminating instruction or else it is invalid
-    // REVIEW: Unclear how this differs from get_last_instruction
     pub fn get_terminator(&self) -> Option<InstructionValue> {
         let value = unsafe {
             LLVMGetBasicBlockTerminator(self.basic_block)
@@ -363,6 +379,11 @@ impl BasicBlock {
     }
 
     /// Removes this `BasicBlock` completely from memory. This is unsafe because you could easily have other references to the same `BasicBlock`.
+    /// This `BasicBlock` must belong to a `FunctionValue`, as LLVM can only delete a `BasicBlock` by erasing it from its parent.
+    ///
+    /// # Panics
+    ///
+    /// Panics if this `BasicBlock` has no parent `FunctionValue`.
     ///
     /// # Example
     /// ```no_run
@@ -384,9 +405,9 @@ impl BasicBlock {
     /// ```
     // REVIEW: Could potentially be unsafe if there are existing references. Might need a global ref counter
     pub unsafe fn delete(self) {
-        // unsafe {
+        assert!(self.get_parent().is_some(), "Cannot delete a BasicBlock without a parent FunctionValue");
+
         LLVMDeleteBasicBlock(self.basic_block)
-        // }
     }
 
     /// Obtains the `ContextRef` this `BasicBlock` belongs to.
@@ -1,14 +1,22 @@
 use either::Either;
-use llvm_sys::core::{LLVMBuildAdd, LLVMBuildAlloca, LLVMBuildAnd, LLVMBuildArrayAlloca, LLVMBuildArrayMalloc, LLVMBuildBr, LLVMBuildCall, LLVMBuildCast, LLVMBuildCondBr, LLVMBuildExtractValue, LLVMBuildFAdd, LLVMBuildFCmp, LLVMBuildFDiv, LLVMBuildFence, LLVMBuildFMul, LLVMBuildFNeg, LLVMBuildFree, LLVMBuildFSub, LLVMBuildGEP, LLVMBuildICmp, LLVMBuildInsertValue, LLVMBuildIsNotNull, LLVMBuildIsNull, LLVMBuildLoad, LLVMBuildMalloc, LLVMBuildMul, LLVMBuildNeg, LLVMBuildNot, LLVMBuildOr, LLVMBuildPhi, LLVMBuildPointerCast, LLVMBuildRet, LLVMBuildRetVoid, LLVMBuildStore, LLVMBuildSub, LLVMBuildUDiv, LLVMBuildUnreachable, LLVMBuildXor, LLVMDisposeBuilder, LLVMGetElementType, LLVMGetInsertBlock, LLVMGetReturnType, LLVMGetTypeKind, LLVMInsertIntoBuilder, LLVMPositionBuilderAtEnd, LLVMTypeOf, LLVMSetTailCall, LLVMBuildExtractElement, LLVMBuildInsertElement, LLVMBuildIntToPtr, LLVMBuildPtrToInt, LLVMInsertIntoBuilderWithName, LLVMClearInsertionPosition, LLVMCreateBuilder, LLVMPositionBuilder, LLVMPositionBuilderBefore, LLVMBuildAggregateRet, LLVMBuildStructGEP, LLVMBuildInBoundsGEP, LLVMBuildPtrDiff, LLVMBuildNSWAdd, LLVMBuildNUWAdd, LLVMBuildNSWSub, LLVMBuildNUWSub, LLVMBuildNSWMul, LLVMBuildNUWMul, LLVMBuildSDiv, LLVMBuildSRem, LLVMBuildURem, LLVMBuildFRem, LLVMBuildNSWNeg, LLVMBuildNUWNeg, LLVMBuildFPToUI, LLVMBuildFPToSI, LLVMBuildSIToFP, LLVMBuildUIToFP, LLVMBuildFPTrunc, LLVMBuildFPExt, LLVMBuildIntCast, LLVMBuildFPCast, LLVMBuildSExtOrBitCast, LLVMBuildZExtOrBitCast, LLVMBuildTruncOrBitCast, LLVMBuildSwitch, LLVMAddCase, LLVMBuildShl, LLVMBuildAShr, LLVMBuildLShr, LLVMBuildGlobalString, LLVMBuildGlobalStringPtr, LLVMBuildExactSDiv, LLVMBuildTrunc, LLVMBuildSExt, LLVMBuildZExt};
-use llvm_sys::prelude::{LLVMBuilderRef, LLVMValueRef};
-use llvm_sys::{LLVMTypeKind, LLVMAtomicOrdering};
-
-use {IntPredicate, FloatPredicate};
+use llvm_sys::core::{LLVMBuildAdd, LLVMBuildAlloca, LLVMBuildAnd, LLVMBuildArrayAlloca, LLVMBuildArrayMalloc, LLVMBuildBr, LLVMBuildCall, LLVMBuildCast, LLVMBuildCondBr, LLVMBuildExtractValue, LLVMBuildFAdd, LLVMBuildFCmp, LLVMBuildFDiv, LLVMBuildFence, LLVMBuildFMul, LLVMBuildFNeg, LLVMBuildFree, LLVMBuildFSub, LLVMBuildGEP, LLVMBuildICmp, LLVMBuildInsertValue, LLVMBuildIsNotNull, LLVMBuildIsNull, LLVMBuildLoad, LLVMBuildMalloc, LLVMBuildMul, LLVMBuildNeg, LLVMBuildNot, LLVMBuildOr, LLVMBuildPhi, LLVMBuildPointerCast, LLVMBuildRet, LLVMBuildRetVoid, LLVMBuildStore, LLVMBuildSub, LLVMBuildUDiv, LLVMBuildUnreachable, LLVMBuildXor, LLVMDisposeBuilder, LLVMGetElementType, LLVMGetInsertBlock, LLVMGetReturnType, LLVMGetTypeKind, LLVMInsertIntoBuilder, LLVMPositionBuilderAtEnd, LLVMTypeOf, LLVMSetTailCall, LLVMBuildExtractElement, LLVMBuildInsertElement, LLVMBuildIntToPtr, LLVMBuildPtrToInt, LLVMInsertIntoBuilderWithName, LLVMClearInsertionPosition, LLVMCreateBuilder, LLVMPositionBuilder, LLVMPositionBuilderBefore, LLVMBuildAggregateRet, LLVMBuildStructGEP, LLVMBuildInBoundsGEP, LLVMBuildPtrDiff, LLVMBuildNSWAdd, LLVMBuildNUWAdd, LLVMBuildNSWSub, LLVMBuildNUWSub, LLVMBuildNSWMul, LLVMBuildNUWMul, LLVMBuildSDiv, LLVMBuildSRem, LLVMBuildURem, LLVMBuildFRem, LLVMBuildNSWNeg, LLVMBuildNUWNeg, LLVMBuildFPToUI, LLVMBuildFPToSI, LLVMBuildSIToFP, LLVMBuildUIToFP, LLVMBuildFPTrunc, LLVMBuildFPExt, LLVMBuildIntCast, LLVMBuildFPCast, LLVMBuildSExtOrBitCast, LLVMBuildZExtOrBitCast, LLVMBuildTruncOrBitCast, LLVMBuildSwitch, LLVMAddCase, LLVMBuildShl, LLVMBuildAShr, LLVMBuildLShr, LLVMBuildGlobalString, LLVMBuildGlobalStringPtr, LLVMBuildExactSDiv, LLVMBuildTrunc, LLVMBuildSExt, LLVMBuildZExt, LLVMBuildAtomicRMW, LLVMCountStructElementTypes, LLVMGetArrayLength, LLVMIsAConstantInt, LLVMBuildIndirectBr, LLVMAddDestination, LLVMBuildShuffleVector, LLVMIsConstant, LLVMGetIntTypeWidth, LLVMGetVectorSize, LLVMBuildSelect, LLVMTypeIsSized};
+#[cfg(not(any(feature = "llvm3-6", feature = "llvm3-7", feature = "llvm3-8")))]
+use llvm_sys::core::LLVMBuildAtomicCmpXchg;
+#[cfg(not(any(feature = "llvm3-6", feature = "llvm3-7", feature = "llvm3-8", feature = "llvm3-9", feature = "llvm4-0", feature = "llvm5-0")))]
+use llvm_sys::core::{LLVMGetCurrentDebugLocation, LLVMGetMDNodeNumOperands, LLVMGetTypeContext, LLVMSetCurrentDebugLocation, LLVMValueAsMetadata};
+use llvm_sys::prelude::{LLVMBuilderRef, LLVMTypeRef, LLVMValueRef};
+use llvm_sys::LLVMTypeKind;
+
+use {AtomicOrdering, AtomicRMWBinOp, IntPredicate, FloatPredicate};
 use basic_block::BasicBlock;
-use values::{AggregateValue, AsValueRef, BasicValue, BasicValueEnum, PhiValue, FunctionValue, IntValue, PointerValue, VectorValue, InstructionValue, GlobalValue, IntMathValue, FloatMathValue, PointerMathValue, InstructionOpcode};
+#[cfg(not(any(feature = "llvm3-6", feature = "llvm3-7", feature = "llvm3-8", feature = "llvm3-9", feature = "llvm4-0", feature = "llvm5-0")))]
+use debug_info::DILocation;
+use values::{AggregateValue, AggregateValueEnum, AsValueRef, BasicValue, BasicValueEnum, PhiValue, FunctionValue, IntValue, PointerValue, VectorValue, InstructionValue, GlobalValue, IntMathValue, FloatMathValue, PointerMathValue, InstructionOpcode, StructValue};
 use types::{AsTypeRef, BasicType, IntMathType, FloatMathType, PointerMathType};
 
 use std::ffi::CString;
+#[cfg(not(any(feature = "llvm3-6", feature = "llvm3-7", feature = "llvm3-8", feature = "llvm3-9", feature = "llvm4-0", feature = "llvm5-0")))]
+use std::ptr;
 
 #[derive(Debug)]
 pub struct Builder {
@@ -63,9 +71,50 @@ impl Builder {
     }
 
     pub fn build_call(&self, function: &FunctionValue, args: &[&BasicValue], name: &str, tail_call: bool) -> Either<BasicValueEnum, InstructionValue> {
+        self.build_call_value(function.as_value_ref(), args, name, tail_call)
+    }
+
+    /// Builds a call through a pointer to a function, such as a function pointer loaded from memory
+    /// or inline assembly created by `Context::create_inline_asm`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `function_pointer` does not point to a function type.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use inkwell::context::Context;
+    ///
+    /// let context = Context::create();
+    /// let module = context.create_module("my_mod");
+    /// let builder = context.create_builder();
+    /// let void_type = context.void_type();
+    /// let fn_type = void_type.fn_type(&[], false);
+    /// let fn_value = module.add_function("my_fn", &fn_type, None);
+    /// let entry = fn_value.append_basic_block("entry");
+    /// let asm = context.create_inline_asm(&fn_type, "nop", "", true, false);
+    ///
+    /// builder.position_at_end(&entry);
+    /// builder.build_indirect_call(&asm, &[], "", false);
+    /// builder.build_return(None);
+    ///
+    /// assert!(fn_value.verify(false));
+    /// ```
+    pub fn build_indirect_call(&self, function_pointer: &PointerValue, args: &[&BasicValue], name: &str, tail_call: bool) -> Either<BasicValueEnum, InstructionValue> {
+        let is_fn_ptr = unsafe {
+            LLVMGetTypeKind(LLVMGetElementType(LLVMTypeOf(function_pointer.as_value_ref()))) == LLVMTypeKind::LLVMFunctionTypeKind
+        };
+
+        assert!(is_fn_ptr, "Indirect call requires a pointer to a function type");
+
+        self.build_call_value(function_pointer.as_value_ref(), args, name, tail_call)
+    }
+
+    fn build_call_value(&self, function: LLVMValueRef, args: &[&BasicValue], name: &str, tail_call: bool) -> Either<BasicValueEnum, InstructionValue> {
         // LLVM gets upset when void calls are named because they don't return anything
         let name = unsafe {
-            match LLVMGetTypeKind(LLVMGetReturnType(LLVMGetElementType(LLVMTypeOf(function.as_value_ref())))) {
+            match LLVMGetTypeKind(LLVMGetReturnType(LLVMGetElementType(LLVMTypeOf(function)))) {
                 LLVMTypeKind::LLVMVoidTypeKind => "",
                 _ => name,
             }
@@ -76,7 +125,7 @@ impl Builder {
                                               .map(|val| val.as_value_ref())
                                               .collect();
         let value = unsafe {
-            LLVMBuildCall(self.builder, function.as_value_ref(), args.as_mut_ptr(), args.len() as u32, c_string.as_ptr())
+            LLVMBuildCall(self.builder, function, args.as_mut_ptr(), args.len() as u32, c_string.as_ptr())
         };
 
         if tail_call {
@@ -107,6 +156,10 @@ impl Builder {
     }
 
     // REVIEW: Doesn't GEP work on array too?
+    /// Like `build_gep`, but the resulting pointer is marked `inbounds`: it is assumed to stay within the
+    /// allocated object `ptr` points into, which lets alias analysis reason about it. If it doesn't,
+    /// the result is a poison value.
+    ///
     /// GEP is very likely to segfault if indexes are used incorrectly, and is therefore an unsafe function. Maybe we can change this in the future.
     pub unsafe fn build_in_bounds_gep(&self, ptr: &PointerValue, ordered_indexes: &[IntValue], name: &str) -> PointerValue {
         let c_string = CString::new(name).expect("Conversion to CString failed unexpectedly");
@@ -120,13 +173,56 @@ impl Builder {
     }
 
     // REVIEW: Shouldn't this take a StructValue? Or does it still need to be PointerValue<StructValue>?
-    /// GEP is very likely to segfault if indexes are used incorrectly, and is therefore an unsafe function. Maybe we can change this in the future.
-    pub unsafe fn build_struct_gep(&self, ptr: &PointerValue, index: u32, name: &str) -> PointerValue {
+    /// Builds a GEP instruction which computes the address of the field at `index` of the struct `ptr` points to.
+    /// Returns `None` if `ptr` doesn't point to a struct, or if `index` is out of bounds for it.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use inkwell::AddressSpace;
+    /// use inkwell::context::Context;
+    ///
+    /// let context = Context::create();
+    /// let module = context.create_module("struct_gep");
+    /// let builder = context.create_builder();
+    /// let i32_type = context.i32_type();
+    /// let i32_ptr_type = i32_type.ptr_type(AddressSpace::Generic);
+    /// let struct_type = context.struct_type(&[i32_type.into(), i32_type.into()], false);
+    /// let struct_ptr_type = struct_type.ptr_type(AddressSpace::Generic);
+    /// let fn_type = i32_ptr_type.fn_type(&[&struct_ptr_type], false);
+    /// let fn_value = module.add_function("get_second_field", &fn_type, None);
+    /// let entry = fn_value.append_basic_block("entry");
+    /// let struct_ptr = fn_value.get_first_param().unwrap().into_pointer_value();
+    ///
+    /// builder.position_at_end(&entry);
+    ///
+    /// assert!(builder.build_struct_gep(&struct_ptr, 2, "third").is_none());
+    ///
+    /// let second_ptr = builder.build_struct_gep(&struct_ptr, 1, "second").unwrap();
+    ///
+    /// builder.build_return(Some(&second_ptr));
+    ///
+    /// assert!(fn_value.verify(true));
+    /// ```
+    pub fn build_struct_gep(&self, ptr: &PointerValue, index: u32, name: &str) -> Option<PointerValue> {
+        let is_valid_index = unsafe {
+            let pointee_type = LLVMGetElementType(LLVMTypeOf(ptr.as_value_ref()));
+
+            LLVMGetTypeKind(pointee_type) == LLVMTypeKind::LLVMStructTypeKind &&
+            index < LLVMCountStructElementTypes(pointee_type)
+        };
+
+        if !is_valid_index {
+            return None;
+        }
+
         let c_string = CString::new(name).expect("Conversion to CString failed unexpectedly");
 
-        let value = LLVMBuildStructGEP(self.builder, ptr.as_value_ref(), index, c_string.as_ptr());
+        let value = unsafe {
+            LLVMBuildStructGEP(self.builder, ptr.as_value_ref(), index, c_string.as_ptr())
+        };
 
-        PointerValue::new(value)
+        Some(PointerValue::new(value))
     }
 
     pub fn build_ptr_diff(&self, lhs_ptr: &PointerValue, rhs_ptr: &PointerValue, name: &str) -> IntValue {
@@ -139,6 +235,8 @@ impl Builder {
         IntValue::new(value)
     }
 
+    /// Builds a phi node of the given type at the current position. Incoming edges are added afterwards
+    /// through `PhiValue::add_incoming`. Phi nodes must be grouped at the start of their `BasicBlock`.
     pub fn build_phi<T: BasicType>(&self, type_: &T, name: &str) -> PhiValue {
         let c_string = CString::new(name).expect("Conversion to CString failed unexpectedly");
 
@@ -167,7 +265,9 @@ impl Builder {
         BasicValueEnum::new(value)
     }
 
-    // TODOC: Stack allocation
+    /// Builds a stack allocation of the given type at the current position. The allocated memory lives
+    /// until the function returns, so allocas built inside of a loop grow the stack on every iteration.
+    /// See `build_entry_alloca` to avoid this.
     pub fn build_alloca<T: BasicType>(&self, ty: T, name: &str) -> PointerValue {
         let c_string = CString::new(name).expect("Conversion to CString failed unexpectedly");
 
@@ -178,7 +278,58 @@ impl Builder {
         PointerValue::new(value)
     }
 
-    // TODOC: Stack allocation
+    /// Builds a stack allocation of the given type at the start of the entry block of the function
+    /// currently being built, regardless of this `Builder`'s position, which is left unchanged.
+    /// Allocas in the entry block are allocated once per call and can be promoted to registers by
+    /// the `mem2reg` pass.
+    ///
+    /// # Panics
+    ///
+    /// Panics if this `Builder` is not positioned in a `BasicBlock` belonging to a function.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use inkwell::context::Context;
+    ///
+    /// let context = Context::create();
+    /// let module = context.create_module("my_mod");
+    /// let builder = context.create_builder();
+    /// let i32_type = context.i32_type();
+    /// let fn_type = context.void_type().fn_type(&[], false);
+    /// let fn_value = module.add_function("my_fn", &fn_type, None);
+    /// let entry = fn_value.append_basic_block("entry");
+    /// let loop_body = fn_value.append_basic_block("loop_body");
+    ///
+    /// builder.position_at_end(&entry);
+    /// builder.build_unconditional_branch(&loop_body);
+    /// builder.position_at_end(&loop_body);
+    ///
+    /// let counter = builder.build_entry_alloca(i32_type, "counter");
+    ///
+    /// assert_eq!(entry.get_first_instruction(), counter.as_instruction());
+    /// assert_eq!(builder.get_insert_block(), Some(loop_body));
+    /// ```
+    pub fn build_entry_alloca<T: BasicType>(&self, ty: T, name: &str) -> PointerValue {
+        let entry = self.get_insert_block()
+                        .and_then(|block| block.get_parent())
+                        .and_then(|function| function.get_entry_basic_block())
+                        .expect("Builder must be positioned within a function to build an entry alloca");
+
+        // A separate builder is used so that our own position is preserved exactly
+        let entry_builder = entry.get_context().create_builder();
+
+        match entry.get_first_instruction() {
+            Some(first_instruction) => entry_builder.position_before(&first_instruction),
+            None => entry_builder.position_at_end(&entry),
+        }
+
+        entry_builder.build_alloca(ty, name)
+    }
+
+    /// Builds a stack allocation of `size` consecutive elements of the given type at the current position,
+    /// returning a pointer to the first element. `size` need not be a constant, which allows for
+    /// dynamically sized local arrays.
     pub fn build_array_alloca<T: BasicType>(&self, ty: T, size: IntValue, name: &str) -> PointerValue {
         let c_string = CString::new(name).expect("Conversion to CString failed unexpectedly");
 
@@ -189,8 +340,45 @@ impl Builder {
         PointerValue::new(value)
     }
 
-    // TODOC: Heap allocation
+    /// Builds a heap allocation of the given type through a call to `malloc`, which is declared in the
+    /// current `Module` if needed. The allocation size is computed from the `Module`'s `DataLayout` once
+    /// it is compiled, so that `DataLayout` should match the target, ie the `ExecutionEngine`'s.
+    /// Memory allocated this way should be released with `build_free`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if this `Builder` is not positioned in a `BasicBlock` or if the type is not sized.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use inkwell::context::Context;
+    ///
+    /// let context = Context::create();
+    /// let module = context.create_module("my_mod");
+    /// let builder = context.create_builder();
+    /// let i64_type = context.i64_type();
+    /// let fn_type = context.void_type().fn_type(&[&i64_type], false);
+    /// let fn_value = module.add_function("my_fn", &fn_type, None);
+    /// let entry = fn_value.append_basic_block("entry");
+    /// let len = fn_value.get_first_param().unwrap().into_int_value();
+    ///
+    /// builder.position_at_end(&entry);
+    ///
+    /// let single = builder.build_malloc(i64_type, "single");
+    /// let array = builder.build_array_malloc(i64_type, len, "array");
+    ///
+    /// builder.build_free(&single);
+    /// builder.build_free(&array);
+    /// builder.build_return(None);
+    ///
+    /// assert!(module.get_function("malloc").is_some());
+    /// assert!(module.get_function("free").is_some());
+    /// assert!(fn_value.verify(false));
+    /// ```
     pub fn build_malloc<T: BasicType>(&self, ty: T, name: &str) -> PointerValue {
+        self.assert_can_malloc(&ty);
+
         let c_string = CString::new(name).expect("Conversion to CString failed unexpectedly");
 
         let value = unsafe {
@@ -200,8 +388,15 @@ impl Builder {
         PointerValue::new(value)
     }
 
-    // TODOC: Heap allocation
+    /// Builds a heap allocation of `size` consecutive elements of the given type through a call to `malloc`.
+    /// See `build_malloc` for details.
+    ///
+    /// # Panics
+    ///
+    /// Panics if this `Builder` is not positioned in a `BasicBlock` or if the type is not sized.
     pub fn build_array_malloc<T: BasicType>(&self, ty: T, size: IntValue, name: &str) -> PointerValue {
+        self.assert_can_malloc(&ty);
+
         let c_string = CString::new(name).expect("Conversion to CString failed unexpectedly");
 
         let value = unsafe {
@@ -211,8 +406,17 @@ impl Builder {
         PointerValue::new(value)
     }
 
+    /// Builds a call to `free` on a pointer, such as one returned by `build_malloc`. `free` is declared
+    /// in the current `Module` if needed.
+    ///
+    /// # Panics
+    ///
+    /// Panics if this `Builder` is not positioned in a `BasicBlock`.
     // SubType: <P>(&self, ptr: &PointerValue<P>) -> InstructionValue {
     pub fn build_free(&self, ptr: &PointerValue) -> InstructionValue {
+        // LLVM needs the insert block to find the Module to declare free in
+        assert!(self.get_insert_block().is_some(), "Builder must be positioned in a BasicBlock to build a free");
+
         let val = unsafe {
             LLVMBuildFree(self.builder, ptr.as_value_ref())
         };
@@ -220,6 +424,17 @@ impl Builder {
         InstructionValue::new(val)
     }
 
+    // LLVM needs the insert block to find the Module to declare malloc in, and the type's size to call it with
+    fn assert_can_malloc<T: BasicType>(&self, ty: &T) {
+        assert!(self.get_insert_block().is_some(), "Builder must be positioned in a BasicBlock to build a malloc");
+
+        let is_sized = unsafe {
+            LLVMTypeIsSized(ty.as_type_ref()) == 1
+        };
+
+        assert!(is_sized, "Cannot malloc an unsized type");
+    }
+
     pub fn insert_instruction(&self, instruction: &InstructionValue, name: Option<&str>) {
         match name {
             Some(name) => {
@@ -376,7 +591,18 @@ impl Builder {
     }
 
     // REVIEW: Consolidate these two casts into one via subtypes
+    /// Builds a conversion of a float (or float vector) to an unsigned int (or int vector), rounding towards zero.
+    ///
+    /// # Panics
+    ///
+    /// Panics if only one of the two types is a vector, or if their vector sizes differ.
     pub fn build_float_to_unsigned_int<T: FloatMathValue>(&self, float: T, int_type: <T::BaseType as FloatMathType>::MathConvType, name: &str) -> <<T::BaseType as FloatMathType>::MathConvType as IntMathType>::ValueType {
+        let (src_type, dst_type) = unsafe {
+            (LLVMTypeOf(float.as_value_ref()), int_type.as_type_ref())
+        };
+
+        assert_eq!(get_vector_size(src_type), get_vector_size(dst_type), "Cast source and destination types must have the same number of vector elements");
+
         let c_string = CString::new(name).expect("Conversion to CString failed unexpectedly");
 
         let value = unsafe {
@@ -386,7 +612,18 @@ impl Builder {
         <<T::BaseType as FloatMathType>::MathConvType as IntMathType>::ValueType::new(value)
     }
 
+    /// Builds a conversion of a float (or float vector) to a signed int (or int vector), rounding towards zero.
+    ///
+    /// # Panics
+    ///
+    /// Panics if only one of the two types is a vector, or if their vector sizes differ.
     pub fn build_float_to_signed_int<T: FloatMathValue>(&self, float: T, int_type: <T::BaseType as FloatMathType>::MathConvType, name: &str) -> <<T::BaseType as FloatMathType>::MathConvType as IntMathType>::ValueType {
+        let (src_type, dst_type) = unsafe {
+            (LLVMTypeOf(float.as_value_ref()), int_type.as_type_ref())
+        };
+
+        assert_eq!(get_vector_size(src_type), get_vector_size(dst_type), "Cast source and destination types must have the same number of vector elements");
+
         let c_string = CString::new(name).expect("Conversion to CString failed unexpectedly");
 
         let value = unsafe {
@@ -397,7 +634,18 @@ impl Builder {
     }
 
     // REVIEW: Consolidate these two casts into one via subtypes
+    /// Builds a conversion of an int (or int vector), interpreted as unsigned, to a float (or float vector).
+    ///
+    /// # Panics
+    ///
+    /// Panics if only one of the two types is a vector, or if their vector sizes differ.
     pub fn build_unsigned_int_to_float<T: IntMathValue>(&self, int: T, float_type: <T::BaseType as IntMathType>::MathConvType, name: &str) -> <<T::BaseType as IntMathType>::MathConvType as FloatMathType>::ValueType {
+        let (src_type, dst_type) = unsafe {
+            (LLVMTypeOf(int.as_value_ref()), float_type.as_type_ref())
+        };
+
+        assert_eq!(get_vector_size(src_type), get_vector_size(dst_type), "Cast source and destination types must have the same number of vector elements");
+
         let c_string = CString::new(name).expect("Conversion to CString failed unexpectedly");
 
         let value = unsafe {
@@ -407,7 +655,18 @@ impl Builder {
         <<T::BaseType as IntMathType>::MathConvType as FloatMathType>::ValueType::new(value)
     }
 
+    /// Builds a conversion of an int (or int vector), interpreted as signed, to a float (or float vector).
+    ///
+    /// # Panics
+    ///
+    /// Panics if only one of the two types is a vector, or if their vector sizes differ.
     pub fn build_signed_int_to_float<T: IntMathValue>(&self, int: T, float_type: <T::BaseType as IntMathType>::MathConvType, name: &str) -> <<T::BaseType as IntMathType>::MathConvType as FloatMathType>::ValueType {
+        let (src_type, dst_type) = unsafe {
+            (LLVMTypeOf(int.as_value_ref()), float_type.as_type_ref())
+        };
+
+        assert_eq!(get_vector_size(src_type), get_vector_size(dst_type), "Cast source and destination types must have the same number of vector elements");
+
         let c_string = CString::new(name).expect("Conversion to CString failed unexpectedly");
 
         let value = unsafe {
@@ -417,7 +676,19 @@ impl Builder {
         <<T::BaseType as IntMathType>::MathConvType as FloatMathType>::ValueType::new(value)
     }
 
+    /// Builds a truncation of a float (or float vector) to a smaller float type, ie `f64` to `f32`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `float_type` is not smaller than the type of `float`, or if the vector sizes differ.
     pub fn build_float_trunc<T: FloatMathValue>(&self, float: T, float_type: T::BaseType, name: &str) -> T {
+        let (src_type, dst_type) = unsafe {
+            (LLVMTypeOf(float.as_value_ref()), float_type.as_type_ref())
+        };
+
+        assert_eq!(get_vector_size(src_type), get_vector_size(dst_type), "Cast source and destination types must have the same number of vector elements");
+        assert!(get_float_bit_width(src_type) > get_float_bit_width(dst_type), "Float truncation requires a smaller destination type");
+
         let c_string = CString::new(name).expect("Conversion to CString failed unexpectedly");
 
         let value = unsafe {
@@ -427,7 +698,19 @@ impl Builder {
         T::new(value)
     }
 
+    /// Builds an extension of a float (or float vector) to a larger float type, ie `f32` to `f64`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `float_type` is not larger than the type of `float`, or if the vector sizes differ.
     pub fn build_float_ext<T: FloatMathValue>(&self, float: T, float_type: T::BaseType, name: &str) -> T {
+        let (src_type, dst_type) = unsafe {
+            (LLVMTypeOf(float.as_value_ref()), float_type.as_type_ref())
+        };
+
+        assert_eq!(get_vector_size(src_type), get_vector_size(dst_type), "Cast source and destination types must have the same number of vector elements");
+        assert!(get_float_bit_width(src_type) < get_float_bit_width(dst_type), "Float extension requires a larger destination type");
+
         let c_string = CString::new(name).expect("Conversion to CString failed unexpectedly");
 
         let value = unsafe {
@@ -827,6 +1110,118 @@ impl Builder {
         InstructionValue::new(value)
     }
 
+    /// Builds a select instruction, which evaluates to `then_value` if `condition` is true and to
+    /// `else_value` otherwise, without branching. A vector of bools can be used as the `condition`
+    /// to select each element of vector values individually.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `then_value` and `else_value` have different types, if `condition` is not a bool
+    /// or a vector of bools, or if a vector `condition` is not the same size as the vector values.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use inkwell::IntPredicate;
+    /// use inkwell::context::Context;
+    ///
+    /// let context = Context::create();
+    /// let module = context.create_module("my_mod");
+    /// let builder = context.create_builder();
+    /// let i32_type = context.i32_type();
+    /// let fn_type = i32_type.fn_type(&[&i32_type, &i32_type], false);
+    /// let fn_value = module.add_function("max", &fn_type, None);
+    /// let entry = fn_value.append_basic_block("entry");
+    /// let lhs = fn_value.get_first_param().unwrap().into_int_value();
+    /// let rhs = fn_value.get_last_param().unwrap().into_int_value();
+    ///
+    /// builder.position_at_end(&entry);
+    ///
+    /// let is_greater = builder.build_int_compare(IntPredicate::SGT, lhs, rhs, "is_greater");
+    /// let max = builder.build_select(is_greater, &lhs, &rhs, "max");
+    ///
+    /// builder.build_return(Some(&max));
+    ///
+    /// assert!(fn_value.verify(false));
+    /// ```
+    pub fn build_select<T: IntMathValue>(&self, condition: T, then_value: &BasicValue, else_value: &BasicValue, name: &str) -> BasicValueEnum {
+        let (condition_type, value_type) = unsafe {
+            assert_eq!(LLVMTypeOf(then_value.as_value_ref()), LLVMTypeOf(else_value.as_value_ref()), "Select values must have the same type");
+
+            (LLVMTypeOf(condition.as_value_ref()), LLVMTypeOf(then_value.as_value_ref()))
+        };
+
+        let condition_bit_width = unsafe {
+            match LLVMGetTypeKind(condition_type) {
+                LLVMTypeKind::LLVMVectorTypeKind => LLVMGetIntTypeWidth(LLVMGetElementType(condition_type)),
+                _ => LLVMGetIntTypeWidth(condition_type),
+            }
+        };
+
+        assert_eq!(condition_bit_width, 1, "Select condition must be a bool or a vector of bools");
+
+        if let Some(size) = get_vector_size(condition_type) {
+            assert_eq!(Some(size), get_vector_size(value_type), "Select condition vector must be the same size as the value vectors");
+        }
+
+        let c_string = CString::new(name).expect("Conversion to CString failed unexpectedly");
+
+        let value = unsafe {
+            LLVMBuildSelect(self.builder, condition.as_value_ref(), then_value.as_value_ref(), else_value.as_value_ref(), c_string.as_ptr())
+        };
+
+        BasicValueEnum::new(value)
+    }
+
+    /// Builds an indirect branch to the block address held by `address`, such as one obtained from
+    /// `FunctionValue::get_block_address`. `destinations` must contain every `BasicBlock` that `address`
+    /// may point to, and each must belong to the same function as the current insert block.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use inkwell::context::Context;
+    ///
+    /// let context = Context::create();
+    /// let module = context.create_module("my_mod");
+    /// let builder = context.create_builder();
+    /// let void_type = context.void_type();
+    /// let fn_type = void_type.fn_type(&[], false);
+    /// let fn_value = module.add_function("my_fn", &fn_type, None);
+    /// let entry = fn_value.append_basic_block("entry");
+    /// let target = fn_value.append_basic_block("target");
+    /// let address = fn_value.get_block_address(&target).unwrap();
+    ///
+    /// builder.position_at_end(&entry);
+    /// builder.build_indirect_branch(&address, &[&target]);
+    ///
+    /// builder.position_at_end(&target);
+    /// builder.build_return(None);
+    ///
+    /// assert!(fn_value.verify(true));
+    /// ```
+    pub fn build_indirect_branch(&self, address: &PointerValue, destinations: &[&BasicBlock]) -> InstructionValue {
+        let parent = self.get_insert_block().and_then(|block| block.get_parent());
+
+        assert!(parent.is_some(), "Builder must be positioned in a function to build an indirect branch");
+
+        for destination in destinations {
+            assert!(destination.get_parent() == parent, "Indirect branch destinations must belong to the same function as the current insert block");
+        }
+
+        let value = unsafe {
+            LLVMBuildIndirectBr(self.builder, address.as_value_ref(), destinations.len() as u32)
+        };
+
+        for destination in destinations {
+            unsafe {
+                LLVMAddDestination(value, destination.basic_block)
+            }
+        }
+
+        InstructionValue::new(value)
+    }
+
     // SubType: <I>(&self, value: &IntValue<I>, name) -> IntValue<I> {
     pub fn build_int_neg<T: IntMathValue>(&self, value: &T, name: &str) -> T {
         let c_string = CString::new(name).expect("Conversion to CString failed unexpectedly");
@@ -903,30 +1298,108 @@ impl Builder {
         }
     }
 
-    // REVIEW: How does LLVM treat out of bound index? Maybe we should return an Option?
-    // or is that only in bounds GEP
-    // REVIEW: Should this be AggregatePointerValue?
-    pub fn build_extract_value(&self, value: &AggregateValue, index: u32, name: &str) -> BasicValueEnum {
+    /// Builds an `extractvalue` instruction, which gets the element of a struct or array value at `index`.
+    ///
+    /// Returns `None` if `index` is out of bounds for `value`, rather than building invalid IR.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use inkwell::context::Context;
+    ///
+    /// let context = Context::create();
+    /// let module = context.create_module("pairs");
+    /// let builder = context.create_builder();
+    /// let i32_type = context.i32_type();
+    /// let pair_type = context.struct_type(&[i32_type.into(), i32_type.into()], false);
+    /// let fn_type = i32_type.fn_type(&[&pair_type], false);
+    /// let fn_value = module.add_function("second", &fn_type, None);
+    /// let entry = context.append_basic_block(&fn_value, "entry");
+    ///
+    /// builder.position_at_end(&entry);
+    ///
+    /// let pair = fn_value.get_first_param().unwrap().into_struct_value();
+    ///
+    /// assert!(builder.build_extract_value(&pair, 2, "third").is_none());
+    ///
+    /// let second = builder.build_extract_value(&pair, 1, "second").unwrap();
+    ///
+    /// builder.build_return(Some(&second));
+    ///
+    /// assert!(module.verify().is_ok());
+    /// ```
+    pub fn build_extract_value(&self, value: &AggregateValue, index: u32, name: &str) -> Option<BasicValueEnum> {
+        if index >= Builder::count_aggregate_elements(value) {
+            return None;
+        }
+
         let c_string = CString::new(name).expect("Conversion to CString failed unexpectedly");
 
         let value = unsafe {
             LLVMBuildExtractValue(self.builder, value.as_value_ref(), index, c_string.as_ptr())
         };
 
-        BasicValueEnum::new(value)
+        Some(BasicValueEnum::new(value))
     }
 
-    // REVIEW: Should this be AggregatePointerValue instead of just PointerValue?
-    pub fn build_insert_value(&self, value: &BasicValue, ptr: &PointerValue, index: u32, name: &str) -> InstructionValue {
+    /// Builds an `insertvalue` instruction, which produces a copy of the struct or array `value` with
+    /// its element at `index` replaced by `element`.
+    ///
+    /// Returns `None` if `index` is out of bounds for `value`, rather than building invalid IR.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use inkwell::context::Context;
+    ///
+    /// let context = Context::create();
+    /// let module = context.create_module("pairs");
+    /// let builder = context.create_builder();
+    /// let i32_type = context.i32_type();
+    /// let pair_type = context.struct_type(&[i32_type.into(), i32_type.into()], false);
+    /// let fn_type = pair_type.fn_type(&[&i32_type, &i32_type], false);
+    /// let fn_value = module.add_function("make_pair", &fn_type, None);
+    /// let entry = context.append_basic_block(&fn_value, "entry");
+    ///
+    /// builder.position_at_end(&entry);
+    ///
+    /// let first = fn_value.get_first_param().unwrap();
+    /// let second = fn_value.get_last_param().unwrap();
+    /// let pair = builder.build_insert_value(&pair_type.get_undef(), &first, 0, "pair").unwrap();
+    /// let pair = builder.build_insert_value(&pair, &second, 1, "pair").unwrap();
+    ///
+    /// builder.build_return(Some(&pair));
+    ///
+    /// assert!(module.verify().is_ok());
+    /// ```
+    pub fn build_insert_value(&self, value: &AggregateValue, element: &BasicValue, index: u32, name: &str) -> Option<AggregateValueEnum> {
+        if index >= Builder::count_aggregate_elements(value) {
+            return None;
+        }
+
         let c_string = CString::new(name).expect("Conversion to CString failed unexpectedly");
 
         let value = unsafe {
-            LLVMBuildInsertValue(self.builder, value.as_value_ref(), ptr.as_value_ref(), index, c_string.as_ptr())
+            LLVMBuildInsertValue(self.builder, value.as_value_ref(), element.as_value_ref(), index, c_string.as_ptr())
         };
 
-        InstructionValue::new(value)
+        Some(AggregateValueEnum::new(value))
     }
 
+    fn count_aggregate_elements(value: &AggregateValue) -> u32 {
+        unsafe {
+            let type_ = LLVMTypeOf(value.as_value_ref());
+
+            match LLVMGetTypeKind(type_) {
+                LLVMTypeKind::LLVMArrayTypeKind => LLVMGetArrayLength(type_),
+                LLVMTypeKind::LLVMStructTypeKind => LLVMCountStructElementTypes(type_),
+                _ => unreachable!("AggregateValue should always be a struct or array"),
+            }
+        }
+    }
+
+    /// Builds an instruction which extracts the lane of `vector` at `index`. The result is undefined
+    /// if `index` is out of bounds.
     pub fn build_extract_element(&self, vector: &VectorValue, index: &IntValue, name: &str) -> BasicValueEnum {
         let c_string = CString::new(name).expect("Conversion to CString failed unexpectedly");
 
@@ -937,6 +1410,8 @@ impl Builder {
         BasicValueEnum::new(value)
     }
 
+    /// Builds an instruction which returns a copy of `vector` with the lane at `index` replaced by `element`,
+    /// which must be of `vector`'s element type. The result is undefined if `index` is out of bounds.
     pub fn build_insert_element(&self, vector: &VectorValue, element: &BasicValue, index: &IntValue, name: &str) -> BasicValueEnum {
         let c_string = CString::new(name).expect("Conversion to CString failed unexpectedly");
 
@@ -947,6 +1422,69 @@ impl Builder {
         BasicValueEnum::new(value)
     }
 
+    /// Builds a shuffle of the lanes of `left` and `right`, which must have the same type, into a new vector
+    /// with as many lanes as `mask`. Each lane of `mask` is an index into the concatenation of `left` and
+    /// `right`, or undef if the resulting lane doesn't matter.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `mask` is not a constant vector of i32s.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use inkwell::context::Context;
+    /// use inkwell::types::{BasicType, VectorType};
+    ///
+    /// let context = Context::create();
+    /// let module = context.create_module("my_mod");
+    /// let builder = context.create_builder();
+    /// let i32_type = context.i32_type();
+    /// let i32_vec_type = i32_type.vec_type(4);
+    /// let fn_type = i32_vec_type.fn_type(&[&i32_vec_type, &i32_vec_type], false);
+    /// let fn_value = module.add_function("interleave_low", &fn_type, None);
+    /// let entry = fn_value.append_basic_block("entry");
+    /// let left = fn_value.get_first_param().unwrap().into_vector_value();
+    /// let right = fn_value.get_last_param().unwrap().into_vector_value();
+    /// let mask = VectorType::const_vector(&[
+    ///     i32_type.const_int(0, false),
+    ///     i32_type.const_int(4, false),
+    ///     i32_type.const_int(1, false),
+    ///     i32_type.const_int(5, false),
+    /// ]);
+    ///
+    /// builder.position_at_end(&entry);
+    ///
+    /// let shuffled = builder.build_shuffle_vector(&left, &right, &mask, "shuffled");
+    ///
+    /// builder.build_return(Some(&shuffled));
+    ///
+    /// assert!(fn_value.verify(true));
+    /// ```
+    pub fn build_shuffle_vector(&self, left: &VectorValue, right: &VectorValue, mask: &VectorValue, name: &str) -> VectorValue {
+        let is_valid_mask = unsafe {
+            let element_type = LLVMGetElementType(LLVMTypeOf(mask.as_value_ref()));
+
+            LLVMIsConstant(mask.as_value_ref()) == 1 &&
+            LLVMGetTypeKind(element_type) == LLVMTypeKind::LLVMIntegerTypeKind &&
+            LLVMGetIntTypeWidth(element_type) == 32
+        };
+
+        assert!(is_valid_mask, "Shuffle vector mask must be a constant vector of i32s");
+        assert_eq!(left.get_type(), right.get_type(), "Shuffled vectors must have the same type");
+
+        let c_string = CString::new(name).expect("Conversion to CString failed unexpectedly");
+
+        let value = unsafe {
+            LLVMBuildShuffleVector(self.builder, left.as_value_ref(), right.as_value_ref(), mask.as_value_ref(), c_string.as_ptr())
+        };
+
+        VectorValue::new(value)
+    }
+
+    // TODO: Support the freeze instruction (ie build_freeze(&self, value: &BasicValue, name: &str) -> BasicValueEnum)
+    // once we support LLVM 10.0, which is the first version to have both the instruction and LLVMBuildFreeze.
+    // It should be gated behind the llvm10-0 (and later) features, as none of the versions supported so far have it
     pub fn build_unreachable(&self) -> InstructionValue {
         let val = unsafe {
             LLVMBuildUnreachable(self.builder)
@@ -955,18 +1493,124 @@ impl Builder {
         InstructionValue::new(val)
     }
 
-    // REVIEW: Not sure if this should return InstructionValue or an actual value
-    // TODO: Better name for num?
-    pub fn build_fence(&self, atmoic_ordering: LLVMAtomicOrdering, num: i32, name: &str) -> InstructionValue {
+    /// Builds a `fence` instruction, which orders the memory operations around it according to `ordering`.
+    /// If `single_thread` is true, it only synchronizes with code running in the same thread, such as signal handlers.
+    ///
+    /// `ordering` must be `AtomicOrdering::Acquire`, `AtomicOrdering::Release`, `AtomicOrdering::AcquireRelease`
+    /// or `AtomicOrdering::SequentiallyConsistent`.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use inkwell::AtomicOrdering;
+    /// use inkwell::context::Context;
+    ///
+    /// let context = Context::create();
+    /// let module = context.create_module("fences");
+    /// let builder = context.create_builder();
+    /// let fn_type = context.void_type().fn_type(&[], false);
+    /// let fn_value = module.add_function("barrier", &fn_type, None);
+    /// let entry = context.append_basic_block(&fn_value, "entry");
+    ///
+    /// builder.position_at_end(&entry);
+    /// builder.build_fence(AtomicOrdering::AcquireRelease, false, "");
+    /// builder.build_return(None);
+    ///
+    /// assert!(module.verify().is_ok());
+    /// ```
+    pub fn build_fence(&self, ordering: AtomicOrdering, single_thread: bool, name: &str) -> InstructionValue {
         let c_string = CString::new(name).expect("Conversion to CString failed unexpectedly");
 
         let val = unsafe {
-            LLVMBuildFence(self.builder, atmoic_ordering, num, c_string.as_ptr())
+            LLVMBuildFence(self.builder, ordering.as_llvm_ordering(), single_thread as i32, c_string.as_ptr())
         };
 
         InstructionValue::new(val)
     }
 
+    /// Builds an `atomicrmw` instruction, which atomically applies `op` to the integer `ptr` points to
+    /// and `value`, storing the result back to `ptr`. Returns the value `ptr` pointed to beforehand.
+    ///
+    /// `ordering` must be at least `AtomicOrdering::Monotonic`, and `value` must be of the type `ptr` points to.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use inkwell::{AddressSpace, AtomicOrdering, AtomicRMWBinOp};
+    /// use inkwell::context::Context;
+    ///
+    /// let context = Context::create();
+    /// let module = context.create_module("counter");
+    /// let builder = context.create_builder();
+    /// let i32_type = context.i32_type();
+    /// let i32_ptr_type = i32_type.ptr_type(AddressSpace::Generic);
+    /// let fn_type = i32_type.fn_type(&[&i32_ptr_type], false);
+    /// let fn_value = module.add_function("fetch_increment", &fn_type, None);
+    /// let entry = context.append_basic_block(&fn_value, "entry");
+    ///
+    /// builder.position_at_end(&entry);
+    ///
+    /// let counter = fn_value.get_first_param().unwrap().into_pointer_value();
+    /// let one = i32_type.const_int(1, false);
+    /// let previous = builder.build_atomic_rmw(AtomicRMWBinOp::Add, &counter, &one, AtomicOrdering::SequentiallyConsistent);
+    ///
+    /// builder.build_return(Some(&previous));
+    ///
+    /// assert!(module.verify().is_ok());
+    /// ```
+    // REVIEW: Should we support single threaded (ie syncscope("singlethread")) operations too?
+    pub fn build_atomic_rmw(&self, op: AtomicRMWBinOp, ptr: &PointerValue, value: &IntValue, ordering: AtomicOrdering) -> IntValue {
+        let value = unsafe {
+            LLVMBuildAtomicRMW(self.builder, op.as_llvm_bin_op(), ptr.as_value_ref(), value.as_value_ref(), ordering.as_llvm_ordering(), false as i32)
+        };
+
+        IntValue::new(value)
+    }
+
+    /// Builds a `cmpxchg` instruction, which atomically compares the integer `ptr` points to with
+    /// `cmp` and, if they are equal, stores `new` to `ptr`. Returns a `{ value, success }` struct of the
+    /// value `ptr` pointed to beforehand and an `i1` which is true if `new` was stored.
+    ///
+    /// Both orderings must be at least `AtomicOrdering::Monotonic`, and `failure_ordering` can be neither
+    /// stronger than `success_ordering` nor `AtomicOrdering::Release` or `AtomicOrdering::AcquireRelease`.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use inkwell::{AddressSpace, AtomicOrdering};
+    /// use inkwell::context::Context;
+    ///
+    /// let context = Context::create();
+    /// let module = context.create_module("lock");
+    /// let builder = context.create_builder();
+    /// let bool_type = context.bool_type();
+    /// let i32_type = context.i32_type();
+    /// let i32_ptr_type = i32_type.ptr_type(AddressSpace::Generic);
+    /// let fn_type = bool_type.fn_type(&[&i32_ptr_type], false);
+    /// let fn_value = module.add_function("try_lock", &fn_type, None);
+    /// let entry = context.append_basic_block(&fn_value, "entry");
+    ///
+    /// builder.position_at_end(&entry);
+    ///
+    /// let lock = fn_value.get_first_param().unwrap().into_pointer_value();
+    /// let unlocked = i32_type.const_int(0, false);
+    /// let locked = i32_type.const_int(1, false);
+    /// let result = builder.build_cmpxchg(&lock, &unlocked, &locked, AtomicOrdering::Acquire, AtomicOrdering::Monotonic);
+    /// let success = builder.build_extract_value(&result, 1, "success").unwrap();
+    ///
+    /// builder.build_return(Some(&success));
+    ///
+    /// assert!(module.verify().is_ok());
+    /// ```
+    #[cfg(not(any(feature = "llvm3-6", feature = "llvm3-7", feature = "llvm3-8")))]
+    pub fn build_cmpxchg(&self, ptr: &PointerValue, cmp: &IntValue, new: &IntValue, success_ordering: AtomicOrdering, failure_ordering: AtomicOrdering) -> StructValue {
+        let value = unsafe {
+            LLVMBuildAtomicCmpXchg(self.builder, ptr.as_value_ref(), cmp.as_value_ref(), new.as_value_ref(), success_ordering.as_llvm_ordering(), failure_ordering.as_llvm_ordering(), false as i32)
+        };
+
+        StructValue::new(value)
+    }
+
     // SubType: <P>(&self, ptr: &PointerValue<P>, name) -> IntValue<bool> {
     pub fn build_is_null<T: PointerMathValue>(&self, ptr: T, name: &str) -> <<T::BaseType as PointerMathType>::PtrConvType as IntMathType>::ValueType {
         let c_string = CString::new(name).expect("Conversion to CString failed unexpectedly");
@@ -1017,10 +1661,97 @@ impl Builder {
         }
     }
 
+    /// Sets the source location attached to every instruction this `Builder` creates from now on.
+    #[cfg(not(any(feature = "llvm3-6", feature = "llvm3-7", feature = "llvm3-8", feature = "llvm3-9", feature = "llvm4-0", feature = "llvm5-0")))]
+    pub fn set_current_debug_location(&self, location: &DILocation) {
+        unsafe {
+            LLVMSetCurrentDebugLocation(self.builder, location.as_value_ref())
+        }
+    }
+
+    /// Gets the source location currently attached to the instructions this `Builder` creates, if any.
+    #[cfg(not(any(feature = "llvm3-6", feature = "llvm3-7", feature = "llvm3-8", feature = "llvm3-9", feature = "llvm4-0", feature = "llvm5-0")))]
+    pub fn get_current_debug_location(&self) -> Option<DILocation> {
+        let location = unsafe {
+            LLVMGetCurrentDebugLocation(self.builder)
+        };
+
+        // Without a location, LLVM returns an empty metadata node rather than null
+        if location.is_null() || unsafe { LLVMGetMDNodeNumOperands(location) } == 0 {
+            return None;
+        }
+
+        let (context, metadata_ref) = unsafe {
+            (LLVMGetTypeContext(LLVMTypeOf(location)), LLVMValueAsMetadata(location))
+        };
+
+        Some(DILocation::new(context, metadata_ref))
+    }
+
+    /// Stops attaching a source location to the instructions this `Builder` creates.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use inkwell::context::Context;
+    ///
+    /// let context = Context::create();
+    /// let builder = context.create_builder();
+    ///
+    /// builder.unset_current_debug_location();
+    ///
+    /// assert!(builder.get_current_debug_location().is_none());
+    /// ```
+    #[cfg(not(any(feature = "llvm3-6", feature = "llvm3-7", feature = "llvm3-8", feature = "llvm3-9", feature = "llvm4-0", feature = "llvm5-0")))]
+    pub fn unset_current_debug_location(&self) {
+        unsafe {
+            LLVMSetCurrentDebugLocation(self.builder, ptr::null_mut())
+        }
+    }
+
     // REVIEW: Returning InstructionValue is the safe move here; but if the value means something
     // (IE the result of the switch) it should probably return BasicValueEnum?
-    // SubTypes: I think value and case values must be the same subtype (maybe). Case value might need to be constants
+    // SubTypes: value and case values must be the same subtype, and case values must be constants
+    /// Builds a switch instruction which jumps to the `BasicBlock` of the first case whose value is equal
+    /// to `value`, or to `else_block` if there is none. Cases are added in the given order, and each case
+    /// value must be a unique constant of the same type as `value`. With no cases, this is equivalent to
+    /// an unconditional branch to `else_block`.
+    ///
+    /// Unlike a chain of conditional branches, a switch may be lowered into a jump table by LLVM.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use inkwell::context::Context;
+    ///
+    /// let context = Context::create();
+    /// let module = context.create_module("ret");
+    /// let builder = context.create_builder();
+    /// let i32_type = context.i32_type();
+    /// let fn_type = i32_type.fn_type(&[&i32_type], false);
+    /// let fn_value = module.add_function("ret", &fn_type, None);
+    /// let entry = fn_value.append_basic_block("entry");
+    /// let one = fn_value.append_basic_block("one");
+    /// let other = fn_value.append_basic_block("other");
+    /// let i32_one = i32_type.const_int(1, false);
+    /// let value = fn_value.get_first_param().unwrap().into_int_value();
+    ///
+    /// builder.position_at_end(&entry);
+    /// builder.build_switch(&value, &other, &[(&i32_one, &one)]);
+    ///
+    /// builder.position_at_end(&one);
+    /// builder.build_return(Some(&i32_one));
+    ///
+    /// builder.position_at_end(&other);
+    /// builder.build_return(Some(&i32_type.const_int(0, false)));
+    ///
+    /// assert!(fn_value.verify(true));
+    /// ```
     pub fn build_switch(&self, value: &IntValue, else_block: &BasicBlock, cases: &[(&IntValue, &BasicBlock)]) -> InstructionValue {
+        for &(case_value, _) in cases {
+            assert!(unsafe { !LLVMIsAConstantInt(case_value.as_value_ref()).is_null() }, "Switch case values must be constant integers");
+        }
+
         let switch_value = unsafe {
             LLVMBuildSwitch(self.builder, value.as_value_ref(), else_block.basic_block, cases.len() as u32)
         };
@@ -1043,16 +1774,80 @@ impl Builder {
         GlobalValue::new(value)
     }
 
-    // REVIEW: Does this similar fn have the same issue build_global_string does? If so, mark as unsafe
-    // and fix with subtypes.
-    pub fn build_global_string_ptr(&self, value: &str, name: &str) -> GlobalValue {
+    /// Creates a private, null-terminated constant global string in the `Module` of the block this
+    /// `Builder` is positioned in, and returns a pointer to its first character. This is the usual way
+    /// to pass a string literal, such as a format string for `printf`, as an `i8*`.
+    ///
+    /// The returned `PointerValue` is a constant expression pointing into the new global rather than
+    /// the global itself, which can be found with `Module::get_global`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if this `Builder` is not positioned in a `BasicBlock`, as the string is added to its `Module`.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use inkwell::AddressSpace;
+    /// use inkwell::context::Context;
+    ///
+    /// let context = Context::create();
+    /// let module = context.create_module("my_mod");
+    /// let builder = context.create_builder();
+    /// let i8_ptr_type = context.i8_type().ptr_type(AddressSpace::Generic);
+    /// let fn_type = i8_ptr_type.fn_type(&[], false);
+    /// let fn_value = module.add_function("get_greeting", &fn_type, None);
+    /// let entry = fn_value.append_basic_block("entry");
+    ///
+    /// builder.position_at_end(&entry);
+    ///
+    /// let greeting = builder.build_global_string_ptr("Hello, World!\n", "greeting");
+    ///
+    /// builder.build_return(Some(&greeting));
+    ///
+    /// assert!(module.get_global("greeting").unwrap().is_constant());
+    /// assert!(module.verify().is_ok());
+    /// ```
+    pub fn build_global_string_ptr(&self, value: &str, name: &str) -> PointerValue {
+        assert!(self.get_insert_block().is_some(), "Builder must be positioned in a BasicBlock to build a global string");
+
         let c_string_value = CString::new(value).expect("Conversion to CString failed unexpectedly");
         let c_string_name = CString::new(name).expect("Conversion to CString failed unexpectedly");
         let value = unsafe {
             LLVMBuildGlobalStringPtr(self.builder, c_string_value.as_ptr(), c_string_name.as_ptr())
         };
 
-        GlobalValue::new(value)
+        PointerValue::new(value)
+    }
+}
+
+// Gets the number of elements of a vector type, or None for a scalar type
+fn get_vector_size(type_: LLVMTypeRef) -> Option<u32> {
+    unsafe {
+        match LLVMGetTypeKind(type_) {
+            LLVMTypeKind::LLVMVectorTypeKind => Some(LLVMGetVectorSize(type_)),
+            _ => None,
+        }
+    }
+}
+
+// Gets the bit width of a float type, or of the element type of a float vector type
+fn get_float_bit_width(type_: LLVMTypeRef) -> u32 {
+    let type_ = unsafe {
+        match LLVMGetTypeKind(type_) {
+            LLVMTypeKind::LLVMVectorTypeKind => LLVMGetElementType(type_),
+            _ => type_,
+        }
+    };
+
+    match unsafe { LLVMGetTypeKind(type_) } {
+        LLVMTypeKind::LLVMHalfTypeKind => 16,
+        LLVMTypeKind::LLVMFloatTypeKind => 32,
+        LLVMTypeKind::LLVMDoubleTypeKind => 64,
+        LLVMTypeKind::LLVMX86_FP80TypeKind => 80,
+        LLVMTypeKind::LLVMFP128TypeKind |
+        LLVMTypeKind::LLVMPPC_FP128TypeKind => 128,
+        _ => unreachable!("Expected a float type"),
     }
 }
 
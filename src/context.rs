@@ -1,16 +1,23 @@
 //! A `Context` is an opaque owner and manager of core global data.
 
-use llvm_sys::core::{LLVMAppendBasicBlockInContext, LLVMContextCreate, LLVMContextDispose, LLVMCreateBuilderInContext, LLVMDoubleTypeInContext, LLVMFloatTypeInContext, LLVMFP128TypeInContext, LLVMInsertBasicBlockInContext, LLVMInt16TypeInContext, LLVMInt1TypeInContext, LLVMInt32TypeInContext, LLVMInt64TypeInContext, LLVMInt8TypeInContext, LLVMIntTypeInContext, LLVMModuleCreateWithNameInContext, LLVMStructCreateNamed, LLVMStructTypeInContext, LLVMVoidTypeInContext, LLVMHalfTypeInContext, LLVMGetGlobalContext, LLVMPPCFP128TypeInContext, LLVMConstStructInContext, LLVMMDNodeInContext, LLVMMDStringInContext, LLVMGetMDKindIDInContext, LLVMX86FP80TypeInContext};
+use llvm_sys::core::{LLVMAppendBasicBlockInContext, LLVMContextCreate, LLVMContextDispose, LLVMCreateBuilderInContext, LLVMDoubleTypeInContext, LLVMFloatTypeInContext, LLVMFP128TypeInContext, LLVMInsertBasicBlockInContext, LLVMInt16TypeInContext, LLVMInt1TypeInContext, LLVMInt32TypeInContext, LLVMInt64TypeInContext, LLVMInt8TypeInContext, LLVMIntTypeInContext, LLVMModuleCreateWithNameInContext, LLVMStructCreateNamed, LLVMStructTypeInContext, LLVMVoidTypeInContext, LLVMHalfTypeInContext, LLVMGetGlobalContext, LLVMPPCFP128TypeInContext, LLVMConstStructInContext, LLVMMDNodeInContext, LLVMMDStringInContext, LLVMGetMDKindIDInContext, LLVMX86FP80TypeInContext, LLVMConstInlineAsm};
 use llvm_sys::prelude::{LLVMContextRef, LLVMTypeRef, LLVMValueRef};
+#[cfg(not(any(feature = "llvm3-6", feature = "llvm3-7", feature = "llvm3-8")))]
+use llvm_sys::core::{LLVMCreateEnumAttribute, LLVMCreateStringAttribute};
 use llvm_sys::ir_reader::LLVMParseIRInContext;
 
+#[cfg(not(any(feature = "llvm3-6", feature = "llvm3-7", feature = "llvm3-8")))]
+use attributes::Attribute;
+
 use basic_block::BasicBlock;
 use builder::Builder;
 use memory_buffer::MemoryBuffer;
 use module::Module;
 use support::LLVMString;
-use types::{BasicType, BasicTypeEnum, FloatType, IntType, StructType, VoidType, AsTypeRef};
-use values::{AsValueRef, BasicValue, FunctionValue, StructValue, MetadataValue, BasicValueEnum};
+#[cfg(not(any(feature = "llvm3-6", feature = "llvm3-7", feature = "llvm3-8")))]
+use support::error_handling::capture_diagnostic_error;
+use types::{BasicType, BasicTypeEnum, FloatType, FunctionType, IntType, StructType, VoidType, AsTypeRef, MAX_INT_BITS};
+use values::{AsValueRef, BasicValue, FunctionValue, PointerValue, StructValue, MetadataValue, BasicValueEnum};
 
 use std::ffi::CString;
 use std::mem::forget;
@@ -108,32 +115,29 @@ impl Context {
         Module::new(module, Some(&self))
     }
 
-    /// Creates a new `Module` for the current `Context` from a `MemoryBuffer`.
+    /// Creates a new `Module` for the current `Context` from a `MemoryBuffer` containing either
+    /// textual LLVM IR (as in a `.ll` file) or bitcode. Parse errors are returned as LLVM's
+    /// diagnostic message, which includes the line and column of the error.
+    ///
+    /// LLVM takes ownership of the `MemoryBuffer`, so it is consumed even if parsing fails.
     ///
     /// # Example
     ///
-    /// ```no_run
+    /// ```
     /// use inkwell::context::Context;
+    /// use inkwell::memory_buffer::MemoryBuffer;
     ///
     /// let context = Context::create();
-    /// let module = context.create_module("my_module");
-    /// let builder = context.create_builder();
-    /// let void_type = context.void_type();
-    /// let fn_type = void_type.fn_type(&[], false);
-    /// let fn_val = module.add_function("my_fn", &fn_type, None);
-    /// let basic_block = fn_val.append_basic_block("entry");
+    /// let ir = "define i32 @forty_two() {\n  ret i32 42\n}\n";
+    /// let memory_buffer = MemoryBuffer::create_from_memory_range_copy(ir.as_bytes(), "forty_two.ll");
+    /// let module = context.create_module_from_ir(memory_buffer).unwrap();
     ///
-    /// builder.position_at_end(&basic_block);
-    /// builder.build_return(None);
+    /// assert!(module.get_function("forty_two").is_some());
     ///
-    /// let memory_buffer = module.write_bitcode_to_memory();
+    /// let memory_buffer = MemoryBuffer::create_from_memory_range_copy(b"define i32 @broken(", "broken.ll");
     ///
-    /// let module2 = context.create_module_from_ir(memory_buffer).unwrap();
+    /// assert!(context.create_module_from_ir(memory_buffer).is_err());
     /// ```
-    // REVIEW: I haven't yet been able to find docs or other wrappers that confirm, but my suspicion
-    // is that the method needs to take ownership of the MemoryBuffer... otherwise I see what looks like
-    // a double free in valgrind when the MemoryBuffer drops so we are `forget`ting MemoryBuffer here
-    // for now until we can confirm this is the correct thing to do
     pub fn create_module_from_ir(&self, memory_buffer: MemoryBuffer) -> Result<Module, LLVMString> {
         let mut module = ptr::null_mut();
         let mut err_str = ptr::null_mut();
@@ -142,6 +146,7 @@ impl Context {
             LLVMParseIRInContext(*self.context, memory_buffer.memory_buffer, &mut module, &mut err_str)
         };
 
+        // LLVMParseIRInContext takes ownership of (and eventually frees) the MemoryBuffer
         forget(memory_buffer);
 
         if code == 0 {
@@ -151,6 +156,62 @@ impl Context {
         Err(LLVMString::new(err_str))
     }
 
+    /// Creates a new `Module` for the current `Context` from a `MemoryBuffer` containing bitcode,
+    /// such as one written by `Module::write_bitcode_to_memory`.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use inkwell::context::Context;
+    ///
+    /// let context = Context::create();
+    /// let module = context.create_module("my_module");
+    /// let void_type = context.void_type();
+    /// let fn_type = void_type.fn_type(&[], false);
+    ///
+    /// module.add_function("my_fn", &fn_type, None);
+    ///
+    /// let buffer = module.write_bitcode_to_memory();
+    /// let module2 = context.create_module_from_bitcode(&buffer).unwrap();
+    ///
+    /// assert_eq!(*module2.get_context(), context);
+    /// assert!(module2.get_function("my_fn").is_some());
+    /// ```
+    #[cfg(not(any(feature = "llvm3-6", feature = "llvm3-7", feature = "llvm3-8")))]
+    pub fn create_module_from_bitcode(&self, buffer: &MemoryBuffer) -> Result<Module, LLVMString> {
+        use llvm_sys::bit_reader::LLVMParseBitcodeInContext2;
+
+        let mut module = ptr::null_mut();
+
+        // LLVMParseBitcodeInContext2 only reports errors through the context's diagnostic handler
+        let (code, err_str) = unsafe {
+            capture_diagnostic_error(*self.context, || LLVMParseBitcodeInContext2(*self.context, buffer.memory_buffer, &mut module))
+        };
+
+        if code == 1 {
+            if err_str.is_null() {
+                let c_string = CString::new("Failed to parse bitcode").expect("Conversion to CString failed unexpectedly");
+
+                return Err(LLVMString::create(c_string.as_ptr()));
+            }
+
+            return Err(LLVMString::new(err_str));
+        }
+
+        // Free any diagnostic that was captured even though parsing succeeded
+        if !err_str.is_null() {
+            drop(LLVMString::new(err_str));
+        }
+
+        Ok(Module::new(module, Some(self)))
+    }
+
+    // LLVMParseBitcodeInContext2 isn't available before LLVM 3.9
+    #[cfg(any(feature = "llvm3-6", feature = "llvm3-7", feature = "llvm3-8"))]
+    pub fn create_module_from_bitcode(&self, buffer: &MemoryBuffer) -> Result<Module, LLVMString> {
+        Module::parse_bitcode_from_buffer_in_context(buffer, self)
+    }
+
     /// Gets the `VoidType`. It will be assigned the current context.
     ///
     /// # Example
@@ -298,6 +359,10 @@ impl Context {
 
     /// Gets the `IntType` representing a custom bit width. It will be assigned the current context.
     ///
+    /// # Panics
+    ///
+    /// Panics if `bits` is 0 or greater than the maximum width LLVM supports, 2^24 - 1.
+    ///
     /// # Example
     ///
     /// ```no_run
@@ -310,6 +375,8 @@ impl Context {
     /// assert_eq!(*i42_type.get_context(), context);
     /// ```
     pub fn custom_width_int_type(&self, bits: u32) -> IntType {
+        assert!(bits > 0 && bits <= MAX_INT_BITS, "IntType bit width must be between 1 and 2^24 - 1");
+
         let int_type = unsafe {
             LLVMIntTypeInContext(*self.context, bits)
         };
@@ -471,19 +538,26 @@ impl Context {
         StructType::new(struct_type)
     }
 
-    /// Creates an opaque `StructType` with no type definition yet defined.
+    /// Creates an opaque `StructType` with no type definition yet defined. Its body can later
+    /// be defined with `StructType::set_body`, which allows it to refer to itself.
     ///
     /// # Example
     ///
     /// ```no_run
+    /// use inkwell::AddressSpace;
     /// use inkwell::context::Context;
     ///
     /// let context = Context::create();
     /// let f32_type = context.f32_type();
-    /// let i16_type = context.i16_type();
     /// let struct_type = context.opaque_struct_type("my_struct");
     ///
     /// assert_eq!(struct_type.get_field_types(), &[]);
+    ///
+    /// let struct_ptr_type = struct_type.ptr_type(AddressSpace::Generic);
+    ///
+    /// struct_type.set_body(&[&f32_type, &struct_ptr_type], false);
+    ///
+    /// assert_eq!(struct_type.count_fields(), 2);
     /// ```
     pub fn opaque_struct_type(&self, name: &str) -> StructType {
         let c_string = CString::new(name).expect("Conversion to CString failed unexpectedly");
@@ -682,6 +756,45 @@ impl Context {
         MetadataValue::new(metadata_value)
     }
 
+    /// Creates a piece of inline assembly of the given `FunctionType`, which can be called through
+    /// `Builder::build_indirect_call`. Both `asm` and `constraints` are passed through to LLVM as is,
+    /// so the constraint string must follow LLVM's inline assembly constraint syntax (ie "=r,r,~{memory}").
+    /// `has_side_effects` prevents the assembly from being removed or reordered when its result is unused,
+    /// and `is_align_stack` requires the stack to be aligned before the assembly runs.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use inkwell::context::Context;
+    ///
+    /// let context = Context::create();
+    /// let module = context.create_module("my_mod");
+    /// let builder = context.create_builder();
+    /// let i64_type = context.i64_type();
+    /// let fn_type = i64_type.fn_type(&[], false);
+    /// let fn_value = module.add_function("read_cycles", &fn_type, None);
+    /// let entry = fn_value.append_basic_block("entry");
+    /// let rdtsc = context.create_inline_asm(&fn_type, "rdtsc", "=A", true, false);
+    ///
+    /// builder.position_at_end(&entry);
+    ///
+    /// let cycles = builder.build_indirect_call(&rdtsc, &[], "cycles", false).left().unwrap();
+    ///
+    /// builder.build_return(Some(&cycles));
+    ///
+    /// assert!(fn_value.verify(false));
+    /// ```
+    pub fn create_inline_asm(&self, ty: &FunctionType, asm: &str, constraints: &str, has_side_effects: bool, is_align_stack: bool) -> PointerValue {
+        let asm_c_string = CString::new(asm).expect("Conversion to CString failed unexpectedly");
+        let constraints_c_string = CString::new(constraints).expect("Conversion to CString failed unexpectedly");
+
+        let value = unsafe {
+            LLVMConstInlineAsm(ty.as_type_ref(), asm_c_string.as_ptr(), constraints_c_string.as_ptr(), has_side_effects as i32, is_align_stack as i32)
+        };
+
+        PointerValue::new(value)
+    }
+
     /// Obtains the index of a metadata kind id. If the string doesn't exist, LLVM will add it at index `FIRST_CUSTOM_METADATA_KIND_ID` onward.
     ///
     /// # Example
@@ -705,6 +818,53 @@ impl Context {
         }
     }
 
+    /// Creates an enum `Attribute` in this `Context`.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use inkwell::attributes::Attribute;
+    /// use inkwell::context::Context;
+    ///
+    /// let context = Context::create();
+    /// let kind_id = Attribute::get_named_enum_kind_id("noinline");
+    /// let enum_attribute = context.create_enum_attribute(kind_id, 0);
+    ///
+    /// assert!(enum_attribute.is_enum());
+    /// assert_eq!(enum_attribute.get_enum_kind_id(), kind_id);
+    /// ```
+    #[cfg(not(any(feature = "llvm3-6", feature = "llvm3-7", feature = "llvm3-8")))]
+    pub fn create_enum_attribute(&self, kind_id: u32, val: u64) -> Attribute {
+        let attribute = unsafe {
+            LLVMCreateEnumAttribute(*self.context, kind_id, val)
+        };
+
+        Attribute::new(attribute)
+    }
+
+    /// Creates a string `Attribute` in this `Context`.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use inkwell::context::Context;
+    ///
+    /// let context = Context::create();
+    /// let string_attribute = context.create_string_attribute("my_key", "my_val");
+    ///
+    /// assert!(string_attribute.is_string());
+    /// assert_eq!(string_attribute.get_string_kind(), b"my_key");
+    /// assert_eq!(string_attribute.get_string_value(), b"my_val");
+    /// ```
+    #[cfg(not(any(feature = "llvm3-6", feature = "llvm3-7", feature = "llvm3-8")))]
+    pub fn create_string_attribute(&self, key: &str, val: &str) -> Attribute {
+        let attribute = unsafe {
+            LLVMCreateStringAttribute(*self.context, key.as_ptr() as *const _, key.len() as u32, val.as_ptr() as *const _, val.len() as u32)
+        };
+
+        Attribute::new(attribute)
+    }
+
     // LLVM 3.9+
     // pub fn get_diagnostic_handler(&self) -> DiagnosticHandler {
     //     let handler = unsafe {
@@ -0,0 +1,405 @@
+//! Debug information for source-level debugging, such as DWARF line tables, built through a `DebugInfoBuilder`.
+
+use libc::c_char;
+use llvm_sys::core::{LLVMConstInt, LLVMGetModuleContext, LLVMInt32TypeInContext, LLVMMDNodeInContext, LLVMMDStringInContext, LLVMMetadataAsValue};
+use llvm_sys::debuginfo::{LLVMCreateDIBuilder, LLVMCreateDIBuilderDisallowUnresolved, LLVMDebugMetadataVersion, LLVMDIBuilderCreateCompileUnit, LLVMDIBuilderCreateDebugLocation, LLVMDIBuilderCreateFile, LLVMDIBuilderFinalize, LLVMDisposeDIBuilder, LLVMDWARFEmissionKind, LLVMDWARFSourceLanguage};
+use llvm_sys::prelude::{LLVMContextRef, LLVMDIBuilderRef, LLVMMetadataRef, LLVMValueRef};
+
+use module::Module;
+use values::MetadataValue;
+
+use std::cell::Cell;
+use std::ptr;
+
+/// Gets the version of debug metadata produced by this version of LLVM.
+///
+/// # Example
+///
+/// ```
+/// use inkwell::debug_info::debug_metadata_version;
+///
+/// assert_eq!(debug_metadata_version(), 3);
+/// ```
+pub fn debug_metadata_version() -> u32 {
+    unsafe {
+        LLVMDebugMetadataVersion()
+    }
+}
+
+/// The source language of a compile unit, as known by DWARF.
+#[allow(missing_docs)]
+#[derive(Debug, PartialEq, Eq, Copy, Clone)]
+pub enum DWARFSourceLanguage {
+    C89,
+    C,
+    Ada83,
+    CPlusPlus,
+    Cobol74,
+    Cobol85,
+    Fortran77,
+    Fortran90,
+    Pascal83,
+    Modula2,
+    Java,
+    C99,
+    Ada95,
+    Fortran95,
+    PLI,
+    ObjC,
+    ObjCPlusPlus,
+    UPC,
+    D,
+    Python,
+    OpenCL,
+    Go,
+    Modula3,
+    Haskell,
+    CPlusPlus03,
+    CPlusPlus11,
+    OCaml,
+    Rust,
+    C11,
+    Swift,
+    Julia,
+    Dylan,
+    CPlusPlus14,
+    Fortran03,
+    Fortran08,
+    RenderScript,
+    BLISS,
+    MipsAssembler,
+    GOOGLERenderScript,
+    BORLANDDelphi,
+}
+
+impl DWARFSourceLanguage {
+    fn as_llvm_source_language(&self) -> LLVMDWARFSourceLanguage {
+        match *self {
+            DWARFSourceLanguage::C89 => LLVMDWARFSourceLanguage::LLVMDWARFSourceLanguageC89,
+            DWARFSourceLanguage::C => LLVMDWARFSourceLanguage::LLVMDWARFSourceLanguageC,
+            DWARFSourceLanguage::Ada83 => LLVMDWARFSourceLanguage::LLVMDWARFSourceLanguageAda83,
+            DWARFSourceLanguage::CPlusPlus => LLVMDWARFSourceLanguage::LLVMDWARFSourceLanguageC_plus_plus,
+            DWARFSourceLanguage::Cobol74 => LLVMDWARFSourceLanguage::LLVMDWARFSourceLanguageCobol74,
+            DWARFSourceLanguage::Cobol85 => LLVMDWARFSourceLanguage::LLVMDWARFSourceLanguageCobol85,
+            DWARFSourceLanguage::Fortran77 => LLVMDWARFSourceLanguage::LLVMDWARFSourceLanguageFortran77,
+            DWARFSourceLanguage::Fortran90 => LLVMDWARFSourceLanguage::LLVMDWARFSourceLanguageFortran90,
+            DWARFSourceLanguage::Pascal83 => LLVMDWARFSourceLanguage::LLVMDWARFSourceLanguagePascal83,
+            DWARFSourceLanguage::Modula2 => LLVMDWARFSourceLanguage::LLVMDWARFSourceLanguageModula2,
+            DWARFSourceLanguage::Java => LLVMDWARFSourceLanguage::LLVMDWARFSourceLanguageJava,
+            DWARFSourceLanguage::C99 => LLVMDWARFSourceLanguage::LLVMDWARFSourceLanguageC99,
+            DWARFSourceLanguage::Ada95 => LLVMDWARFSourceLanguage::LLVMDWARFSourceLanguageAda95,
+            DWARFSourceLanguage::Fortran95 => LLVMDWARFSourceLanguage::LLVMDWARFSourceLanguageFortran95,
+            DWARFSourceLanguage::PLI => LLVMDWARFSourceLanguage::LLVMDWARFSourceLanguagePLI,
+            DWARFSourceLanguage::ObjC => LLVMDWARFSourceLanguage::LLVMDWARFSourceLanguageObjC,
+            DWARFSourceLanguage::ObjCPlusPlus => LLVMDWARFSourceLanguage::LLVMDWARFSourceLanguageObjC_plus_plus,
+            DWARFSourceLanguage::UPC => LLVMDWARFSourceLanguage::LLVMDWARFSourceLanguageUPC,
+            DWARFSourceLanguage::D => LLVMDWARFSourceLanguage::LLVMDWARFSourceLanguageD,
+            DWARFSourceLanguage::Python => LLVMDWARFSourceLanguage::LLVMDWARFSourceLanguagePython,
+            DWARFSourceLanguage::OpenCL => LLVMDWARFSourceLanguage::LLVMDWARFSourceLanguageOpenCL,
+            DWARFSourceLanguage::Go => LLVMDWARFSourceLanguage::LLVMDWARFSourceLanguageGo,
+            DWARFSourceLanguage::Modula3 => LLVMDWARFSourceLanguage::LLVMDWARFSourceLanguageModula3,
+            DWARFSourceLanguage::Haskell => LLVMDWARFSourceLanguage::LLVMDWARFSourceLanguageHaskell,
+            DWARFSourceLanguage::CPlusPlus03 => LLVMDWARFSourceLanguage::LLVMDWARFSourceLanguageC_plus_plus_03,
+            DWARFSourceLanguage::CPlusPlus11 => LLVMDWARFSourceLanguage::LLVMDWARFSourceLanguageC_plus_plus_11,
+            DWARFSourceLanguage::OCaml => LLVMDWARFSourceLanguage::LLVMDWARFSourceLanguageOCaml,
+            DWARFSourceLanguage::Rust => LLVMDWARFSourceLanguage::LLVMDWARFSourceLanguageRust,
+            DWARFSourceLanguage::C11 => LLVMDWARFSourceLanguage::LLVMDWARFSourceLanguageC11,
+            DWARFSourceLanguage::Swift => LLVMDWARFSourceLanguage::LLVMDWARFSourceLanguageSwift,
+            DWARFSourceLanguage::Julia => LLVMDWARFSourceLanguage::LLVMDWARFSourceLanguageJulia,
+            DWARFSourceLanguage::Dylan => LLVMDWARFSourceLanguage::LLVMDWARFSourceLanguageDylan,
+            DWARFSourceLanguage::CPlusPlus14 => LLVMDWARFSourceLanguage::LLVMDWARFSourceLanguageC_plus_plus_14,
+            DWARFSourceLanguage::Fortran03 => LLVMDWARFSourceLanguage::LLVMDWARFSourceLanguageFortran03,
+            DWARFSourceLanguage::Fortran08 => LLVMDWARFSourceLanguage::LLVMDWARFSourceLanguageFortran08,
+            DWARFSourceLanguage::RenderScript => LLVMDWARFSourceLanguage::LLVMDWARFSourceLanguageRenderScript,
+            DWARFSourceLanguage::BLISS => LLVMDWARFSourceLanguage::LLVMDWARFSourceLanguageBLISS,
+            DWARFSourceLanguage::MipsAssembler => LLVMDWARFSourceLanguage::LLVMDWARFSourceLanguageMips_Assembler,
+            DWARFSourceLanguage::GOOGLERenderScript => LLVMDWARFSourceLanguage::LLVMDWARFSourceLanguageGOOGLE_RenderScript,
+            DWARFSourceLanguage::BORLANDDelphi => LLVMDWARFSourceLanguage::LLVMDWARFSourceLanguageBORLAND_Delphi,
+        }
+    }
+}
+
+/// The amount of debug information a compile unit should emit.
+#[derive(Debug, PartialEq, Eq, Copy, Clone)]
+pub enum DWARFEmissionKind {
+    /// Emit no debug information.
+    None,
+    /// Emit full debug information.
+    Full,
+    /// Only emit line tables, which is enough to map addresses back to source lines.
+    LineTablesOnly,
+}
+
+impl DWARFEmissionKind {
+    fn as_llvm_emission_kind(&self) -> LLVMDWARFEmissionKind {
+        match *self {
+            DWARFEmissionKind::None => LLVMDWARFEmissionKind::LLVMDWARFEmissionKindNone,
+            DWARFEmissionKind::Full => LLVMDWARFEmissionKind::LLVMDWARFEmissionKindFull,
+            DWARFEmissionKind::LineTablesOnly => LLVMDWARFEmissionKind::LLVMDWARFEmissionKindLineTablesOnly,
+        }
+    }
+}
+
+// TODO: create_function (subprograms), create_basic_type, lexical blocks and local variables
+// all require the DIBuilder C API added in LLVM 7
+/// A `DebugInfoBuilder` creates debug information metadata, such as files, compile units and
+/// source locations, for the `Module` it was created from. It is created by
+/// `Module::create_debug_info_builder`, borrows that `Module` for as long as it lives and is
+/// finalized when dropped, if not finalized beforehand.
+#[derive(Debug)]
+pub struct DebugInfoBuilder<'a> {
+    builder: LLVMDIBuilderRef,
+    module: &'a Module,
+    finalized: Cell<bool>,
+}
+
+impl<'a> DebugInfoBuilder<'a> {
+    pub(crate) fn new(module: &'a Module, allow_unresolved: bool) -> Self {
+        let builder = unsafe {
+            if allow_unresolved {
+                LLVMCreateDIBuilder(module.module.get())
+            } else {
+                LLVMCreateDIBuilderDisallowUnresolved(module.module.get())
+            }
+        };
+
+        assert!(!builder.is_null());
+
+        DebugInfoBuilder {
+            builder,
+            module,
+            finalized: Cell::new(false),
+        }
+    }
+
+    fn get_context(&self) -> LLVMContextRef {
+        unsafe {
+            LLVMGetModuleContext(self.module.module.get())
+        }
+    }
+
+    /// Creates a `DIFile` describing a source file by its name and the directory it resides in.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use inkwell::context::Context;
+    ///
+    /// let context = Context::create();
+    /// let module = context.create_module("my_module");
+    /// let debug_info_builder = module.create_debug_info_builder(true);
+    /// let file = debug_info_builder.create_file("main.rs", "/home/user/project");
+    /// ```
+    pub fn create_file(&self, filename: &str, directory: &str) -> DIFile {
+        let metadata_ref = unsafe {
+            LLVMDIBuilderCreateFile(self.builder, filename.as_ptr() as *const c_char, filename.len(), directory.as_ptr() as *const c_char, directory.len())
+        };
+
+        DIFile::new(metadata_ref)
+    }
+
+    /// Creates the `DICompileUnit` for a `Module`, which is the root of all of its debug information.
+    /// Only one compile unit should be created per `DebugInfoBuilder`. This also adds the "Debug Info
+    /// Version" module flag, without which LLVM would discard the `Module`'s debug information.
+    ///
+    /// `producer` identifies the compiler which generated the code, `flags` are the command line
+    /// flags it was invoked with and `runtime_version` is the Objective-C runtime version (or 0).
+    /// `split_name` is the name of the split debug info (.dwo) file, if any, and `dwo_id` its id.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use inkwell::context::Context;
+    /// use inkwell::debug_info::{DWARFEmissionKind, DWARFSourceLanguage, debug_metadata_version};
+    ///
+    /// let context = Context::create();
+    /// let module = context.create_module("my_module");
+    /// let debug_info_builder = module.create_debug_info_builder(true);
+    /// let file = debug_info_builder.create_file("main.rs", "/home/user/project");
+    /// let compile_unit = debug_info_builder.create_compile_unit(DWARFSourceLanguage::Rust, &file, "my_compiler", false, "", 0, "", DWARFEmissionKind::Full, 0, false, false);
+    ///
+    /// debug_info_builder.finalize();
+    ///
+    /// assert_eq!(module.get_debug_metadata_version(), debug_metadata_version());
+    /// ```
+    pub fn create_compile_unit(
+        &self,
+        language: DWARFSourceLanguage,
+        file: &DIFile,
+        producer: &str,
+        is_optimized: bool,
+        flags: &str,
+        runtime_version: u32,
+        split_name: &str,
+        kind: DWARFEmissionKind,
+        dwo_id: u32,
+        split_debug_inlining: bool,
+        debug_info_for_profiling: bool,
+    ) -> DICompileUnit {
+        let metadata_ref = unsafe {
+            LLVMDIBuilderCreateCompileUnit(
+                self.builder,
+                language.as_llvm_source_language(),
+                file.metadata_ref,
+                producer.as_ptr() as *const c_char,
+                producer.len(),
+                is_optimized as i32,
+                flags.as_ptr() as *const c_char,
+                flags.len(),
+                runtime_version,
+                split_name.as_ptr() as *const c_char,
+                split_name.len(),
+                kind.as_llvm_emission_kind(),
+                dwo_id,
+                split_debug_inlining as i32,
+                debug_info_for_profiling as i32,
+            )
+        };
+
+        if self.module.get_debug_metadata_version() == 0 {
+            self.add_debug_info_version_flag();
+        }
+
+        DICompileUnit::new(metadata_ref)
+    }
+
+    // Adds !{i32 2, !"Debug Info Version", i32 3} to llvm.module.flags, where 2 is the "warning"
+    // behavior for modules with mismatched versions being linked together
+    fn add_debug_info_version_flag(&self) {
+        let context = self.get_context();
+        let key = "Debug Info Version";
+        let flag = unsafe {
+            let i32_type = LLVMInt32TypeInContext(context);
+            let mut values = [
+                LLVMConstInt(i32_type, 2, 0),
+                LLVMMDStringInContext(context, key.as_ptr() as *const c_char, key.len() as u32),
+                LLVMConstInt(i32_type, debug_metadata_version() as u64, 0),
+            ];
+
+            LLVMMDNodeInContext(context, values.as_mut_ptr(), values.len() as u32)
+        };
+
+        self.module.add_global_metadata("llvm.module.flags", &MetadataValue::new(flag));
+    }
+
+    /// Creates a `DILocation` describing a line and column within a `DIScope`, optionally
+    /// inlined at another `DILocation`. It can be attached to the instructions a `Builder`
+    /// creates with `Builder::set_current_debug_location`.
+    ///
+    /// Note that LLVM expects the scope of a location attached to an instruction to be a local
+    /// scope, such as the subprogram of the function the instruction is in.
+    pub fn create_debug_location(&self, line: u32, column: u32, scope: &DIScope, inlined_at: Option<&DILocation>) -> DILocation {
+        let context = self.get_context();
+        let inlined_at = inlined_at.map(|location| location.metadata_ref).unwrap_or(ptr::null_mut());
+        let metadata_ref = unsafe {
+            LLVMDIBuilderCreateDebugLocation(context, line, column, scope.metadata_ref, inlined_at)
+        };
+
+        DILocation::new(context, metadata_ref)
+    }
+
+    /// Constructs any deferred debug information. This must be done before the `Module`
+    /// is verified or compiled. Creating further debug information afterwards is not supported.
+    pub fn finalize(&self) {
+        if self.finalized.replace(true) {
+            return;
+        }
+
+        unsafe {
+            LLVMDIBuilderFinalize(self.builder)
+        }
+    }
+}
+
+impl<'a> Drop for DebugInfoBuilder<'a> {
+    fn drop(&mut self) {
+        self.finalize();
+
+        unsafe {
+            LLVMDisposeDIBuilder(self.builder)
+        }
+    }
+}
+
+/// A source file referenced by debug information.
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub struct DIFile {
+    pub(crate) metadata_ref: LLVMMetadataRef,
+}
+
+impl DIFile {
+    pub(crate) fn new(metadata_ref: LLVMMetadataRef) -> Self {
+        assert!(!metadata_ref.is_null());
+
+        DIFile {
+            metadata_ref,
+        }
+    }
+
+    /// Gets this `DIFile` as a `DIScope`.
+    pub fn as_debug_info_scope(&self) -> DIScope {
+        DIScope::new(self.metadata_ref)
+    }
+}
+
+/// The root of a `Module`'s debug information.
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub struct DICompileUnit {
+    pub(crate) metadata_ref: LLVMMetadataRef,
+}
+
+impl DICompileUnit {
+    pub(crate) fn new(metadata_ref: LLVMMetadataRef) -> Self {
+        assert!(!metadata_ref.is_null());
+
+        DICompileUnit {
+            metadata_ref,
+        }
+    }
+
+    /// Gets this `DICompileUnit` as a `DIScope`.
+    pub fn as_debug_info_scope(&self) -> DIScope {
+        DIScope::new(self.metadata_ref)
+    }
+}
+
+/// Any debug information which other debug information can be nested in, such as a file or compile unit.
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub struct DIScope {
+    pub(crate) metadata_ref: LLVMMetadataRef,
+}
+
+impl DIScope {
+    pub(crate) fn new(metadata_ref: LLVMMetadataRef) -> Self {
+        assert!(!metadata_ref.is_null());
+
+        DIScope {
+            metadata_ref,
+        }
+    }
+}
+
+/// A source location, made up of a line and column within a `DIScope`.
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub struct DILocation {
+    // The context the location was created in, which LLVM needs to wrap it up as a value
+    context: LLVMContextRef,
+    pub(crate) metadata_ref: LLVMMetadataRef,
+}
+
+impl DILocation {
+    pub(crate) fn new(context: LLVMContextRef, metadata_ref: LLVMMetadataRef) -> Self {
+        assert!(!metadata_ref.is_null());
+
+        DILocation {
+            context,
+            metadata_ref,
+        }
+    }
+
+    pub(crate) fn as_value_ref(&self) -> LLVMValueRef {
+        unsafe {
+            LLVMMetadataAsValue(self.context, self.metadata_ref)
+        }
+    }
+}
@@ -1,32 +1,56 @@
-use libc::c_int;
-use llvm_sys::execution_engine::{LLVMGetExecutionEngineTargetData, LLVMExecutionEngineRef, LLVMRunFunction, LLVMRunFunctionAsMain, LLVMDisposeExecutionEngine, LLVMGetFunctionAddress, LLVMAddModule, LLVMFindFunction, LLVMLinkInMCJIT, LLVMLinkInInterpreter, LLVMRemoveModule, LLVMGenericValueRef, LLVMFreeMachineCodeForFunction, LLVMAddGlobalMapping, LLVMRunStaticConstructors, LLVMRunStaticDestructors};
-
-use module::Module;
+use libc::{c_char, c_int, c_uint, c_void};
+use llvm_sys::core::{LLVMCreateMessage, LLVMGetElementType, LLVMGetIntTypeWidth, LLVMGetReturnType, LLVMGetTypeKind, LLVMGetFirstFunction, LLVMGetGlobalParent, LLVMGetModuleIdentifier, LLVMGetNamedFunction, LLVMGetNamedGlobal, LLVMIsDeclaration, LLVMIsFunctionVarArg, LLVMTypeOf};
+use llvm_sys::prelude::{LLVMBool, LLVMModuleRef, LLVMValueRef};
+use llvm_sys::LLVMTypeKind;
+use llvm_sys::target::LLVMCopyStringRepOfTargetData;
+#[cfg(not(any(feature = "llvm3-6", feature = "llvm3-7", feature = "llvm3-8")))]
+use llvm_sys::core::{LLVMAddAttributeAtIndex, LLVMAddGlobal, LLVMAppendBasicBlockInContext, LLVMBasicBlockAsValue, LLVMBuildCondBr, LLVMBuildICmp, LLVMBuildLoad, LLVMBuildRet, LLVMBuildRetVoid, LLVMBuildStore, LLVMBuildSub, LLVMConstInt, LLVMCountBasicBlocks, LLVMCreateBuilderInContext, LLVMCreateStringAttribute, LLVMDisposeBuilder, LLVMGetFirstBasicBlock, LLVMGetFirstInstruction, LLVMGetFirstUse, LLVMGetInstructionOpcode, LLVMGetModuleContext, LLVMGetNextBasicBlock, LLVMGetNextFunction, LLVMGetNextInstruction, LLVMGetNextUse, LLVMGetNumOperands, LLVMGetOperand, LLVMGetStringAttributeAtIndex, LLVMGetTypeContext, LLVMGetUndef, LLVMGetUser, LLVMGetValueName, LLVMInsertBasicBlockInContext, LLVMInsertIntoBuilderWithName, LLVMInstructionRemoveFromParent, LLVMInt64TypeInContext, LLVMIsABranchInst, LLVMIsASwitchInst, LLVMPositionBuilderAtEnd, LLVMSetInitializer, LLVMSetLinkage, LLVMSetOperand};
+#[cfg(not(any(feature = "llvm3-6", feature = "llvm3-7", feature = "llvm3-8")))]
+use llvm_sys::prelude::{LLVMBasicBlockRef, LLVMBuilderRef};
+#[cfg(not(any(feature = "llvm3-6", feature = "llvm3-7", feature = "llvm3-8")))]
+use llvm_sys::{LLVMAttributeFunctionIndex, LLVMIntPredicate, LLVMLinkage, LLVMOpcode};
+#[cfg(not(any(feature = "llvm3-6", feature = "llvm3-7", feature = "llvm3-8")))]
+use llvm_sys::execution_engine::LLVMGetPointerToGlobal;
+use llvm_sys::execution_engine::{LLVMGetExecutionEngineTargetData, LLVMGetExecutionEngineTargetMachine, LLVMExecutionEngineRef, LLVMRunFunction, LLVMRunFunctionAsMain, LLVMDisposeExecutionEngine, LLVMGetFunctionAddress, LLVMAddModule, LLVMFindFunction, LLVMLinkInMCJIT, LLVMLinkInInterpreter, LLVMRemoveModule, LLVMGenericValueRef, LLVMFreeMachineCodeForFunction, LLVMAddGlobalMapping, LLVMRunStaticConstructors, LLVMRunStaticDestructors, LLVMGetGlobalValueAddress, LLVMCreateSimpleMCJITMemoryManager, LLVMInitializeMCJITCompilerOptions, LLVMMCJITCompilerOptions, LLVMMCJITMemoryManagerRef, LLVMDisposeMCJITMemoryManager};
+
+use {CallConv, OptimizationLevel};
+use context::Context;
+use module::{Linkage, Module, ModuleState};
 use support::LLVMString;
-use targets::TargetData;
+use targets::{CodeModel, TargetData, TargetMachine};
+use types::FunctionType;
 use values::{AnyValue, AsValueRef, FunctionValue, GenericValue};
 
+use std::cell::{Cell, RefCell};
 use std::error::Error;
-use std::rc::Rc;
+use std::rc::{Rc, Weak};
 use std::ops::Deref;
+use std::panic::{catch_unwind, AssertUnwindSafe};
+use std::ptr;
 use std::ffi::{CStr, CString};
 use std::fmt::{self, Debug, Display, Formatter};
 use std::mem::{forget, uninitialized, zeroed, transmute_copy, size_of};
+use std::vec::IntoIter;
+use std::slice::from_raw_parts;
 
 #[derive(Debug, PartialEq, Eq)]
 pub enum FunctionLookupError {
     JITNotEnabled,
     FunctionNotFound, // 404!
+    DeclarationOnly,
+    FunctionFreed,
+    FunctionNotOwned,
+    SignatureMismatch {
+        expected: u32,
+        found: u32,
+    },
+    CallConvMismatch {
+        found: u32,
+    },
 }
 
 impl Error for FunctionLookupError {
-    // This method is deprecated on nighty so it's probably not
-    // something we should worry about
-    fn description(&self) -> &str {
-        self.as_str()
-    }
-
-    fn cause(&self) -> Option<&Error> {
+    fn source(&self) -> Option<&(Error + 'static)> {
         None
     }
 }
@@ -36,6 +60,11 @@ impl FunctionLookupError {
         match self {
             FunctionLookupError::JITNotEnabled => "ExecutionEngine does not have JIT functionality enabled",
             FunctionLookupError::FunctionNotFound => "Function not found in ExecutionEngine",
+            FunctionLookupError::DeclarationOnly => "Function is only declared and has no body",
+            FunctionLookupError::FunctionFreed => "Function's machine code has been freed",
+            FunctionLookupError::FunctionNotOwned => "Function is not owned by this Execution Engine",
+            FunctionLookupError::SignatureMismatch { .. } => "Function's parameter count does not match the requested signature",
+            FunctionLookupError::CallConvMismatch { .. } => "Function does not use the C calling convention",
         }
     }
 }
@@ -46,6 +75,226 @@ impl Display for FunctionLookupError {
     }
 }
 
+/// A builder for a JIT `ExecutionEngine` using MCJIT, created with `Module::jit_engine_builder`.
+/// Unless configured otherwise, `create` behaves the same as
+/// `Module::create_jit_execution_engine(OptimizationLevel::None)`.
+///
+/// # Example
+/// ```no_run
+/// use inkwell::OptimizationLevel;
+/// use inkwell::context::Context;
+/// use inkwell::targets::{InitializationConfig, Target};
+///
+/// Target::initialize_native(&InitializationConfig::default()).expect("Failed to initialize native target");
+///
+/// let context = Context::create();
+/// let module = context.create_module("my_module");
+/// let execution_engine = module.jit_engine_builder()
+///     .opt_level(OptimizationLevel::Less)
+///     .no_frame_pointer_elim(true)
+///     .enable_fast_isel(true)
+///     .create()
+///     .unwrap();
+///
+/// assert!(execution_engine.is_jit());
+/// ```
+#[derive(Debug)]
+pub struct JitEngineBuilder<'m> {
+    module: &'m Module,
+    opt_level: OptimizationLevel,
+    code_model: CodeModel,
+    no_frame_pointer_elim: bool,
+    enable_fast_isel: bool,
+    verify_module: bool,
+    memory_manager: Option<LLVMMCJITMemoryManagerRef>,
+}
+
+impl<'m> JitEngineBuilder<'m> {
+    pub(crate) fn new(module: &'m Module) -> Self {
+        JitEngineBuilder {
+            module,
+            opt_level: OptimizationLevel::None,
+            code_model: CodeModel::JITDefault,
+            no_frame_pointer_elim: false,
+            enable_fast_isel: false,
+            verify_module: false,
+            memory_manager: None,
+        }
+    }
+
+    pub fn opt_level(mut self, opt_level: OptimizationLevel) -> Self {
+        self.opt_level = opt_level;
+        self
+    }
+
+    pub fn code_model(mut self, code_model: CodeModel) -> Self {
+        self.code_model = code_model;
+        self
+    }
+
+    /// Keeps frame pointers in the generated code, which profilers may rely on to walk the stack.
+    pub fn no_frame_pointer_elim(mut self, no_frame_pointer_elim: bool) -> Self {
+        self.no_frame_pointer_elim = no_frame_pointer_elim;
+        self
+    }
+
+    /// Uses the fast instruction selector, which compiles faster at the expense of code quality.
+    pub fn enable_fast_isel(mut self, enable_fast_isel: bool) -> Self {
+        self.enable_fast_isel = enable_fast_isel;
+        self
+    }
+
+    /// Whether or not to verify the `Module` before compiling it, in which case `create` returns
+    /// `Err(ExecutionEngineCreationError::InvalidModule)` if it is malformed.
+    pub fn verify_module(mut self, verify_module: bool) -> Self {
+        self.verify_module = verify_module;
+        self
+    }
+
+    /// Has MCJIT allocate the memory for compiled code and data through `memory_manager`
+    /// rather than its default one. The `ExecutionEngine` drops it once it is itself dropped.
+    pub fn memory_manager<M: MCJITMemoryManager + 'static>(mut self, memory_manager: M) -> Self {
+        let opaque = Box::into_raw(Box::new(memory_manager));
+        let memory_manager = unsafe {
+            LLVMCreateSimpleMCJITMemoryManager(opaque as *mut c_void, allocate_code_section::<M>, allocate_data_section::<M>, finalize_memory::<M>, Some(destroy_memory_manager::<M>))
+        };
+
+        if let Some(previous) = self.memory_manager.replace(memory_manager) {
+            unsafe {
+                LLVMDisposeMCJITMemoryManager(previous);
+            }
+        }
+
+        self
+    }
+
+    /// Creates the `ExecutionEngine`, which takes ownership of the `Module`.
+    // SubType: ExecutionEngine<Jit>
+    pub fn create(mut self) -> Result<ExecutionEngine, ExecutionEngineCreationError> {
+        if self.verify_module {
+            self.module.verify().map_err(ExecutionEngineCreationError::InvalidModule)?;
+        }
+
+        let mut options = unsafe { zeroed() };
+
+        unsafe {
+            LLVMInitializeMCJITCompilerOptions(&mut options, size_of::<LLVMMCJITCompilerOptions>());
+        }
+
+        options.OptLevel = self.opt_level as u32;
+        options.CodeModel = self.code_model.as_llvm_code_model();
+        options.NoFramePointerElim = self.no_frame_pointer_elim as i32;
+        options.EnableFastISel = self.enable_fast_isel as i32;
+
+        // LLVM disposes of the memory manager along with the EE, or right away if creating it fails
+        if let Some(memory_manager) = self.memory_manager.take() {
+            options.MCJMM = memory_manager;
+        }
+
+        self.module.create_mcjit_execution_engine(options)
+    }
+}
+
+impl<'m> Drop for JitEngineBuilder<'m> {
+    fn drop(&mut self) {
+        if let Some(memory_manager) = self.memory_manager.take() {
+            unsafe {
+                LLVMDisposeMCJITMemoryManager(memory_manager);
+            }
+        }
+    }
+}
+
+/// A custom memory manager for MCJIT, which allocates the memory that compiled code and
+/// data are written to. Set one with `JitEngineBuilder::memory_manager`.
+///
+/// Panics in these methods are caught before they reach LLVM, and are reported to it as a
+/// failed allocation or finalization.
+pub trait MCJITMemoryManager {
+    /// Allocates `size` bytes aligned to `alignment` for a code section. The memory must remain
+    /// valid until the memory manager is dropped. Returning a null pointer signals a failure.
+    fn allocate_code_section(&mut self, size: usize, alignment: u32, section_id: u32, section_name: &str) -> *mut u8;
+
+    /// Allocates `size` bytes aligned to `alignment` for a data section. The memory must remain
+    /// valid until the memory manager is dropped. Returning a null pointer signals a failure.
+    fn allocate_data_section(&mut self, size: usize, alignment: u32, section_id: u32, section_name: &str, is_read_only: bool) -> *mut u8;
+
+    /// Called once every section has been written and relocated. This is where code sections
+    /// should be made executable, and read only data sections read only.
+    fn finalize_memory(&mut self) -> Result<(), String>;
+}
+
+// Panics must not unwind into LLVM, so each callback catches them and reports a failure instead
+
+extern "C" fn allocate_code_section<M: MCJITMemoryManager>(opaque: *mut c_void, size: usize, alignment: c_uint, section_id: c_uint, section_name: *const c_char) -> *mut u8 {
+    let memory_manager = unsafe { &mut *(opaque as *mut M) };
+    let section_name = unsafe { CStr::from_ptr(section_name) }.to_string_lossy();
+
+    catch_unwind(AssertUnwindSafe(|| memory_manager.allocate_code_section(size, alignment, section_id, &section_name)))
+        .unwrap_or(ptr::null_mut())
+}
+
+extern "C" fn allocate_data_section<M: MCJITMemoryManager>(opaque: *mut c_void, size: usize, alignment: c_uint, section_id: c_uint, section_name: *const c_char, is_read_only: LLVMBool) -> *mut u8 {
+    let memory_manager = unsafe { &mut *(opaque as *mut M) };
+    let section_name = unsafe { CStr::from_ptr(section_name) }.to_string_lossy();
+
+    catch_unwind(AssertUnwindSafe(|| memory_manager.allocate_data_section(size, alignment, section_id, &section_name, is_read_only == 1)))
+        .unwrap_or(ptr::null_mut())
+}
+
+extern "C" fn finalize_memory<M: MCJITMemoryManager>(opaque: *mut c_void, err_msg: *mut *mut c_char) -> LLVMBool {
+    let memory_manager = unsafe { &mut *(opaque as *mut M) };
+    let result = catch_unwind(AssertUnwindSafe(|| memory_manager.finalize_memory()))
+        .unwrap_or_else(|_| Err("MCJITMemoryManager::finalize_memory panicked".to_string()));
+
+    match result {
+        Ok(()) => 0,
+        Err(message) => {
+            // Interior nul bytes would otherwise truncate the message, or fail the conversion
+            let c_string = CString::new(message.replace('\0', "")).expect("Conversion to CString failed unexpectedly");
+
+            // LLVM frees the message itself, so it must be allocated by LLVM
+            unsafe {
+                *err_msg = LLVMCreateMessage(c_string.as_ptr());
+            }
+
+            1
+        },
+    }
+}
+
+extern "C" fn destroy_memory_manager<M: MCJITMemoryManager>(opaque: *mut c_void) {
+    let memory_manager = unsafe { Box::from_raw(opaque as *mut M) };
+
+    // There's nothing to report a panicking Drop to, but it still mustn't unwind into LLVM
+    let _ = catch_unwind(AssertUnwindSafe(move || drop(memory_manager)));
+}
+
+#[derive(Debug, PartialEq, Eq)]
+pub enum AddModuleError {
+    ModuleAlreadyOwned,
+}
+
+impl Error for AddModuleError {
+    fn source(&self) -> Option<&(Error + 'static)> {
+        None
+    }
+}
+
+impl AddModuleError {
+    fn as_str(&self) -> &str {
+        match self {
+            AddModuleError::ModuleAlreadyOwned => "Module is already owned by an Execution Engine",
+        }
+    }
+}
+
+impl Display for AddModuleError {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        write!(f, "AddModuleError({})", self.as_str())
+    }
+}
+
 #[derive(Debug, PartialEq, Eq)]
 pub enum RemoveModuleError {
     ModuleNotOwned,
@@ -54,14 +303,11 @@ pub enum RemoveModuleError {
 }
 
 impl Error for RemoveModuleError {
-    // This method is deprecated on nighty so it's probably not
-    // something we should worry about
-    fn description(&self) -> &str {
-        self.as_str()
-    }
-
-    fn cause(&self) -> Option<&Error> {
-        None
+    fn source(&self) -> Option<&(Error + 'static)> {
+        match self {
+            RemoveModuleError::LLVMError(string) => Some(string),
+            _ => None,
+        }
     }
 }
 
@@ -77,7 +323,175 @@ impl RemoveModuleError {
 
 impl Display for RemoveModuleError {
     fn fmt(&self, f: &mut Formatter) -> fmt::Result {
-        write!(f, "RemoveModuleError({})", self.as_str())
+        match self {
+            RemoveModuleError::LLVMError(string) => write!(f, "RemoveModuleError({})", string),
+            _ => write!(f, "RemoveModuleError({})", self.as_str()),
+        }
+    }
+}
+
+#[derive(Debug, PartialEq, Eq)]
+pub enum ExecutionEngineCreationError {
+    InterpreterUnavailable,
+    MCJITUnavailable,
+    TargetNotInitialized,
+    InvalidModule(LLVMString),
+    LLVMError(LLVMString),
+}
+
+impl ExecutionEngineCreationError {
+    // LLVM only reports these failures as strings, so we have to guess which case we hit
+    // based on the wording of the messages emitted by EngineBuilder
+    pub(crate) fn new(err_string: LLVMString) -> Self {
+        let is_target_error = match err_string.to_str() {
+            Ok(string) if string.contains("Interpreter") => return ExecutionEngineCreationError::InterpreterUnavailable,
+            Ok(string) if string.contains("JIT") => return ExecutionEngineCreationError::MCJITUnavailable,
+            Ok(string) => string.contains("no targets are registered") || string.contains("No available targets"),
+            Err(_) => false,
+        };
+
+        if is_target_error {
+            return ExecutionEngineCreationError::TargetNotInitialized;
+        }
+
+        ExecutionEngineCreationError::LLVMError(err_string)
+    }
+
+    fn as_str(&self) -> &str {
+        match self {
+            ExecutionEngineCreationError::InterpreterUnavailable => "Interpreter has not been linked in",
+            ExecutionEngineCreationError::MCJITUnavailable => "MCJIT has not been linked in",
+            ExecutionEngineCreationError::TargetNotInitialized => "No compatible targets have been initialized",
+            ExecutionEngineCreationError::InvalidModule(string) => string.to_str().unwrap_or("InvalidModule with invalid unicode"),
+            ExecutionEngineCreationError::LLVMError(string) => string.to_str().unwrap_or("LLVMError with invalid unicode"),
+        }
+    }
+}
+
+impl Error for ExecutionEngineCreationError {
+    fn source(&self) -> Option<&(Error + 'static)> {
+        match self {
+            ExecutionEngineCreationError::InvalidModule(string) |
+            ExecutionEngineCreationError::LLVMError(string) => Some(string),
+            _ => None,
+        }
+    }
+}
+
+impl Display for ExecutionEngineCreationError {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        match self {
+            ExecutionEngineCreationError::InvalidModule(string) |
+            ExecutionEngineCreationError::LLVMError(string) => write!(f, "ExecutionEngineCreationError({})", string),
+            _ => write!(f, "ExecutionEngineCreationError({})", self.as_str()),
+        }
+    }
+}
+
+#[derive(Debug, PartialEq, Eq)]
+pub enum JitFunctionError {
+    CreationError(ExecutionEngineCreationError),
+    LookupError(FunctionLookupError),
+}
+
+impl Error for JitFunctionError {
+    fn source(&self) -> Option<&(Error + 'static)> {
+        match self {
+            JitFunctionError::CreationError(err) => Some(err),
+            JitFunctionError::LookupError(err) => Some(err),
+        }
+    }
+}
+
+impl JitFunctionError {
+    fn as_str(&self) -> &str {
+        match self {
+            JitFunctionError::CreationError(err) => err.as_str(),
+            JitFunctionError::LookupError(err) => err.as_str(),
+        }
+    }
+}
+
+impl Display for JitFunctionError {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        match self {
+            JitFunctionError::CreationError(ExecutionEngineCreationError::InvalidModule(string)) |
+            JitFunctionError::CreationError(ExecutionEngineCreationError::LLVMError(string)) => write!(f, "JitFunctionError({})", string),
+            _ => write!(f, "JitFunctionError({})", self.as_str()),
+        }
+    }
+}
+
+impl From<ExecutionEngineCreationError> for JitFunctionError {
+    fn from(err: ExecutionEngineCreationError) -> Self {
+        JitFunctionError::CreationError(err)
+    }
+}
+
+impl From<FunctionLookupError> for JitFunctionError {
+    fn from(err: FunctionLookupError) -> Self {
+        JitFunctionError::LookupError(err)
+    }
+}
+
+#[derive(Debug, PartialEq, Eq)]
+pub enum RunFunctionError {
+    FunctionNotOwned,
+    IncorrectArgumentCount,
+    InvalidMainSignature,
+    JITNotSupported,
+    InstructionLimitExceeded,
+}
+
+impl Error for RunFunctionError {
+    fn source(&self) -> Option<&(Error + 'static)> {
+        None
+    }
+}
+
+impl RunFunctionError {
+    fn as_str(&self) -> &str {
+        match self {
+            RunFunctionError::FunctionNotOwned => "Function is not owned by this Execution Engine",
+            RunFunctionError::IncorrectArgumentCount => "Number of arguments does not match the function's parameter count",
+            RunFunctionError::InvalidMainSignature => "Function's signature is not compatible with i32 main(i32, i8**, i8**)",
+            RunFunctionError::JITNotSupported => "Instruction limits are not supported by JIT Execution Engines",
+            RunFunctionError::InstructionLimitExceeded => "Function exceeded its instruction limit",
+        }
+    }
+}
+
+impl Display for RunFunctionError {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        write!(f, "RunFunctionError({})", self.as_str())
+    }
+}
+
+#[derive(Debug, PartialEq, Eq)]
+pub enum FinalizeObjectError {
+    /// MCJIT failed to compile the `Module` with the contained name.
+    CompilationFailed(String),
+}
+
+impl Error for FinalizeObjectError {
+    fn source(&self) -> Option<&(Error + 'static)> {
+        None
+    }
+}
+
+impl FinalizeObjectError {
+    fn as_str(&self) -> &str {
+        match self {
+            FinalizeObjectError::CompilationFailed(_) => "Module failed to compile",
+        }
+    }
+}
+
+impl Display for FinalizeObjectError {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        match self {
+            FinalizeObjectError::CompilationFailed(name) => write!(f, "FinalizeObjectError({}: {:?})", self.as_str(), name),
+        }
     }
 }
 
@@ -87,41 +501,80 @@ impl Display for RemoveModuleError {
 /// and incrementing one or two atomics, so this should be quite cheap to create
 /// copies. The underlying LLVM object will be automatically deallocated when
 /// there are no more references to it.
-#[derive(PartialEq, Eq, Debug)]
+#[derive(Debug)]
 pub struct ExecutionEngine {
     execution_engine: ExecEngineInner,
-    target_data: Option<TargetData>,
+    // Shared between clones so that cloning doesn't need to go back through LLVM
+    target_data: Option<Rc<TargetData>>,
+    target_machine: Option<Rc<TargetMachine>>,
     jit_mode: bool,
+    // LLVM doesn't let us walk an EE's modules, so we keep track of them ourselves. Shared between
+    // clones so that every handle to the same EE sees the same modules
+    modules: Rc<RefCell<Vec<OwnedModule>>>,
+    // Functions whose machine code has been freed, so that they can't be looked up again. Keyed by
+    // module and name rather than by value, since the values would dangle once their module is removed
+    freed_functions: Rc<RefCell<Vec<(LLVMModuleRef, CString)>>>,
 }
 
 impl ExecutionEngine {
     pub(crate) fn new(execution_engine: Rc<LLVMExecutionEngineRef>, jit_mode: bool) -> ExecutionEngine {
         assert!(!execution_engine.is_null());
 
-        // REVIEW: Will we have to do this for LLVMGetExecutionEngineTargetMachine too?
         let target_data = unsafe {
             LLVMGetExecutionEngineTargetData(*execution_engine)
         };
 
+        // The interpreter doesn't have a TargetMachine, so this may be null
+        let target_machine = unsafe {
+            LLVMGetExecutionEngineTargetMachine(*execution_engine)
+        };
+
         ExecutionEngine {
             execution_engine: ExecEngineInner(execution_engine),
-            target_data: Some(TargetData::new(target_data)),
+            target_data: Some(Rc::new(TargetData::new(target_data))),
+            target_machine: if target_machine.is_null() { None } else { Some(Rc::new(TargetMachine::new(target_machine))) },
             jit_mode: jit_mode,
+            modules: Rc::new(RefCell::new(Vec::new())),
+            freed_functions: Rc::new(RefCell::new(Vec::new())),
         }
     }
 
+    /// Links in MCJIT so that JIT `ExecutionEngine`s may be created. See `Module::create_jit_execution_engine`.
     pub fn link_in_mc_jit() {
         unsafe {
             LLVMLinkInMCJIT()
         }
     }
 
+    /// Links in the interpreter so that interpreter `ExecutionEngine`s may be created. See
+    /// `Module::create_interpreter_execution_engine`.
     pub fn link_in_interpreter() {
         unsafe {
             LLVMLinkInInterpreter();
         }
     }
 
+    /// Determines whether or not this `ExecutionEngine` was created in JIT mode. Only JIT `ExecutionEngine`s
+    /// support looking up functions with `get_function`; otherwise `run_function` must be used to run them.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// # use inkwell::targets::{InitializationConfig, Target};
+    /// # use inkwell::context::Context;
+    /// # use inkwell::OptimizationLevel;
+    /// # Target::initialize_native(&InitializationConfig::default()).unwrap();
+    /// let context = Context::create();
+    /// let jit_ee = context.create_module("jit").create_jit_execution_engine(OptimizationLevel::None).unwrap();
+    /// let interpreter_ee = context.create_module("interpreter").create_interpreter_execution_engine().unwrap();
+    ///
+    /// assert!(jit_ee.is_jit());
+    /// assert!(!interpreter_ee.is_jit());
+    /// ```
+    pub fn is_jit(&self) -> bool {
+        self.jit_mode
+    }
+
     /// Maps the specified value to an address.
     ///
     /// # Example
@@ -157,7 +610,7 @@ impl ExecutionEngine {
     /// let mut ee = module.create_jit_execution_engine(OptimizationLevel::None).unwrap();
     /// ee.add_global_mapping(&extf, sumf as usize);
     ///
-    /// let result = unsafe { ee.run_function(&f, &[]) }.as_float(&ft);
+    /// let result = unsafe { ee.run_function(&f, &[]) }.unwrap().as_float(&ft);
     ///
     /// assert_eq!(result, 128.);
     /// ```
@@ -167,9 +620,51 @@ impl ExecutionEngine {
         }
     }
 
+    /// Maps each value to its address, as if by calling `add_global_mapping` for every entry.
+    ///
+    /// # Example
+    /// ```no_run
+    /// use inkwell::targets::{InitializationConfig, Target};
+    /// use inkwell::context::Context;
+    /// use inkwell::OptimizationLevel;
+    ///
+    /// Target::initialize_native(&InitializationConfig::default()).unwrap();
+    ///
+    /// extern fn sumf(a: f64, b: f64) -> f64 {
+    ///     a + b
+    /// }
+    ///
+    /// extern fn mulf(a: f64, b: f64) -> f64 {
+    ///     a * b
+    /// }
+    ///
+    /// let context = Context::create();
+    /// let module = context.create_module("test");
+    /// let ft = context.f64_type();
+    /// let fnt = ft.fn_type(&[ &ft, &ft ], false);
+    ///
+    /// let sum_fn = module.add_function("sumf", &fnt, None);
+    /// let mul_fn = module.add_function("mulf", &fnt, None);
+    ///
+    /// let ee = module.create_jit_execution_engine(OptimizationLevel::None).unwrap();
+    ///
+    /// ee.add_global_mappings(&[
+    ///     (&sum_fn, sumf as usize),
+    ///     (&mul_fn, mulf as usize),
+    /// ]);
+    /// ```
+    pub fn add_global_mappings(&self, mappings: &[(&AnyValue, usize)]) {
+        for &(value, addr) in mappings {
+            debug_assert!(self.owns_global(value.as_value_ref()), "Value is not owned by this ExecutionEngine");
+
+            self.add_global_mapping(value, addr);
+        }
+    }
+
     /// Adds a module to an `ExecutionEngine`.
     ///
-    /// The method will be `Ok(())` if the module does not belong to an `ExecutionEngine` already and `Err(())` otherwise.
+    /// The method will be `Ok(())` if the module does not belong to an `ExecutionEngine` already and
+    /// `Err(AddModuleError::ModuleAlreadyOwned)` otherwise, in which case the module is left untouched.
     ///
     /// ```rust,no_run
     /// use inkwell::targets::{InitializationConfig, Target};
@@ -184,23 +679,51 @@ impl ExecutionEngine {
     ///
     /// assert!(ee.add_module(&module).is_err());
     /// ```
-    pub fn add_module(&self, module: &Module) -> Result<(), ()> {
+    pub fn add_module(&self, module: &Module) -> Result<(), AddModuleError> {
+        if module.state.owned_by_ee.borrow().is_some() {
+            return Err(AddModuleError::ModuleAlreadyOwned);
+        }
+
         unsafe {
             LLVMAddModule(*self.execution_engine, module.module.get())
         }
 
-        if module.owned_by_ee.borrow().is_some() {
-            return Err(());
+        self.track_module(module);
+
+        Ok(())
+    }
+
+    // Records that `module`, which LLVM has already handed over to this EE, is now owned by it
+    pub(crate) fn track_module(&self, module: &Module) {
+        *module.state.owned_by_ee.borrow_mut() = Some(self.clone());
+
+        self.modules.borrow_mut().push(OwnedModule {
+            module: module.module.get(),
+            context: module.non_global_context.as_ref().map(|ctx| Context::new(ctx.context.clone())),
+            state: Rc::downgrade(&module.state),
+            frozen: module.state.frozen.clone(),
+        });
+    }
+
+    // Gets a handle to one of our modules, which shares its state with any other live handles to it
+    fn get_module_handle(&self, module: LLVMModuleRef) -> Option<Module> {
+        let mut modules = self.modules.borrow_mut();
+        let owned = modules.iter_mut().find(|owned| owned.module == module)?;
+
+        if let Some(state) = owned.state.upgrade() {
+            return Some(Module::new_shared(module, owned.context.as_ref(), state));
         }
 
-        *module.owned_by_ee.borrow_mut() = Some(self.clone());
+        let module = Module::new_owned(module, owned.context.as_ref(), self, &owned.frozen);
 
-        Ok(())
+        owned.state = Rc::downgrade(&module.state);
+
+        Some(module)
     }
 
     pub fn remove_module(&self, module: &Module) -> Result<(), RemoveModuleError> {
-        match *module.owned_by_ee.borrow() {
-            Some(ref ee) if *ee.execution_engine != *self.execution_engine => return Err(RemoveModuleError::IncorrectModuleOwner),
+        match *module.state.owned_by_ee.borrow() {
+            Some(ref ee) if ee != self => return Err(RemoveModuleError::IncorrectModuleOwner),
             None => return Err(RemoveModuleError::ModuleNotOwned),
             _ => ()
         }
@@ -216,29 +739,101 @@ impl ExecutionEngine {
             return Err(RemoveModuleError::LLVMError(LLVMString::new(err_string)));
         }
 
-        module.module.set(new_module);
-        *module.owned_by_ee.borrow_mut() = None;
+        // LLVM hands back the same module it was given
+        debug_assert_eq!(new_module, module.module.get());
+
+        self.modules.borrow_mut().retain(|owned| owned.module != module.module.get());
+        self.forget_freed_functions(module.module.get());
+
+        // The state is shared, so every other handle to the module sees that it's no longer owned
+        *module.state.owned_by_ee.borrow_mut() = None;
 
         Ok(())
     }
 
-    /// Try to load a function from the execution engine.
+    /// Removes the module whose identifier is `name` from this `ExecutionEngine`, returning it as
+    /// a `Module` which is no longer owned by any `ExecutionEngine`. Any other handles to the
+    /// module share the returned `Module`'s state, so the module is only disposed of once all of
+    /// them have been dropped.
     ///
-    /// If a target hasn't already been initialized, spurious "function not
-    /// found" errors may be encountered.
+    /// Returns `Err(RemoveModuleError::ModuleNotOwned)` if no module by that name is owned by this `ExecutionEngine`.
     ///
-    /// The [`UnsafeFunctionPointer`] trait is designed so only `unsafe extern
-    /// "C"` functions can be retrieved via the `get_function()` method. If you
-    /// get funny type errors then it's probably because you have specified the
-    /// wrong calling convention or forgotten to specify the retrieved function
-    /// as `unsafe`.
+    /// # Example
     ///
-    /// # Examples
+    /// ```no_run
+    /// use inkwell::targets::{InitializationConfig, Target};
+    /// use inkwell::context::Context;
+    /// use inkwell::OptimizationLevel;
     ///
+    /// Target::initialize_native(&InitializationConfig::default()).unwrap();
     ///
-    /// ```rust
-    /// # use inkwell::targets::{InitializationConfig, Target};
-    /// # use inkwell::context::Context;
+    /// let context = Context::create();
+    /// let ee = context.create_module("main").create_jit_execution_engine(OptimizationLevel::None).unwrap();
+    ///
+    /// ee.add_module(&context.create_module("plugin")).unwrap();
+    ///
+    /// let plugin = ee.remove_module_by_name("plugin").unwrap();
+    ///
+    /// assert_eq!(ee.modules().count(), 1);
+    /// ```
+    pub fn remove_module_by_name(&self, name: &str) -> Result<Module, RemoveModuleError> {
+        let module = self.modules.borrow().iter().map(|owned| owned.module).find(|&module| {
+            let mut length = 0;
+            let identifier = unsafe {
+                let identifier = LLVMGetModuleIdentifier(module, &mut length);
+
+                from_raw_parts(identifier as *const u8, length)
+            };
+
+            identifier == name.as_bytes()
+        });
+
+        let module = match module {
+            Some(module) => module,
+            None => return Err(RemoveModuleError::ModuleNotOwned),
+        };
+
+        let mut new_module = unsafe { uninitialized() };
+        let mut err_string = unsafe { zeroed() };
+
+        let code = unsafe {
+            LLVMRemoveModule(*self.execution_engine, module, &mut new_module, &mut err_string)
+        };
+
+        if code == 1 {
+            return Err(RemoveModuleError::LLVMError(LLVMString::new(err_string)));
+        }
+
+        debug_assert_eq!(new_module, module);
+
+        // Reuse the state of any existing handles, rather than making a second owner of the module
+        let module_handle = self.get_module_handle(module).expect("Module should still be tracked");
+
+        self.modules.borrow_mut().retain(|owned| owned.module != module);
+        self.forget_freed_functions(module);
+
+        *module_handle.state.owned_by_ee.borrow_mut() = None;
+
+        Ok(module_handle)
+    }
+
+    /// Try to load a function from the execution engine.
+    ///
+    /// If a target hasn't already been initialized, spurious "function not
+    /// found" errors may be encountered.
+    ///
+    /// The [`UnsafeFunctionPointer`] trait is designed so only `unsafe extern
+    /// "C"` functions can be retrieved via the `get_function()` method. If you
+    /// get funny type errors then it's probably because you have specified the
+    /// wrong calling convention or forgotten to specify the retrieved function
+    /// as `unsafe`.
+    ///
+    /// # Examples
+    ///
+    ///
+    /// ```rust
+    /// # use inkwell::targets::{InitializationConfig, Target};
+    /// # use inkwell::context::Context;
     /// # use inkwell::OptimizationLevel;
     /// # Target::initialize_native(&InitializationConfig::default()).unwrap();
     /// let context = Context::create();
@@ -269,10 +864,16 @@ impl ExecutionEngine {
     /// }
     /// ```
     ///
+    /// The number of parameters of `F` is checked against the function's declared
+    /// parameter count, returning `FunctionLookupError::SignatureMismatch` if they differ.
+    /// Since `F` must be an `extern "C"` function, the function must also use the C calling
+    /// convention, otherwise `FunctionLookupError::CallConvMismatch` is returned.
+    ///
     /// # Safety
     ///
     /// It is the caller's responsibility to ensure they call the function with
-    /// the correct signature and calling convention.
+    /// the correct signature and calling convention. Only the number of parameters
+    /// is verified, not their types.
     ///
     /// The `Symbol` wrapper ensures a function won't accidentally outlive the
     /// execution engine it came from, but adding functions after calling this
@@ -282,17 +883,158 @@ impl ExecutionEngine {
     pub unsafe fn get_function<F>(&self, fn_name: &str) -> Result<Symbol<F>, FunctionLookupError>
     where F: UnsafeFunctionPointer
     {
+        let address = self.get_function_address(fn_name)?;
+        let function = self.get_function_value(fn_name)?;
+        let expected = function.count_params();
+
+        // FunctionValue::get_type gives us the function's pointer type, so we have to dig out the function type ourselves
+        let is_var_arg = LLVMIsFunctionVarArg(LLVMGetElementType(LLVMTypeOf(function.as_value_ref()))) == 1;
+
+        if F::ARITY < expected || (F::ARITY > expected && !is_var_arg) {
+            return Err(FunctionLookupError::SignatureMismatch {
+                expected,
+                found: F::ARITY,
+            });
+        }
+
+        // UnsafeFunctionPointer is only implemented for extern "C" functions
+        let call_conventions = function.get_call_conventions();
+
+        if call_conventions != CallConv::C as u32 {
+            return Err(FunctionLookupError::CallConvMismatch {
+                found: call_conventions,
+            });
+        }
+
+        // Checked at compile time, so this can never fail at runtime
+        let () = F::ASSERT_POINTER_SIZED;
+
+        Ok(Symbol {
+            _execution_engine: self.execution_engine.clone(),
+            inner: transmute_copy(&address),
+        })
+    }
+
+    /// Attempts to look up a function's raw address by its name. This is useful when the
+    /// function's signature is only known at runtime; otherwise prefer `get_function`.
+    ///
+    /// Like `get_function`, functions with `InternalLinkage` or `PrivateLinkage` are not found,
+    /// since they aren't exported from their module.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// # use inkwell::targets::{InitializationConfig, Target};
+    /// # use inkwell::context::Context;
+    /// # use inkwell::OptimizationLevel;
+    /// # Target::initialize_native(&InitializationConfig::default()).unwrap();
+    /// let context = Context::create();
+    /// let module = context.create_module("test");
+    /// let builder = context.create_builder();
+    ///
+    /// let fn_type = context.void_type().fn_type(&[], false);
+    /// let fn_value = module.add_function("test_fn", &fn_type, None);
+    /// let entry = context.append_basic_block(&fn_value, "entry");
+    ///
+    /// builder.position_at_end(&entry);
+    /// builder.build_return(None);
+    ///
+    /// let ee = module.create_jit_execution_engine(OptimizationLevel::None).unwrap();
+    ///
+    /// assert!(unsafe { ee.get_function_address("test_fn") }.is_ok());
+    /// ```
+    ///
+    /// # Safety
+    ///
+    /// The returned address is only valid for as long as this `ExecutionEngine` is alive, and
+    /// adding functions after calling this method *may* invalidate it.
+    pub unsafe fn get_function_address(&self, fn_name: &str) -> Result<usize, FunctionLookupError> {
         if !self.jit_mode {
             return Err(FunctionLookupError::JITNotEnabled);
         }
 
-        // LLVMGetFunctionAddress segfaults in llvm 5.0 & 6.0 when fn_name doesn't exist. This is a workaround
-        // to see if it exists and avoid the segfault when it doesn't
-        #[cfg(any(feature = "llvm5-0", feature = "llvm6-0"))]
-        self.get_function_value(fn_name)?;
+        // LLVMGetFunctionAddress segfaults in llvm 5.0 & 6.0 when fn_name doesn't exist, so looking the function
+        // up first also avoids the segfault when it doesn't
+        let function = self.get_function_value(fn_name)?;
+
+        // MCJIT will happily resolve functions which aren't exported from their module, but they're not
+        // meant to be reachable from outside of it
+        match function.get_linkage() {
+            Linkage::InternalLinkage | Linkage::PrivateLinkage => return Err(FunctionLookupError::FunctionNotFound),
+            _ => (),
+        }
 
+        self.lookup_function_address(fn_name)
+    }
+
+    /// Attempts to look up a JIT compiled function by its name, like `get_function`, but without
+    /// first looking up its `FunctionValue`. This saves a lookup per call, which can add up in a hot
+    /// dispatch loop, at the cost of skipping the checks of `F`'s parameter count and the function's
+    /// linkage and calling convention, as well as (on LLVM 5.0 & 6.0) the workaround for `LLVMGetFunctionAddress` segfaulting
+    /// on unknown names.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// # use inkwell::targets::{InitializationConfig, Target};
+    /// # use inkwell::context::Context;
+    /// # use inkwell::OptimizationLevel;
+    /// # Target::initialize_native(&InitializationConfig::default()).unwrap();
+    /// let context = Context::create();
+    /// let module = context.create_module("test");
+    /// let builder = context.create_builder();
+    /// let i32_type = context.i32_type();
+    /// let fn_type = i32_type.fn_type(&[], false);
+    /// let fn_value = module.add_function("one", &fn_type, None);
+    /// let entry = context.append_basic_block(&fn_value, "entry");
+    ///
+    /// builder.position_at_end(&entry);
+    /// builder.build_return(Some(&i32_type.const_int(1, false)));
+    ///
+    /// let ee = module.create_jit_execution_engine(OptimizationLevel::None).unwrap();
+    ///
+    /// // Checked once up front, so the lookups below are known to succeed
+    /// assert!(ee.get_function_value("one").is_ok());
+    ///
+    /// for _ in 0..1000 {
+    ///     unsafe {
+    ///         let one = ee.get_function_unchecked::<unsafe extern "C" fn() -> i32>("one").unwrap();
+    ///
+    ///         assert_eq!(one(), 1);
+    ///     }
+    /// }
+    /// ```
+    ///
+    /// # Safety
+    ///
+    /// In addition to the requirements of `get_function`, a function named `fn_name` must be known
+    /// to exist in this `ExecutionEngine` (ie by a prior call to `get_function`), and `F` must
+    /// have the right number of parameters since it is not checked.
+    pub unsafe fn get_function_unchecked<F>(&self, fn_name: &str) -> Result<Symbol<F>, FunctionLookupError>
+    where F: UnsafeFunctionPointer
+    {
+        if !self.jit_mode {
+            return Err(FunctionLookupError::JITNotEnabled);
+        }
+
+        let address = self.lookup_function_address(fn_name)?;
+
+        // Checked at compile time, so this can never fail at runtime
+        let () = F::ASSERT_POINTER_SIZED;
+
+        Ok(Symbol {
+            _execution_engine: self.execution_engine.clone(),
+            inner: transmute_copy(&address),
+        })
+    }
+
+    unsafe fn lookup_function_address(&self, fn_name: &str) -> Result<usize, FunctionLookupError> {
         let c_string = CString::new(fn_name).expect("Conversion to CString failed unexpectedly");
 
+        if self.freed_functions.borrow().iter().any(|&(_, ref name)| *name == c_string) {
+            return Err(FunctionLookupError::FunctionFreed);
+        }
+
         let address = LLVMGetFunctionAddress(*self.execution_engine, c_string.as_ptr());
 
         // REVIEW: Can also return 0 if no targets are initialized.
@@ -303,13 +1045,115 @@ impl ExecutionEngine {
             return Err(FunctionLookupError::FunctionNotFound);
         }
 
-        assert_eq!(size_of::<F>(), size_of::<usize>(),
-            "The type `F` must have the same size as a function pointer");
+        Ok(address as usize)
+    }
 
-        Ok(Symbol {
-            _execution_engine: self.execution_engine.clone(),
-            inner: transmute_copy(&address),
-        })
+    /// Gets an iterator over the `Module`s owned by this `ExecutionEngine`, in the order
+    /// they were added.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// # use inkwell::targets::{InitializationConfig, Target};
+    /// # use inkwell::context::Context;
+    /// # use inkwell::OptimizationLevel;
+    /// # Target::initialize_native(&InitializationConfig::default()).unwrap();
+    /// let context = Context::create();
+    /// let module = context.create_module("module");
+    /// let module2 = context.create_module("module2");
+    /// let ee = module.create_jit_execution_engine(OptimizationLevel::None).unwrap();
+    ///
+    /// ee.add_module(&module2).unwrap();
+    ///
+    /// for module in ee.modules() {
+    ///     assert!(module.verify().is_ok());
+    /// }
+    /// ```
+    pub fn modules(&self) -> ModuleIter {
+        let modules: Vec<_> = self.modules.borrow().iter().map(|owned| owned.module).collect();
+
+        ModuleIter {
+            execution_engine: self.clone(),
+            modules: modules.into_iter(),
+        }
+    }
+
+    /// Gets an iterator over every function in the `Module`s owned by this `ExecutionEngine`,
+    /// including declarations. Functions which have a body have at least one basic block.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// # use inkwell::targets::{InitializationConfig, Target};
+    /// # use inkwell::context::Context;
+    /// # use inkwell::OptimizationLevel;
+    /// # Target::initialize_native(&InitializationConfig::default()).unwrap();
+    /// let context = Context::create();
+    /// let module = context.create_module("module");
+    /// let ee = module.create_jit_execution_engine(OptimizationLevel::None).unwrap();
+    ///
+    /// for function in ee.function_values().filter(|function| function.count_basic_blocks() > 0) {
+    ///     println!("{:?}", function.get_name());
+    /// }
+    /// ```
+    pub fn function_values(&self) -> FunctionValueIter {
+        let modules = self.modules.borrow().iter().map(|owned| owned.module).collect();
+
+        FunctionValueIter::new(Some(self.clone()), modules)
+    }
+
+    /// Attempts to look up the address of a global variable compiled by the JIT by its name.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// # use inkwell::targets::{InitializationConfig, Target};
+    /// # use inkwell::context::Context;
+    /// # use inkwell::OptimizationLevel;
+    /// # Target::initialize_native(&InitializationConfig::default()).unwrap();
+    /// let context = Context::create();
+    /// let module = context.create_module("test");
+    /// let i32_type = context.i32_type();
+    /// let global = module.add_global(&i32_type, None, "counter");
+    ///
+    /// global.set_initializer(&i32_type.const_int(0, false));
+    ///
+    /// let ee = module.create_jit_execution_engine(OptimizationLevel::None).unwrap();
+    /// let counter = unsafe { ee.get_global_address("counter") }.unwrap() as *mut i32;
+    ///
+    /// unsafe {
+    ///     *counter += 1;
+    /// }
+    /// ```
+    ///
+    /// # Safety
+    ///
+    /// The returned address is only valid for as long as this `ExecutionEngine` is alive.
+    pub unsafe fn get_global_address(&self, name: &str) -> Result<usize, FunctionLookupError> {
+        if !self.jit_mode {
+            return Err(FunctionLookupError::JITNotEnabled);
+        }
+
+        let c_string = CString::new(name).expect("Conversion to CString failed unexpectedly");
+
+        // Same workaround as get_function_address, only LLVMFindFunction won't find globals so we
+        // look through our modules instead
+        #[cfg(any(feature = "llvm5-0", feature = "llvm6-0"))]
+        {
+            let is_found = self.modules.borrow().iter().any(|&OwnedModule { module, .. }| !LLVMGetNamedGlobal(module, c_string.as_ptr()).is_null());
+
+            if !is_found {
+                return Err(FunctionLookupError::FunctionNotFound);
+            }
+        }
+
+        let address = LLVMGetGlobalValueAddress(*self.execution_engine, c_string.as_ptr());
+
+        if address == 0 {
+            return Err(FunctionLookupError::FunctionNotFound);
+        }
+
+        Ok(address as usize)
     }
 
     // REVIEW: Not sure if an EE's target data can change.. if so we might want to update the value
@@ -318,6 +1162,54 @@ impl ExecutionEngine {
         self.target_data.as_ref().expect("TargetData should always exist until Drop")
     }
 
+    /// Gets the string representation of the data layout used by this `ExecutionEngine`. Together
+    /// with the triple of its `TargetMachine`, this can be used to check whether previously
+    /// compiled code matches the current `ExecutionEngine` before reusing it.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// # use inkwell::targets::{InitializationConfig, Target};
+    /// # use inkwell::context::Context;
+    /// # use inkwell::OptimizationLevel;
+    /// # Target::initialize_native(&InitializationConfig::default()).unwrap();
+    /// let context = Context::create();
+    /// let module = context.create_module("test");
+    /// let ee = module.create_jit_execution_engine(OptimizationLevel::None).unwrap();
+    /// let data_layout = ee.get_data_layout_string();
+    /// let triple = ee.get_target_machine().unwrap().get_triple();
+    ///
+    /// println!("JIT target: {} ({})", triple, data_layout);
+    /// ```
+    pub fn get_data_layout_string(&self) -> LLVMString {
+        let data_layout = unsafe {
+            LLVMCopyStringRepOfTargetData(self.get_target_data().target_data)
+        };
+
+        LLVMString::new(data_layout)
+    }
+
+    /// Gets the `TargetMachine` used by this `ExecutionEngine`, if any. Interpreter
+    /// `ExecutionEngine`s do not have one.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// # use inkwell::targets::{InitializationConfig, Target};
+    /// # use inkwell::context::Context;
+    /// # use inkwell::OptimizationLevel;
+    /// # Target::initialize_native(&InitializationConfig::default()).unwrap();
+    /// let context = Context::create();
+    /// let module = context.create_module("test");
+    /// let ee = module.create_jit_execution_engine(OptimizationLevel::None).unwrap();
+    /// let target_machine = ee.get_target_machine().unwrap();
+    ///
+    /// println!("JIT CPU: {}", target_machine.get_cpu());
+    /// ```
+    pub fn get_target_machine(&self) -> Option<&TargetMachine> {
+        self.target_machine.as_ref().map(|target_machine| &**target_machine)
+    }
+
     // REVIEW: Can also find nothing if no targeting is initialized. Maybe best to
     // do have a global flag for anything initialized. Catch is that it must be initialized
     // before EE is created
@@ -340,67 +1232,723 @@ impl ExecutionEngine {
         Err(FunctionLookupError::FunctionNotFound)
     }
 
+    /// Like `get_function_value`, but returns `Err(FunctionLookupError::DeclarationOnly)`
+    /// rather than `Err(FunctionLookupError::FunctionNotFound)` if a function by that name
+    /// exists in one of this `ExecutionEngine`'s modules but has no body, such as an extern
+    /// which was never defined.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// # use inkwell::targets::{InitializationConfig, Target};
+    /// # use inkwell::context::Context;
+    /// # use inkwell::execution_engine::FunctionLookupError;
+    /// # use inkwell::OptimizationLevel;
+    /// # Target::initialize_native(&InitializationConfig::default()).unwrap();
+    /// let context = Context::create();
+    /// let module = context.create_module("test");
+    /// let fn_type = context.void_type().fn_type(&[], false);
+    ///
+    /// module.add_function("extern_fn", &fn_type, None);
+    ///
+    /// let ee = module.create_jit_execution_engine(OptimizationLevel::None).unwrap();
+    ///
+    /// assert_eq!(ee.get_function_value("extern_fn"), Err(FunctionLookupError::FunctionNotFound));
+    /// assert_eq!(ee.get_function_definition("extern_fn"), Err(FunctionLookupError::DeclarationOnly));
+    /// ```
+    pub fn get_function_definition(&self, fn_name: &str) -> Result<FunctionValue, FunctionLookupError> {
+        match self.get_function_value(fn_name) {
+            Ok(function) => {
+                if function.count_basic_blocks() == 0 {
+                    return Err(FunctionLookupError::DeclarationOnly);
+                }
+
+                Ok(function)
+            },
+            // LLVMFindFunction skips over declarations, so we have to look for them ourselves
+            Err(FunctionLookupError::FunctionNotFound) => {
+                let c_string = CString::new(fn_name).expect("Conversion to CString failed unexpectedly");
+                let is_declared = self.modules.borrow().iter().any(|&OwnedModule { module, .. }| unsafe {
+                    !LLVMGetNamedFunction(module, c_string.as_ptr()).is_null()
+                });
+
+                if is_declared {
+                    return Err(FunctionLookupError::DeclarationOnly);
+                }
+
+                Err(FunctionLookupError::FunctionNotFound)
+            },
+            Err(err) => Err(err),
+        }
+    }
+
+    /// Runs `function` with the given `args`.
+    ///
+    /// Returns `Err(RunFunctionError::FunctionNotOwned)` if `function` can't be found in a module owned
+    /// by this `ExecutionEngine` and `Err(RunFunctionError::IncorrectArgumentCount)` if the number of
+    /// `args` doesn't match the function's parameter count.
     // TODOC: Marked as unsafe because input function could very well do something unsafe. It's up to the caller
     // to ensure that doesn't happen by defining their function correctly.
-    pub unsafe fn run_function(&self, function: &FunctionValue, args: &[&GenericValue]) -> GenericValue {
+    pub unsafe fn run_function(&self, function: &FunctionValue, args: &[&GenericValue]) -> Result<GenericValue, RunFunctionError> {
+        if !self.owns_function(function) {
+            return Err(RunFunctionError::FunctionNotOwned);
+        }
+
+        if args.len() != function.count_params() as usize {
+            return Err(RunFunctionError::IncorrectArgumentCount);
+        }
+
         let mut args: Vec<LLVMGenericValueRef> = args.iter()
                                                      .map(|val| val.generic_value)
                                                      .collect();
 
         let value = LLVMRunFunction(*self.execution_engine, function.as_value_ref(), args.len() as u32, args.as_mut_ptr()); // REVIEW: usize to u32 ok??
 
-        GenericValue::new(value)
+        Ok(GenericValue::new(value))
     }
 
+    /// Runs `function` with the given `args` like `run_function`, but stops it once it has executed
+    /// `max_instructions` instructions, in which case `Err(RunFunctionError::InstructionLimitExceeded)`
+    /// is returned. This is only supported by interpreter `ExecutionEngine`s, JIT ones return
+    /// `Err(RunFunctionError::JITNotSupported)`.
+    ///
+    /// LLVM's interpreter can't count instructions itself, so every function defined by the modules owned
+    /// by this `ExecutionEngine` is instrumented the first time it could run under a limit, which is
+    /// visible in their IR. Each basic block starts with, and each call is followed by, a check which
+    /// deducts from a budget stored in the `inkwell.instruction_budget` global. Once it runs out, functions
+    /// return `undef` at their next check, so the instructions between the last check and it still run.
+    ///
+    /// The limit is best effort: only instructions of the original IR are counted, a call to an external
+    /// function counts as a single instruction, and blocks jumped to with `indirectbr` skip their first check.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use inkwell::context::Context;
+    /// use inkwell::execution_engine::RunFunctionError;
+    ///
+    /// let context = Context::create();
+    /// let module = context.create_module("test");
+    /// let builder = context.create_builder();
+    ///
+    /// let fn_type = context.void_type().fn_type(&[], false);
+    /// let fn_value = module.add_function("spin", &fn_type, None);
+    /// let entry = context.append_basic_block(&fn_value, "entry");
+    /// let spin = context.append_basic_block(&fn_value, "spin");
+    ///
+    /// builder.position_at_end(&entry);
+    /// builder.build_unconditional_branch(&spin);
+    /// builder.position_at_end(&spin);
+    /// builder.build_unconditional_branch(&spin);
+    ///
+    /// let ee = module.create_interpreter_execution_engine().unwrap();
+    /// let result = unsafe { ee.run_function_with_limit(&fn_value, &[], 1000) };
+    ///
+    /// assert_eq!(result.unwrap_err(), RunFunctionError::InstructionLimitExceeded);
+    /// ```
+    // TODOC: Marked as unsafe for the same reasons as run_function
+    #[cfg(not(any(feature = "llvm3-6", feature = "llvm3-7", feature = "llvm3-8")))]
+    pub unsafe fn run_function_with_limit(&self, function: &FunctionValue, args: &[&GenericValue], max_instructions: u64) -> Result<GenericValue, RunFunctionError> {
+        if self.jit_mode {
+            return Err(RunFunctionError::JITNotSupported);
+        }
+
+        if !self.owns_function(function) {
+            return Err(RunFunctionError::FunctionNotOwned);
+        }
+
+        if args.len() != function.count_params() as usize {
+            return Err(RunFunctionError::IncorrectArgumentCount);
+        }
+
+        // Modules may have been added or had functions added since the last run, so every module is
+        // checked for functions which still need instrumenting
+        let function_module = LLVMGetGlobalParent(function.as_value_ref());
+        let mut budget_global = ptr::null_mut();
+
+        for &OwnedModule { module, .. } in self.modules.borrow().iter() {
+            let global = instrument_instruction_limit(module);
+
+            if module == function_module {
+                budget_global = global;
+            }
+        }
+
+        // The interpreter maps globals to memory by name, so every module's budget global shares this
+        let budget = LLVMGetPointerToGlobal(*self.execution_engine, budget_global) as *mut i64;
+
+        *budget = if max_instructions > i64::max_value() as u64 { i64::max_value() } else { max_instructions as i64 };
+
+        let mut args: Vec<LLVMGenericValueRef> = args.iter()
+                                                     .map(|val| val.generic_value)
+                                                     .collect();
+
+        let value = GenericValue::new(LLVMRunFunction(*self.execution_engine, function.as_value_ref(), args.len() as u32, args.as_mut_ptr()));
+        let exceeded = *budget < 0;
+
+        // Lift the limit again, since run_function executes the same instrumented IR
+        *budget = i64::max_value();
+
+        if exceeded {
+            return Err(RunFunctionError::InstructionLimitExceeded);
+        }
+
+        Ok(value)
+    }
+
+    // Checks that the function's parent module is one we own. This also catches functions belonging
+    // to modules which have since been removed from this EE
+    fn owns_function(&self, function: &FunctionValue) -> bool {
+        self.owns_global(function.as_value_ref())
+    }
+
+    fn owns_global(&self, value: LLVMValueRef) -> bool {
+        let module = unsafe {
+            LLVMGetGlobalParent(value)
+        };
+
+        self.modules.borrow().iter().any(|owned| owned.module == module)
+    }
+
+    /// Runs `function` as if it were a C `main` function, with `args` as its `argv`.
+    ///
+    /// `function` must return an `i32` and take at most three parameters, which must be
+    /// an `i32` followed by pointers as in `i32 main(i32 argc, i8** argv, i8** envp)`.
+    /// Otherwise, `Err(RunFunctionError::InvalidMainSignature)` is returned rather than
+    /// letting LLVM abort the process. `Err(RunFunctionError::FunctionNotOwned)` is returned
+    /// if `function` doesn't belong to a module owned by this `ExecutionEngine`.
     // TODOC: Marked as unsafe because input function could very well do something unsafe. It's up to the caller
     // to ensure that doesn't happen by defining their function correctly.
     // SubType: Only for JIT EEs?
-    pub unsafe fn run_function_as_main(&self, function: &FunctionValue, args: &[&str]) -> c_int {
+    pub unsafe fn run_function_as_main(&self, function: &FunctionValue, args: &[&str]) -> Result<c_int, RunFunctionError> {
+        self.check_main(function, args)?;
+
         let cstring_args: Vec<CString> = args.iter().map(|&arg| CString::new(arg).expect("Conversion to CString failed unexpectedly")).collect();
         let raw_args: Vec<*const _> = cstring_args.iter().map(|arg| arg.as_ptr()).collect();
 
         let environment_variables = vec![]; // TODO: Support envp. Likely needs to be null terminated
 
-        LLVMRunFunctionAsMain(*self.execution_engine, function.as_value_ref(), raw_args.len() as u32, raw_args.as_ptr(), environment_variables.as_ptr()) // REVIEW: usize to u32 cast ok??
+        Ok(LLVMRunFunctionAsMain(*self.execution_engine, function.as_value_ref(), raw_args.len() as u32, raw_args.as_ptr(), environment_variables.as_ptr()))
+    }
+
+    // LLVM reports a fatal error (aborting the process) when main's signature isn't one it can call,
+    // so we have to check it ourselves first
+    fn check_main(&self, function: &FunctionValue, args: &[&str]) -> Result<(), RunFunctionError> {
+        if !self.owns_function(function) {
+            return Err(RunFunctionError::FunctionNotOwned);
+        }
+
+        if args.len() > u32::max_value() as usize {
+            return Err(RunFunctionError::IncorrectArgumentCount);
+        }
+
+        // FunctionValue::get_type gives us the function's pointer type, so we have to dig out the function type ourselves
+        let fn_type = unsafe {
+            LLVMGetElementType(LLVMTypeOf(function.as_value_ref()))
+        };
+        let returns_i32 = unsafe {
+            let return_type = LLVMGetReturnType(fn_type);
+
+            LLVMGetTypeKind(return_type) == LLVMTypeKind::LLVMIntegerTypeKind && LLVMGetIntTypeWidth(return_type) == 32
+        };
+
+        if !returns_i32 {
+            return Err(RunFunctionError::InvalidMainSignature);
+        }
+
+        let param_types = FunctionType::new(fn_type).get_param_types();
+        let params_valid = param_types.len() <= 3 && param_types.iter().enumerate().all(|(i, param_type)| match i {
+            0 => param_type.is_int_type() && param_type.as_int_type().get_bit_width() == 32,
+            _ => param_type.is_pointer_type(),
+        });
+
+        if !params_valid {
+            return Err(RunFunctionError::InvalidMainSignature);
+        }
+
+        Ok(())
+    }
+
+    /// Runs `function` like `run_function_as_main`, but first runs this `ExecutionEngine`'s static
+    /// constructors if `run_ctors` is true, much like `lli` does. The first of `args` is used as `argv[0]`.
+    ///
+    /// Returns `function`'s return value along with whether or not any static constructors were run,
+    /// or the same errors as `run_function_as_main`, in which case no constructors are run.
+    // TODOC: Marked as unsafe because input function could very well do something unsafe. It's up to the caller
+    // to ensure that doesn't happen by defining their function correctly.
+    pub unsafe fn run_function_as_main_with_ctors(&self, function: &FunctionValue, args: &[&str], run_ctors: bool) -> Result<(c_int, bool), RunFunctionError> {
+        self.check_main(function, args)?;
+
+        let ran_ctors = run_ctors && self.has_static_constructors();
+
+        if ran_ctors {
+            self.run_static_constructors();
+        }
+
+        Ok((self.run_function_as_main(function, args)?, ran_ctors))
     }
 
-    pub fn free_fn_machine_code(&self, function: &FunctionValue) {
+    fn has_static_constructors(&self) -> bool {
+        let c_string = CString::new("llvm.global_ctors").expect("Conversion to CString failed unexpectedly");
+
+        self.modules.borrow().iter().any(|&OwnedModule { module, .. }| {
+            let global = unsafe {
+                LLVMGetNamedGlobal(module, c_string.as_ptr())
+            };
+
+            !global.is_null() && unsafe { LLVMIsDeclaration(global) } == 0
+        })
+    }
+
+    /// Frees the machine code generated for `function`. Afterwards, looking it up with
+    /// `get_function`, `get_function_unchecked` or `get_function_address` returns
+    /// `Err(FunctionLookupError::FunctionFreed)` rather than a dangling address.
+    ///
+    /// Returns `Err(FunctionLookupError::FunctionNotOwned)` if `function` doesn't belong to a module
+    /// owned by this `ExecutionEngine`.
+    ///
+    /// Note that LLVM implements this as a no-op under MCJIT, so the machine code is actually only
+    /// released once the `ExecutionEngine` is dropped. Even so, `Symbol`s or addresses which were
+    /// looked up beforehand should no longer be called.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// # use inkwell::targets::{InitializationConfig, Target};
+    /// # use inkwell::context::Context;
+    /// # use inkwell::execution_engine::FunctionLookupError;
+    /// # use inkwell::OptimizationLevel;
+    /// # Target::initialize_native(&InitializationConfig::default()).unwrap();
+    /// let context = Context::create();
+    /// let module = context.create_module("test");
+    /// let builder = context.create_builder();
+    /// let fn_type = context.void_type().fn_type(&[], false);
+    /// let fn_value = module.add_function("test_fn", &fn_type, None);
+    /// let entry = context.append_basic_block(&fn_value, "entry");
+    ///
+    /// builder.position_at_end(&entry);
+    /// builder.build_return(None);
+    ///
+    /// let ee = module.create_jit_execution_engine(OptimizationLevel::None).unwrap();
+    ///
+    /// ee.free_fn_machine_code(&fn_value).unwrap();
+    ///
+    /// assert_eq!(unsafe { ee.get_function_address("test_fn") }, Err(FunctionLookupError::FunctionFreed));
+    /// ```
+    pub fn free_fn_machine_code(&self, function: &FunctionValue) -> Result<(), FunctionLookupError> {
+        if !self.owns_function(function) {
+            return Err(FunctionLookupError::FunctionNotOwned);
+        }
+
         unsafe {
             LLVMFreeMachineCodeForFunction(*self.execution_engine, function.as_value_ref())
         }
+
+        let module = unsafe {
+            LLVMGetGlobalParent(function.as_value_ref())
+        };
+        let freed_function = (module, function.get_name().to_owned());
+        let mut freed_functions = self.freed_functions.borrow_mut();
+
+        if !freed_functions.contains(&freed_function) {
+            freed_functions.push(freed_function);
+        }
+
+        Ok(())
+    }
+
+    // A removed module's functions may be added again later, at which point they can be looked up again
+    fn forget_freed_functions(&self, module: LLVMModuleRef) {
+        self.freed_functions.borrow_mut().retain(|&(freed_module, _)| freed_module != module);
+    }
+
+    /// Compiles the modules owned by this `ExecutionEngine` up front. Otherwise, MCJIT only compiles
+    /// a module the first time one of its functions is looked up. This is a no-op for interpreter `ExecutionEngine`s.
+    ///
+    /// Only modules which define at least one function without private or internal linkage are compiled,
+    /// since they have nothing which could be called into. The rest are skipped.
+    ///
+    /// Returns `Err(FinalizeObjectError::CompilationFailed)` with the name of the first module which failed to compile.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// # use inkwell::targets::{InitializationConfig, Target};
+    /// # use inkwell::context::Context;
+    /// # use inkwell::OptimizationLevel;
+    /// # Target::initialize_native(&InitializationConfig::default()).unwrap();
+    /// let context = Context::create();
+    /// let module = context.create_module("test");
+    /// let builder = context.create_builder();
+    ///
+    /// let fn_type = context.void_type().fn_type(&[], false);
+    /// let fn_value = module.add_function("test_fn", &fn_type, None);
+    /// let entry = context.append_basic_block(&fn_value, "entry");
+    ///
+    /// builder.position_at_end(&entry);
+    /// builder.build_return(None);
+    ///
+    /// let ee = module.create_jit_execution_engine(OptimizationLevel::None).unwrap();
+    ///
+    /// // Pay the compilation cost now rather than on the first lookup
+    /// ee.finalize_object().unwrap();
+    /// ```
+    pub fn finalize_object(&self) -> Result<(), FinalizeObjectError> {
+        if !self.jit_mode {
+            return Ok(());
+        }
+
+        // The C API doesn't expose MCJIT's finalizeObject, but LLVMGetFunctionAddress compiles the module
+        // defining the function and then finalizes all loaded modules. So we look up one function from each
+        // module, which must be a definition MCJIT exports, ie not one with private or internal linkage.
+        // A module without one has nothing which could be called into, so there's no need to compile it
+        for &OwnedModule { module, .. } in self.modules.borrow().iter() {
+            let mut function = FunctionValue::new(unsafe { LLVMGetFirstFunction(module) });
+
+            while let Some(fn_value) = function {
+                let is_exported = match fn_value.get_linkage() {
+                    Linkage::InternalLinkage | Linkage::PrivateLinkage => false,
+                    _ => true,
+                };
+
+                if is_exported && fn_value.count_basic_blocks() > 0 {
+                    let address = unsafe {
+                        LLVMGetFunctionAddress(*self.execution_engine, fn_value.get_name().as_ptr())
+                    };
+
+                    if address == 0 {
+                        let mut length = 0;
+                        let name = unsafe {
+                            let identifier = LLVMGetModuleIdentifier(module, &mut length);
+
+                            String::from_utf8_lossy(from_raw_parts(identifier as *const u8, length)).into_owned()
+                        };
+
+                        return Err(FinalizeObjectError::CompilationFailed(name));
+                    }
+
+                    break;
+                }
+
+                function = fn_value.get_next_function();
+            }
+        }
+
+        Ok(())
     }
 
+    /// Runs the static constructors (those listed in `llvm.global_ctors`) of every module
+    /// owned by this `ExecutionEngine`, not just the most recently added one.
+    ///
+    /// Running them again will rerun constructors which have already been run.
+    // TODO: Support running the constructors of a single module. MCJIT can do this
+    // (ExecutionEngine::runStaticConstructorsDestructors(Module&, bool)), but only the engine
+    // wide version is exposed through the C API. The alternative is walking the module's
+    // llvm.global_ctors ourselves and calling each function by address, which doesn't work
+    // for constructors with local linkage since MCJIT doesn't export their symbols
     pub fn run_static_constructors(&self) {
         unsafe {
             LLVMRunStaticConstructors(*self.execution_engine)
         }
     }
 
+    /// Runs the static destructors (those listed in `llvm.global_dtors`) of every module
+    /// owned by this `ExecutionEngine`.
     pub fn run_static_destructors(&self) {
         unsafe {
             LLVMRunStaticDestructors(*self.execution_engine)
         }
     }
+
+    // TODO: Support an object cache (ie set_object_cache(&self, cache: impl ObjectCache)) so that
+    // compiled objects can be persisted and reloaded. MCJIT's ObjectCache is only exposed through
+    // the C++ API (ExecutionEngine::setObjectCache); neither LLVMMCJITCompilerOptions nor any other
+    // part of the C API lets us register one, so this would require a C++ shim compiled by build.rs
+}
+
+#[cfg(not(any(feature = "llvm3-6", feature = "llvm3-7", feature = "llvm3-8")))]
+const INSTRUCTION_BUDGET_GLOBAL: &str = "inkwell.instruction_budget";
+#[cfg(not(any(feature = "llvm3-6", feature = "llvm3-7", feature = "llvm3-8")))]
+const INSTRUCTION_LIMIT_ATTRIBUTE: &str = "inkwell-instruction-limit";
+
+// Instruments every function defined in `module` which hasn't been already, and returns the module's
+// budget global. Instrumented functions are marked with a string attribute so that this can be rerun
+#[cfg(not(any(feature = "llvm3-6", feature = "llvm3-7", feature = "llvm3-8")))]
+unsafe fn instrument_instruction_limit(module: LLVMModuleRef) -> LLVMValueRef {
+    let context = LLVMGetModuleContext(module);
+    let i64_type = LLVMInt64TypeInContext(context);
+    let global_name = CString::new(INSTRUCTION_BUDGET_GLOBAL).expect("Conversion to CString failed unexpectedly");
+    let mut budget = LLVMGetNamedGlobal(module, global_name.as_ptr());
+
+    if budget.is_null() {
+        budget = LLVMAddGlobal(module, i64_type, global_name.as_ptr());
+
+        LLVMSetInitializer(budget, LLVMConstInt(i64_type, i64::max_value() as u64, 0));
+        LLVMSetLinkage(budget, LLVMLinkage::LLVMInternalLinkage);
+    }
+
+    let builder = LLVMCreateBuilderInContext(context);
+    let mut function = LLVMGetFirstFunction(module);
+
+    while !function.is_null() {
+        let is_instrumented = !LLVMGetStringAttributeAtIndex(function, LLVMAttributeFunctionIndex, INSTRUCTION_LIMIT_ATTRIBUTE.as_ptr() as *const _, INSTRUCTION_LIMIT_ATTRIBUTE.len() as u32).is_null();
+
+        if !is_instrumented && LLVMCountBasicBlocks(function) > 0 {
+            instrument_function(builder, budget, function);
+
+            let attribute = LLVMCreateStringAttribute(context, INSTRUCTION_LIMIT_ATTRIBUTE.as_ptr() as *const _, INSTRUCTION_LIMIT_ATTRIBUTE.len() as u32, ptr::null(), 0);
+
+            LLVMAddAttributeAtIndex(function, LLVMAttributeFunctionIndex, attribute);
+        }
+
+        function = LLVMGetNextFunction(function);
+    }
+
+    LLVMDisposeBuilder(builder);
+
+    budget
+}
+
+// Adds a block which returns undef once the budget has run out, and splits each of the function's
+// blocks so that its check comes first, with another after every call in case the callee ran out
+#[cfg(not(any(feature = "llvm3-6", feature = "llvm3-7", feature = "llvm3-8")))]
+unsafe fn instrument_function(builder: LLVMBuilderRef, budget: LLVMValueRef, function: LLVMValueRef) {
+    let context = LLVMGetTypeContext(LLVMTypeOf(function));
+    let mut blocks = Vec::new();
+    let mut block = LLVMGetFirstBasicBlock(function);
+
+    while !block.is_null() {
+        blocks.push(block);
+        block = LLVMGetNextBasicBlock(block);
+    }
+
+    let exceeded_name = CString::new("instruction_limit_exceeded").expect("Conversion to CString failed unexpectedly");
+    let exceeded_block = LLVMAppendBasicBlockInContext(context, function, exceeded_name.as_ptr());
+    let return_type = LLVMGetReturnType(LLVMGetElementType(LLVMTypeOf(function)));
+
+    LLVMPositionBuilderAtEnd(builder, exceeded_block);
+
+    if LLVMGetTypeKind(return_type) == LLVMTypeKind::LLVMVoidTypeKind {
+        LLVMBuildRetVoid(builder);
+    } else {
+        LLVMBuildRet(builder, LLVMGetUndef(return_type));
+    }
+
+    for block in blocks {
+        // Predecessors have to branch to the first check instead, which is collected before any
+        // of our own branches to the block exist
+        let block_value = LLVMBasicBlockAsValue(block);
+        let mut predecessors = Vec::new();
+        let mut use_ = LLVMGetFirstUse(block_value);
+
+        while !use_.is_null() {
+            let user = LLVMGetUser(use_);
+
+            if !LLVMIsABranchInst(user).is_null() || !LLVMIsASwitchInst(user).is_null() {
+                predecessors.push(user);
+            }
+
+            use_ = LLVMGetNextUse(use_);
+        }
+
+        // PHIs have to stay at the start of the block its predecessors branch to. Every other
+        // instruction is grouped into the spans between checks, the last of which stays in `block`
+        // along with its terminator, so that successors' PHIs still refer to the right block
+        let mut phis = Vec::new();
+        let mut spans = vec![Vec::new()];
+        let mut instruction = LLVMGetFirstInstruction(block);
+
+        while !instruction.is_null() {
+            match LLVMGetInstructionOpcode(instruction) {
+                LLVMOpcode::LLVMPHI => phis.push(instruction),
+                LLVMOpcode::LLVMCall => {
+                    spans.last_mut().expect("There should always be a span").push(instruction);
+                    spans.push(Vec::new());
+                },
+                _ => spans.last_mut().expect("There should always be a span").push(instruction),
+            }
+
+            instruction = LLVMGetNextInstruction(instruction);
+        }
+
+        let empty_name = CString::new("").expect("Conversion to CString failed unexpectedly");
+        let first_check = LLVMInsertBasicBlockInContext(context, block, empty_name.as_ptr());
+
+        for &predecessor in &predecessors {
+            for i in 0..LLVMGetNumOperands(predecessor) {
+                if LLVMGetOperand(predecessor, i as u32) == block_value {
+                    LLVMSetOperand(predecessor, i as u32, LLVMBasicBlockAsValue(first_check));
+                }
+            }
+        }
+
+        LLVMPositionBuilderAtEnd(builder, first_check);
+
+        for &phi in &phis {
+            move_instruction(builder, phi);
+        }
+
+        let last_span = spans.len() - 1;
+
+        for (i, span) in spans.iter().enumerate() {
+            let cost = if i == 0 { span.len() + phis.len() } else { span.len() };
+            let next_block = if i == last_span {
+                block
+            } else {
+                LLVMInsertBasicBlockInContext(context, block, empty_name.as_ptr())
+            };
+
+            build_budget_check(builder, budget, cost as u64, exceeded_block, next_block);
+
+            if i != last_span {
+                LLVMPositionBuilderAtEnd(builder, next_block);
+
+                for &instruction in span {
+                    move_instruction(builder, instruction);
+                }
+            }
+        }
+    }
+}
+
+// Deducts `cost` from the budget and bails out to `exceeded_block` if it has run out
+#[cfg(not(any(feature = "llvm3-6", feature = "llvm3-7", feature = "llvm3-8")))]
+unsafe fn build_budget_check(builder: LLVMBuilderRef, budget: LLVMValueRef, cost: u64, exceeded_block: LLVMBasicBlockRef, next_block: LLVMBasicBlockRef) {
+    let i64_type = LLVMGetElementType(LLVMTypeOf(budget));
+    let remaining_name = CString::new("remaining_budget").expect("Conversion to CString failed unexpectedly");
+    let exceeded_name = CString::new("budget_exceeded").expect("Conversion to CString failed unexpectedly");
+    let empty_name = CString::new("").expect("Conversion to CString failed unexpectedly");
+
+    let current = LLVMBuildLoad(builder, budget, empty_name.as_ptr());
+    let remaining = LLVMBuildSub(builder, current, LLVMConstInt(i64_type, cost, 0), remaining_name.as_ptr());
+
+    LLVMBuildStore(builder, remaining, budget);
+
+    let exceeded = LLVMBuildICmp(builder, LLVMIntPredicate::LLVMIntSLT, remaining, LLVMConstInt(i64_type, 0, 0), exceeded_name.as_ptr());
+
+    LLVMBuildCondBr(builder, exceeded, exceeded_block, next_block);
+}
+
+// Moves `instruction` to the builder's position, keeping its name
+#[cfg(not(any(feature = "llvm3-6", feature = "llvm3-7", feature = "llvm3-8")))]
+unsafe fn move_instruction(builder: LLVMBuilderRef, instruction: LLVMValueRef) {
+    let name = CStr::from_ptr(LLVMGetValueName(instruction)).to_owned();
+
+    LLVMInstructionRemoveFromParent(instruction);
+    LLVMInsertIntoBuilderWithName(builder, instruction, name.as_ptr());
 }
 
 // Modules owned by the EE will be discarded by the EE so we don't
-// want owned modules to drop.
+// want owned modules to drop. The same goes for the EE's TargetData
+// and TargetMachine, which only the last clone needs to forget.
 impl Drop for ExecutionEngine {
     fn drop(&mut self) {
-        forget(
-            self.target_data
-                .take()
-                .expect("TargetData should always exist until Drop"),
-        );
+        let target_data = self.target_data
+            .take()
+            .expect("TargetData should always exist until Drop");
+
+        if let Ok(target_data) = Rc::try_unwrap(target_data) {
+            forget(target_data);
+        }
+
+        if let Some(target_machine) = self.target_machine.take() {
+            if let Ok(target_machine) = Rc::try_unwrap(target_machine) {
+                forget(target_machine);
+            }
+        }
     }
 }
 
 impl Clone for ExecutionEngine {
     fn clone(&self) -> ExecutionEngine {
-        ExecutionEngine::new(self.execution_engine.0.clone(), self.jit_mode)
+        ExecutionEngine {
+            execution_engine: self.execution_engine.clone(),
+            target_data: self.target_data.clone(),
+            target_machine: self.target_machine.clone(),
+            jit_mode: self.jit_mode,
+            modules: self.modules.clone(),
+            freed_functions: self.freed_functions.clone(),
+        }
     }
 }
 
+// Clones share the same underlying EE, so that's all we need to compare
+impl PartialEq for ExecutionEngine {
+    fn eq(&self, other: &ExecutionEngine) -> bool {
+        *self.execution_engine == *other.execution_engine
+    }
+}
+
+impl Eq for ExecutionEngine {}
+
+/// An iterator over the `Module`s owned by an `ExecutionEngine`.
+#[derive(Debug)]
+pub struct ModuleIter {
+    execution_engine: ExecutionEngine,
+    modules: IntoIter<LLVMModuleRef>,
+}
+
+impl Iterator for ModuleIter {
+    type Item = Module;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            // The module may have been removed from the EE since this iterator was created
+            if let Some(module) = self.execution_engine.get_module_handle(self.modules.next()?) {
+                return Some(module);
+            }
+        }
+    }
+}
+
+/// An iterator over the `FunctionValue`s in a `Module`, or in the `Module`s owned by an `ExecutionEngine`.
+#[derive(Debug)]
+pub struct FunctionValueIter {
+    // Keeps the EE's modules alive, if they belong to one
+    _execution_engine: Option<ExecutionEngine>,
+    modules: IntoIter<LLVMModuleRef>,
+    next_function: Option<FunctionValue>,
+}
+
+impl FunctionValueIter {
+    pub(crate) fn new(execution_engine: Option<ExecutionEngine>, modules: Vec<LLVMModuleRef>) -> Self {
+        FunctionValueIter {
+            _execution_engine: execution_engine,
+            modules: modules.into_iter(),
+            next_function: None,
+        }
+    }
+}
+
+impl Iterator for FunctionValueIter {
+    type Item = FunctionValue;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            if let Some(function) = self.next_function {
+                self.next_function = function.get_next_function();
+
+                return Some(function);
+            }
+
+            let module = self.modules.next()?;
+
+            self.next_function = FunctionValue::new(unsafe { LLVMGetFirstFunction(module) });
+        }
+    }
+}
+
+// A module owned by an EE, along with what's needed to hand out handles to it
+#[derive(Debug)]
+struct OwnedModule {
+    module: LLVMModuleRef,
+    // Keeps the module's context alive for as long as the EE owns the module
+    context: Option<Context>,
+    // Weak, since the state refers back to the EE
+    state: Weak<ModuleState>,
+    frozen: Rc<Cell<bool>>,
+}
+
 /// A smart pointer which wraps the `Drop` logic for `LLVMExecutionEngineRef`.
 #[derive(Debug, Clone, PartialEq, Eq)]
 struct ExecEngineInner(Rc<LLVMExecutionEngineRef>);
@@ -431,6 +1979,65 @@ pub struct Symbol<F> {
     inner: F,
 }
 
+impl<F: UnsafeFunctionPointer> Symbol<F> {
+    /// Gets the raw address of the function this `Symbol` points to.
+    ///
+    /// The address is only valid for as long as the `ExecutionEngine` it came from is alive,
+    /// which unlike `Symbol` itself is not enforced.
+    pub fn as_raw(&self) -> usize {
+        unsafe {
+            transmute_copy(&self.inner)
+        }
+    }
+
+    /// Consumes this `Symbol`, returning the raw address of the function it points to.
+    ///
+    /// Unlike `Symbol`, which is `!Send`, the address may be moved to other threads and turned
+    /// back into a function pointer there (ie with `std::mem::transmute`), which is useful when
+    /// compiling once on one thread and dispatching calls from a thread pool.
+    ///
+    /// It is up to the caller to keep the `ExecutionEngine` this `Symbol` came from alive (and
+    /// on its own thread) for as long as the address is in use, and to ensure the compiled
+    /// function is itself safe to call from multiple threads.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use inkwell::OptimizationLevel;
+    /// use inkwell::context::Context;
+    /// use inkwell::targets::{InitializationConfig, Target};
+    ///
+    /// Target::initialize_native(&InitializationConfig::default()).unwrap();
+    ///
+    /// let context = Context::create();
+    /// let module = context.create_module("my_module");
+    /// let builder = context.create_builder();
+    /// let i32_type = context.i32_type();
+    /// let fn_type = i32_type.fn_type(&[], false);
+    /// let function = module.add_function("forty_two", &fn_type, None);
+    /// let entry = function.append_basic_block("entry");
+    ///
+    /// builder.position_at_end(&entry);
+    /// builder.build_return(Some(&i32_type.const_int(42, false)));
+    ///
+    /// let execution_engine = module.create_jit_execution_engine(OptimizationLevel::None).unwrap();
+    /// let address = unsafe {
+    ///     execution_engine.get_function::<unsafe extern "C" fn() -> i32>("forty_two").unwrap().into_raw_send()
+    /// };
+    ///
+    /// let result = std::thread::spawn(move || {
+    ///     let forty_two: unsafe extern "C" fn() -> i32 = unsafe { std::mem::transmute(address) };
+    ///
+    ///     unsafe { forty_two() }
+    /// }).join().unwrap();
+    ///
+    /// assert_eq!(result, 42);
+    /// ```
+    pub fn into_raw_send(self) -> usize {
+        self.as_raw()
+    }
+}
+
 impl<F: UnsafeFunctionPointer> Deref for Symbol<F> {
     type Target = F;
 
@@ -448,7 +2055,18 @@ impl<F> Debug for Symbol<F> {
 }
 
 /// Marker trait representing an unsafe function pointer (`unsafe extern "C" fn(A, B, ...) -> Output`).
-pub trait UnsafeFunctionPointer: private::Sealed + Copy {}
+pub trait UnsafeFunctionPointer: private::Sealed + Copy {
+    /// The number of parameters the function pointer takes.
+    const ARITY: u32;
+
+    /// The size of the function pointer, which must be the same as that of a `usize`.
+    const SIZE: usize = size_of::<Self>();
+
+    // Fails to evaluate, and so to compile, when indexing out of bounds, which doesn't
+    // require the const panics `assert!` would
+    #[doc(hidden)]
+    const ASSERT_POINTER_SIZED: () = [()][(Self::SIZE != size_of::<usize>()) as usize];
+}
 
 mod private {
     /// A sealed trait which ensures nobody outside this crate can implement
@@ -458,10 +2076,17 @@ mod private {
     pub trait Sealed {}
 }
 
+macro_rules! count_params {
+    () => { 0 };
+    ($head:ident $(, $tail:ident)*) => { 1 + count_params!($( $tail ),*) };
+}
+
 macro_rules! impl_unsafe_fn {
     ($( $param:ident ),*) => {
         impl<Output, $( $param ),*> private::Sealed for unsafe extern "C" fn($( $param ),*) -> Output {}
-        impl<Output, $( $param ),*> UnsafeFunctionPointer for unsafe extern "C" fn($( $param ),*) -> Output {}
+        impl<Output, $( $param ),*> UnsafeFunctionPointer for unsafe extern "C" fn($( $param ),*) -> Output {
+            const ARITY: u32 = count_params!($( $param ),*);
+        }
     };
 }
 
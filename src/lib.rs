@@ -11,12 +11,18 @@ extern crate enum_methods;
 extern crate libc;
 extern crate llvm_sys;
 
+#[deny(missing_docs)]
+#[cfg(not(any(feature = "llvm3-6", feature = "llvm3-7", feature = "llvm3-8")))]
+pub mod attributes;
 #[deny(missing_docs)]
 pub mod basic_block;
 pub mod builder;
 #[deny(missing_docs)]
 pub mod context;
 pub mod data_layout;
+#[deny(missing_docs)]
+#[cfg(not(any(feature = "llvm3-6", feature = "llvm3-7", feature = "llvm3-8", feature = "llvm3-9", feature = "llvm4-0", feature = "llvm5-0")))]
+pub mod debug_info;
 pub mod execution_engine;
 pub mod memory_buffer;
 pub mod module;
@@ -27,7 +33,7 @@ pub mod targets;
 pub mod types;
 pub mod values;
 
-use llvm_sys::{LLVMIntPredicate, LLVMRealPredicate, LLVMVisibility, LLVMThreadLocalMode, LLVMDLLStorageClass};
+use llvm_sys::{LLVMAtomicOrdering, LLVMAtomicRMWBinOp, LLVMIntPredicate, LLVMRealPredicate, LLVMVisibility, LLVMThreadLocalMode, LLVMDLLStorageClass};
 
 #[cfg(not(any(feature = "llvm3-6", feature = "llvm3-7", feature = "llvm3-8", feature = "llvm3-9", feature = "llvm4-0",
               feature = "llvm5-0", feature = "llvm6-0")))]
@@ -72,6 +78,22 @@ impl From<u32> for AddressSpace {
     }
 }
 
+/// Defines some of the common calling conventions a function can use. These can be passed to
+/// `FunctionValue::set_call_conventions` with `as u32`.
+///
+/// # Remarks
+/// See also: https://llvm.org/docs/LangRef.html#calling-conventions
+#[repr(u32)]
+#[derive(Debug, PartialEq, Eq, Copy, Clone)]
+pub enum CallConv {
+    /// The C calling convention, which is the default and what `extern "C"` functions use
+    C    = 0,
+    /// Makes calls as fast as possible, which may not match any external ABI
+    Fast = 8,
+    /// Optimizes for calls which are rarely made, keeping the caller's code fast
+    Cold = 9,
+}
+
 // REVIEW: Maybe this belongs in some sort of prelude?
 #[derive(Clone, Copy, Debug, Eq, PartialEq)]
 pub enum IntPredicate {
@@ -165,6 +187,87 @@ impl FloatPredicate {
     }
 }
 
+/// Defines the memory ordering constraints of an atomic operation.
+///
+/// # Remarks
+/// See also: https://llvm.org/docs/LangRef.html#ordering
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum AtomicOrdering {
+    /// The operation is not atomic
+    NotAtomic,
+    /// Loads and stores are atomic, but no ordering is guaranteed
+    Unordered,
+    /// There is a single total order of operations on each address (C++'s `memory_order_relaxed`)
+    Monotonic,
+    /// Synchronizes with the release operations it reads from (C++'s `memory_order_acquire`)
+    Acquire,
+    /// Synchronizes with the acquire operations that read from it (C++'s `memory_order_release`)
+    Release,
+    /// Both an acquire and a release operation (C++'s `memory_order_acq_rel`)
+    AcquireRelease,
+    /// An acquire and release operation with a single total order over all such
+    /// operations (C++'s `memory_order_seq_cst`)
+    SequentiallyConsistent,
+}
+
+impl AtomicOrdering {
+    pub(crate) fn as_llvm_ordering(&self) -> LLVMAtomicOrdering {
+        match *self {
+            AtomicOrdering::NotAtomic => LLVMAtomicOrdering::LLVMAtomicOrderingNotAtomic,
+            AtomicOrdering::Unordered => LLVMAtomicOrdering::LLVMAtomicOrderingUnordered,
+            AtomicOrdering::Monotonic => LLVMAtomicOrdering::LLVMAtomicOrderingMonotonic,
+            AtomicOrdering::Acquire => LLVMAtomicOrdering::LLVMAtomicOrderingAcquire,
+            AtomicOrdering::Release => LLVMAtomicOrdering::LLVMAtomicOrderingRelease,
+            AtomicOrdering::AcquireRelease => LLVMAtomicOrdering::LLVMAtomicOrderingAcquireRelease,
+            AtomicOrdering::SequentiallyConsistent => LLVMAtomicOrdering::LLVMAtomicOrderingSequentiallyConsistent,
+        }
+    }
+}
+
+/// Defines the operation an `atomicrmw` instruction applies to the value in memory.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum AtomicRMWBinOp {
+    /// Replaces the value
+    Xchg,
+    /// Adds to the value
+    Add,
+    /// Subtracts from the value
+    Sub,
+    /// Bitwise ands the value
+    And,
+    /// Bitwise nands the value
+    Nand,
+    /// Bitwise ors the value
+    Or,
+    /// Bitwise xors the value
+    Xor,
+    /// Keeps the larger of the two, as signed integers
+    Max,
+    /// Keeps the smaller of the two, as signed integers
+    Min,
+    /// Keeps the larger of the two, as unsigned integers
+    UMax,
+    /// Keeps the smaller of the two, as unsigned integers
+    UMin,
+}
+
+impl AtomicRMWBinOp {
+    pub(crate) fn as_llvm_bin_op(&self) -> LLVMAtomicRMWBinOp {
+        match *self {
+            AtomicRMWBinOp::Xchg => LLVMAtomicRMWBinOp::LLVMAtomicRMWBinOpXchg,
+            AtomicRMWBinOp::Add => LLVMAtomicRMWBinOp::LLVMAtomicRMWBinOpAdd,
+            AtomicRMWBinOp::Sub => LLVMAtomicRMWBinOp::LLVMAtomicRMWBinOpSub,
+            AtomicRMWBinOp::And => LLVMAtomicRMWBinOp::LLVMAtomicRMWBinOpAnd,
+            AtomicRMWBinOp::Nand => LLVMAtomicRMWBinOp::LLVMAtomicRMWBinOpNand,
+            AtomicRMWBinOp::Or => LLVMAtomicRMWBinOp::LLVMAtomicRMWBinOpOr,
+            AtomicRMWBinOp::Xor => LLVMAtomicRMWBinOp::LLVMAtomicRMWBinOpXor,
+            AtomicRMWBinOp::Max => LLVMAtomicRMWBinOp::LLVMAtomicRMWBinOpMax,
+            AtomicRMWBinOp::Min => LLVMAtomicRMWBinOp::LLVMAtomicRMWBinOpMin,
+            AtomicRMWBinOp::UMax => LLVMAtomicRMWBinOp::LLVMAtomicRMWBinOpUMax,
+            AtomicRMWBinOp::UMin => LLVMAtomicRMWBinOp::LLVMAtomicRMWBinOpUMin,
+        }
+    }
+}
 
 /// Defines the optimization level used to compile a `Module`.
 ///
@@ -187,6 +290,10 @@ impl Default for OptimizationLevel {
 }
 
 // REVIEW: Maybe this belongs in some sort of prelude?
+/// Defines how a global is visible to other modules once linked.
+///
+/// # Remarks
+/// See also: https://llvm.org/docs/LangRef.html#visibility-styles
 #[derive(Clone, Copy, Debug, Eq, PartialEq)]
 pub enum GlobalVisibility {
     Default,
@@ -219,6 +326,11 @@ impl GlobalVisibility {
     }
 }
 
+/// Defines the thread local storage model used to access a thread local global. The absence
+/// of a `ThreadLocalMode` (`None`) means a global is not thread local.
+///
+/// # Remarks
+/// See also: https://llvm.org/docs/LangRef.html#thread-local-storage-models
 #[derive(Clone, Copy, Debug, Eq, PartialEq)]
 pub enum ThreadLocalMode {
     GeneralDynamicTLSModel,
@@ -1,3 +1,4 @@
+use libc::c_char;
 use llvm_sys::core::{LLVMCreateMemoryBufferWithContentsOfFile, LLVMCreateMemoryBufferWithSTDIN, LLVMCreateMemoryBufferWithMemoryRange, LLVMCreateMemoryBufferWithMemoryRangeCopy, LLVMGetBufferStart, LLVMGetBufferSize, LLVMDisposeMemoryBuffer};
 use llvm_sys::prelude::LLVMMemoryBufferRef;
 use llvm_sys::object::LLVMCreateObjectFile;
@@ -11,6 +12,8 @@ use std::path::Path;
 use std::ptr;
 use std::slice;
 
+/// A buffer of bytes owned by LLVM, such as the contents of a bitcode or IR file. The buffer is
+/// disposed of when the `MemoryBuffer` is dropped.
 #[derive(Debug)]
 pub struct MemoryBuffer {
     pub(crate) memory_buffer: LLVMMemoryBufferRef
@@ -25,14 +28,27 @@ impl MemoryBuffer {
         }
     }
 
+    /// Creates a new `MemoryBuffer` with the contents of the file at `path`, returning the
+    /// reason it couldn't be read otherwise.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use inkwell::memory_buffer::MemoryBuffer;
+    /// use std::path::Path;
+    ///
+    /// let buffer = MemoryBuffer::create_from_file(Path::new("foo/bar.bc")).unwrap();
+    ///
+    /// println!("Read {} bytes", buffer.get_size());
+    /// ```
     pub fn create_from_file(path: &Path) -> Result<Self, LLVMString> {
         let path = path.to_str().expect("Did not find a valid Unicode path string");
+        let c_string = CString::new(path).expect("Conversion to CString failed unexpectedly");
         let mut memory_buffer = ptr::null_mut();
         let mut err_string = unsafe { zeroed() };
 
         let return_code = unsafe {
-            // REVIEW: Unclear why this expects *const i8 instead of *const u8
-            LLVMCreateMemoryBufferWithContentsOfFile(path.as_ptr() as *const i8, &mut memory_buffer, &mut err_string)
+            LLVMCreateMemoryBufferWithContentsOfFile(c_string.as_ptr(), &mut memory_buffer, &mut err_string)
         };
 
         // TODO: Verify 1 is error code (LLVM can be inconsistent)
@@ -82,22 +98,37 @@ impl MemoryBuffer {
         MemoryBuffer::new(memory_buffer)
     }
 
-    /// This will create a new `MemoryBuffer` from the given input.
+    /// This will create a new `MemoryBuffer` with a copy of the given input, which may be any bytes,
+    /// such as bitcode, rather than only text.
     ///
     /// This function is likely slightly more expensive than `create_from_memory_range` since it does not leak
     /// data to LLVM, forcing LLVM to make a copy. This function may be removed in the future in favor of
     /// `create_from_memory_range`
-    pub fn create_from_memory_range_copy(input: &str, name: &str) -> Self {
-        let input_c_string = CString::new(input).expect("Conversion to CString failed unexpectedly");
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use inkwell::context::Context;
+    /// use inkwell::memory_buffer::MemoryBuffer;
+    ///
+    /// let context = Context::create();
+    /// let bitcode = context.create_module("my_module").write_bitcode_to_memory();
+    /// let buffer = MemoryBuffer::create_from_memory_range_copy(bitcode.as_slice(), "my_module.bc");
+    ///
+    /// assert_eq!(buffer.as_slice(), bitcode.as_slice());
+    /// assert!(context.create_module_from_bitcode(&buffer).is_ok());
+    /// ```
+    pub fn create_from_memory_range_copy(input: &[u8], name: &str) -> Self {
         let name_c_string = CString::new(name).expect("Conversion to CString failed unexpectedly");
 
         let memory_buffer = unsafe {
-            LLVMCreateMemoryBufferWithMemoryRangeCopy(input_c_string.as_ptr(), input.len(), name_c_string.as_ptr())
+            LLVMCreateMemoryBufferWithMemoryRangeCopy(input.as_ptr() as *const c_char, input.len(), name_c_string.as_ptr())
         };
 
         MemoryBuffer::new(memory_buffer)
     }
 
+    /// Gets the contents of this `MemoryBuffer`.
     pub fn as_slice(&self) -> &[u8] {
         unsafe {
             let start = LLVMGetBufferStart(self.memory_buffer);
@@ -106,6 +137,7 @@ impl MemoryBuffer {
         }
     }
 
+    /// Gets the size of this `MemoryBuffer` in bytes.
     pub fn get_size(&self) -> usize {
         unsafe {
             LLVMGetBufferSize(self.memory_buffer)
@@ -2,24 +2,31 @@ use llvm_sys::analysis::{LLVMVerifyModule, LLVMVerifierFailureAction};
 use llvm_sys::bit_reader::{LLVMParseBitcode, LLVMParseBitcodeInContext};
 use llvm_sys::bit_writer::{LLVMWriteBitcodeToFile, LLVMWriteBitcodeToMemoryBuffer};
 use llvm_sys::core::{LLVMAddFunction, LLVMAddGlobal, LLVMDumpModule, LLVMGetNamedFunction, LLVMGetTypeByName, LLVMSetDataLayout, LLVMSetTarget, LLVMCloneModule, LLVMDisposeModule, LLVMGetTarget, LLVMModuleCreateWithName, LLVMGetModuleContext, LLVMGetFirstFunction, LLVMGetLastFunction, LLVMSetLinkage, LLVMAddGlobalInAddressSpace, LLVMPrintModuleToString, LLVMGetNamedMetadataNumOperands, LLVMAddNamedMetadataOperand, LLVMGetNamedMetadataOperands, LLVMGetFirstGlobal, LLVMGetLastGlobal, LLVMGetNamedGlobal, LLVMPrintModuleToFile, LLVMSetModuleInlineAsm};
-use llvm_sys::execution_engine::{LLVMCreateInterpreterForModule, LLVMCreateJITCompilerForModule, LLVMCreateExecutionEngineForModule};
+#[cfg(not(any(feature = "llvm3-6", feature = "llvm3-7", feature = "llvm3-8", feature = "llvm3-9", feature = "llvm4-0", feature = "llvm5-0")))]
+use llvm_sys::debuginfo::{LLVMGetModuleDebugMetadataVersion, LLVMStripModuleDebugInfo};
+use llvm_sys::execution_engine::{LLVMCreateInterpreterForModule, LLVMCreateJITCompilerForModule, LLVMCreateExecutionEngineForModule, LLVMCreateMCJITCompilerForModule, LLVMMCJITCompilerOptions};
 use llvm_sys::prelude::{LLVMValueRef, LLVMModuleRef};
 use llvm_sys::LLVMLinkage;
 
 use std::cell::{Cell, RefCell, Ref};
 use std::ffi::{CString, CStr};
 use std::fs::File;
-use std::mem::{forget, uninitialized, zeroed};
+use std::mem::{forget, size_of, uninitialized, zeroed};
 use std::path::Path;
+use std::ptr;
 use std::rc::Rc;
 use std::slice::from_raw_parts;
 
 use {AddressSpace, OptimizationLevel};
 use context::{Context, ContextRef};
 use data_layout::DataLayout;
-use execution_engine::ExecutionEngine;
+#[cfg(not(any(feature = "llvm3-6", feature = "llvm3-7", feature = "llvm3-8", feature = "llvm3-9", feature = "llvm4-0", feature = "llvm5-0")))]
+use debug_info::DebugInfoBuilder;
+use execution_engine::{ExecutionEngine, ExecutionEngineCreationError, FunctionValueIter, JitEngineBuilder, JitFunctionError, Symbol, UnsafeFunctionPointer};
 use memory_buffer::MemoryBuffer;
 use support::LLVMString;
+#[cfg(not(any(feature = "llvm3-6", feature = "llvm3-7", feature = "llvm3-8")))]
+use support::error_handling::capture_diagnostic_error;
 use types::{AsTypeRef, BasicType, FunctionType, BasicTypeEnum};
 use values::{AsValueRef, FunctionValue, GlobalValue, MetadataValue};
 
@@ -96,23 +103,82 @@ impl Linkage {
 #[derive(Debug, PartialEq, Eq)]
 pub struct Module {
     pub(crate) non_global_context: Option<Context>, // REVIEW: Could we just set context to the global context?
-    data_layout: RefCell<Option<DataLayout>>,
     pub(crate) module: Cell<LLVMModuleRef>,
+    pub(crate) state: Rc<ModuleState>,
+}
+
+// The state shared by every handle to the same underlying module, such as those handed out by
+// ExecutionEngine::modules. Only the last handle to drop may dispose of the module
+#[derive(Debug, PartialEq, Eq)]
+pub(crate) struct ModuleState {
+    data_layout: RefCell<Option<DataLayout>>,
     pub(crate) owned_by_ee: RefCell<Option<ExecutionEngine>>,
+    // Also kept by the owning EE, so that handles it hands out later are still frozen
+    pub(crate) frozen: Rc<Cell<bool>>,
 }
 
 impl Module {
     pub(crate) fn new(module: LLVMModuleRef, context: Option<&Context>) -> Self {
+        Module::new_with_state(module, context, None, Rc::new(Cell::new(false)))
+    }
+
+    // Creates a fresh handle to a module owned by `execution_engine`, which shares `frozen` with its other handles
+    pub(crate) fn new_owned(module: LLVMModuleRef, context: Option<&Context>, execution_engine: &ExecutionEngine, frozen: &Rc<Cell<bool>>) -> Self {
+        Module::new_with_state(module, context, Some(execution_engine.clone()), frozen.clone())
+    }
+
+    fn new_with_state(module: LLVMModuleRef, context: Option<&Context>, owned_by_ee: Option<ExecutionEngine>, frozen: Rc<Cell<bool>>) -> Self {
         debug_assert!(!module.is_null());
 
+        let state = ModuleState {
+            data_layout: RefCell::new(Some(Module::get_borrowed_data_layout(module))),
+            owned_by_ee: RefCell::new(owned_by_ee),
+            frozen: frozen,
+        };
+
+        Module::new_shared(module, context, Rc::new(state))
+    }
+
+    // Creates another handle to a module which already has one, sharing its state
+    pub(crate) fn new_shared(module: LLVMModuleRef, context: Option<&Context>, state: Rc<ModuleState>) -> Self {
         Module {
             module: Cell::new(module),
             non_global_context: context.map(|ctx| Context::new(ctx.context.clone())),
-            owned_by_ee: RefCell::new(None),
-            data_layout: RefCell::new(Some(Module::get_borrowed_data_layout(module))),
+            state: state,
         }
     }
 
+    /// Freezes this `Module`, so that any further attempt to add functions, globals, global metadata
+    /// or inline assembly to it will panic, including through other handles to it such as those
+    /// returned by `ExecutionEngine::modules`. This is useful once a `Module` has been
+    /// JIT compiled, since modifying it afterwards may invalidate `Symbol`s which were already looked up.
+    ///
+    /// # Example
+    /// ```should_panic
+    /// use inkwell::context::Context;
+    ///
+    /// let context = Context::create();
+    /// let module = context.create_module("my_module");
+    /// let fn_type = context.void_type().fn_type(&[], false);
+    ///
+    /// module.freeze();
+    ///
+    /// assert!(module.is_frozen());
+    ///
+    /// // Panics
+    /// module.add_function("my_fn", &fn_type, None);
+    /// ```
+    // REVIEW: Builders can still insert instructions into a frozen module's functions, since
+    // they don't know which module they're building in
+    pub fn freeze(&self) {
+        self.state.frozen.set(true);
+    }
+
+    /// Determines whether or not this `Module` has been frozen with `freeze`.
+    pub fn is_frozen(&self) -> bool {
+        self.state.frozen.get()
+    }
+
     /// Creates a named `Module`. Will be automatically assigned the global context.
     ///
     /// To use your own `Context`, see [inkwell::context::create_module()](../context/struct.Context.html#method.create_module)
@@ -158,6 +224,8 @@ impl Module {
     /// assert_eq!(fn_val.get_linkage(), Linkage::ExternalLinkage);
     /// ```
     pub fn add_function(&self, name: &str, ty: &FunctionType, linkage: Option<Linkage>) -> FunctionValue {
+        assert!(!self.is_frozen(), "Cannot add a function to a frozen Module");
+
         let c_string = CString::new(name).expect("Conversion to CString failed unexpectedly");
 
         let value = unsafe {
@@ -173,6 +241,10 @@ impl Module {
         fn_value
     }
 
+    // TODO: Add get_intrinsic(name, param_types) once we support LLVM 9.0, which is the first version with
+    // LLVMLookupIntrinsicID and LLVMGetIntrinsicDeclaration. Until then, intrinsics have to be declared
+    // through add_function using their mangled name (ie "llvm.sqrt.f64")
+
     /// Gets the `Context` from which this `Module` originates.
     ///
     /// # Example
@@ -280,6 +352,30 @@ impl Module {
         FunctionValue::new(value)
     }
 
+    /// Gets an iterator over all of the `FunctionValue`s in this `Module`, including declarations,
+    /// in the order they were defined. The iterator is lazy and walks the `Module`'s function list.
+    ///
+    /// # Example
+    /// ```rust,no_run
+    /// use inkwell::context::Context;
+    /// use inkwell::module::Module;
+    ///
+    /// let context = Context::create();
+    /// let module = context.create_module("my_mod");
+    ///
+    /// assert_eq!(module.get_functions().count(), 0);
+    ///
+    /// let void_type = context.void_type();
+    /// let fn_type = void_type.fn_type(&[], false);
+    /// let fn_value = module.add_function("my_fn", &fn_type, None);
+    /// let fn_value2 = module.add_function("my_fn2", &fn_type, None);
+    ///
+    /// assert_eq!(module.get_functions().collect::<Vec<_>>(), vec![fn_value, fn_value2]);
+    /// ```
+    pub fn get_functions(&self) -> FunctionValueIter {
+        FunctionValueIter::new(None, vec![self.module.get()])
+    }
+
     pub fn get_type(&self, name: &str) -> Option<BasicTypeEnum> {
         let c_string = CString::new(name).expect("Conversion to CString failed unexpectedly");
 
@@ -294,6 +390,22 @@ impl Module {
         Some(BasicTypeEnum::new(type_))
     }
 
+    /// Sets the target triple of this `Module`, ie "x86_64-pc-linux-gnu".
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use inkwell::context::Context;
+    ///
+    /// let context = Context::create();
+    /// let module = context.create_module("my_module");
+    ///
+    /// assert_eq!(module.get_target().to_str(), Ok(""));
+    ///
+    /// module.set_target("x86_64-pc-linux-gnu");
+    ///
+    /// assert_eq!(module.get_target().to_str(), Ok("x86_64-pc-linux-gnu"));
+    /// ```
     // TODO: Make this take a targets::Target object by ref and call get_name
     pub fn set_target(&self, target_triple: &str) {
         let c_string = CString::new(target_triple).expect("Conversion to CString failed unexpectedly");
@@ -303,6 +415,8 @@ impl Module {
         }
     }
 
+    /// Gets the target triple of this `Module`, which is empty unless set through `set_target`
+    /// or parsed from IR or bitcode.
     // REVIEW: Can we link this to Target.from_name or Target.from_triple / etc?
     pub fn get_target(&self) -> &CStr {
         unsafe {
@@ -312,6 +426,10 @@ impl Module {
 
     /// Creates an `ExecutionEngine` from this `Module`.
     ///
+    /// The `Module` is not verified first, and creating an `ExecutionEngine` from an invalid one may abort
+    /// the process. Either call `verify` beforehand or use `jit_engine_builder` with
+    /// `JitEngineBuilder::verify_module` set, which is the only constructor that verifies.
+    ///
     /// # Example
     /// ```no_run
     /// use inkwell::context::Context;
@@ -327,7 +445,7 @@ impl Module {
     /// assert_eq!(module.get_context(), context);
     /// ```
     // SubType: ExecutionEngine<?>
-    pub fn create_execution_engine(&self) -> Result<ExecutionEngine, LLVMString> {
+    pub fn create_execution_engine(&self) -> Result<ExecutionEngine, ExecutionEngineCreationError> {
         let mut execution_engine = unsafe { zeroed() };
         let mut err_string = unsafe { zeroed() };
         let code = unsafe {
@@ -335,18 +453,23 @@ impl Module {
         };
 
         if code == 1 {
-            return Err(LLVMString::new(err_string));
+            return Err(ExecutionEngineCreationError::new(LLVMString::new(err_string)));
         }
 
         let execution_engine = ExecutionEngine::new(Rc::new(execution_engine), false);
 
-        *self.owned_by_ee.borrow_mut() = Some(execution_engine.clone());
+        execution_engine.track_module(self);
 
         Ok(execution_engine)
     }
 
     /// Creates an interpreter `ExecutionEngine` from this `Module`.
     ///
+    /// Interpreter `ExecutionEngine`s are never in JIT mode, so functions must be run through
+    /// `ExecutionEngine::run_function` rather than looked up with `ExecutionEngine::get_function`.
+    ///
+    /// Like `create_execution_engine`, this does not verify the `Module` first.
+    ///
     /// # Example
     /// ```no_run
     /// use inkwell::context::Context;
@@ -362,7 +485,7 @@ impl Module {
     /// assert_eq!(module.get_context(), context);
     /// ```
     // SubType: ExecutionEngine<Interpreter>
-    pub fn create_interpreter_execution_engine(&self) -> Result<ExecutionEngine, LLVMString> {
+    pub fn create_interpreter_execution_engine(&self) -> Result<ExecutionEngine, ExecutionEngineCreationError> {
         let mut execution_engine = unsafe { uninitialized() };
         let mut err_string = unsafe { zeroed() };
 
@@ -371,18 +494,22 @@ impl Module {
         };
 
         if code == 1 {
-            return Err(LLVMString::new(err_string));
+            return Err(ExecutionEngineCreationError::new(LLVMString::new(err_string)));
         }
 
         let execution_engine = ExecutionEngine::new(Rc::new(execution_engine), false);
 
-        *self.owned_by_ee.borrow_mut() = Some(execution_engine.clone());
+        execution_engine.track_module(self);
 
         Ok(execution_engine)
     }
 
     /// Creates a JIT `ExecutionEngine` from this `Module`.
     ///
+    /// JIT `ExecutionEngine`s support looking up compiled functions with `ExecutionEngine::get_function`.
+    ///
+    /// Like `create_execution_engine`, this does not verify the `Module` first.
+    ///
     /// # Example
     /// ```no_run
     /// use inkwell::OptimizationLevel;
@@ -399,7 +526,7 @@ impl Module {
     /// assert_eq!(module.get_context(), context);
     /// ```
     // SubType: ExecutionEngine<Jit>
-    pub fn create_jit_execution_engine(&self, opt_level: OptimizationLevel) -> Result<ExecutionEngine, LLVMString> {
+    pub fn create_jit_execution_engine(&self, opt_level: OptimizationLevel) -> Result<ExecutionEngine, ExecutionEngineCreationError> {
         let mut execution_engine = unsafe { uninitialized() };
         let mut err_string = unsafe { zeroed() };
 
@@ -420,17 +547,134 @@ impl Module {
 
             // REVIEW: Module still seems "owned" in the error case and may segfault on module drop. :/
             // Need to figure out if there's a way to prevent this.
-            return Err(LLVMString::new(err_string));
+            return Err(ExecutionEngineCreationError::new(LLVMString::new(err_string)));
         }
 
         let execution_engine = ExecutionEngine::new(Rc::new(execution_engine), true);
 
-        *self.owned_by_ee.borrow_mut() = Some(execution_engine.clone());
+        execution_engine.track_module(self);
 
         Ok(execution_engine)
     }
 
+    /// Gets a `JitEngineBuilder` for creating a JIT `ExecutionEngine` from this `Module` using MCJIT
+    /// with options beyond an `OptimizationLevel`, such as a custom `MCJITMemoryManager`.
+    ///
+    /// # Example
+    /// ```no_run
+    /// use inkwell::OptimizationLevel;
+    /// use inkwell::context::Context;
+    /// use inkwell::targets::{InitializationConfig, Target};
+    ///
+    /// Target::initialize_native(&InitializationConfig::default()).expect("Failed to initialize native target");
+    ///
+    /// let context = Context::create();
+    /// let module = context.create_module("my_module");
+    /// let execution_engine = module.jit_engine_builder()
+    ///     .opt_level(OptimizationLevel::Less)
+    ///     .verify_module(true)
+    ///     .create()
+    ///     .unwrap();
+    ///
+    /// assert!(execution_engine.is_jit());
+    /// ```
+    pub fn jit_engine_builder(&self) -> JitEngineBuilder {
+        JitEngineBuilder::new(self)
+    }
+
+    pub(crate) fn create_mcjit_execution_engine(&self, mut mcjit_options: LLVMMCJITCompilerOptions) -> Result<ExecutionEngine, ExecutionEngineCreationError> {
+        let mut execution_engine = unsafe { uninitialized() };
+        let mut err_string = unsafe { zeroed() };
+
+        let code = unsafe {
+            LLVMCreateMCJITCompilerForModule(&mut execution_engine, self.module.get(), &mut mcjit_options, size_of::<LLVMMCJITCompilerOptions>(), &mut err_string) // Takes ownership of module
+        };
+
+        if code == 1 {
+            // Same as create_jit_execution_engine, the module still seems "owned" in the error case
+            let module = self.clone();
+
+            self.module.set(module.module.get());
+
+            forget(module);
+
+            return Err(ExecutionEngineCreationError::new(LLVMString::new(err_string)));
+        }
+
+        let execution_engine = ExecutionEngine::new(Rc::new(execution_engine), true);
+
+        execution_engine.track_module(self);
+
+        Ok(execution_engine)
+    }
+
+    /// Creates a JIT `ExecutionEngine` from this `Module` and looks up the function named `fn_name` in it.
+    /// The `ExecutionEngine` is returned alongside the function so that it can be reused, though the
+    /// `Symbol` will keep it alive regardless. On success, this `Module` is frozen as with `freeze`.
+    ///
+    /// Like `create_execution_engine`, this does not verify the `Module` first.
+    ///
+    /// # Example
+    /// ```no_run
+    /// use inkwell::OptimizationLevel;
+    /// use inkwell::context::Context;
+    /// use inkwell::targets::{InitializationConfig, Target};
+    ///
+    /// Target::initialize_native(&InitializationConfig::default()).expect("Failed to initialize native target");
+    ///
+    /// let context = Context::create();
+    /// let module = context.create_module("my_module");
+    /// let builder = context.create_builder();
+    /// let i32_type = context.i32_type();
+    /// let fn_type = i32_type.fn_type(&[], false);
+    /// let fn_value = module.add_function("answer", &fn_type, None);
+    /// let entry = context.append_basic_block(&fn_value, "entry");
+    ///
+    /// builder.position_at_end(&entry);
+    /// builder.build_return(Some(&i32_type.const_int(42, false)));
+    ///
+    /// unsafe {
+    ///     let (_execution_engine, answer) = module.jit_function::<unsafe extern "C" fn() -> i32>("answer", OptimizationLevel::None).unwrap();
+    ///
+    ///     assert_eq!(answer(), 42);
+    /// }
+    /// ```
+    ///
+    /// # Safety
+    ///
+    /// This has the same safety requirements as `ExecutionEngine::get_function`.
+    pub unsafe fn jit_function<F: UnsafeFunctionPointer>(&self, fn_name: &str, opt_level: OptimizationLevel) -> Result<(ExecutionEngine, Symbol<F>), JitFunctionError> {
+        let execution_engine = self.create_jit_execution_engine(opt_level)?;
+        let function = execution_engine.get_function(fn_name)?;
+
+        // Modifying the module from here on out could invalidate the function
+        self.freeze();
+
+        Ok((execution_engine, function))
+    }
+
+    /// Adds a `GlobalValue` of the given type to this `Module`. The global is only a declaration
+    /// until it is given an initializer through `GlobalValue::set_initializer`.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use inkwell::AddressSpace;
+    /// use inkwell::context::Context;
+    ///
+    /// let context = Context::create();
+    /// let module = context.create_module("my_mod");
+    /// let i8_type = context.i8_type();
+    /// let table_type = i8_type.array_type(3);
+    /// let table = module.add_global(&table_type, Some(AddressSpace::Generic), "table");
+    ///
+    /// table.set_initializer(&table_type.const_array(&[i8_type.const_int(1, false), i8_type.const_int(2, false), i8_type.const_int(3, false)]));
+    ///
+    /// assert_eq!(module.get_global("table"), Some(table));
+    /// ```
     pub fn add_global(&self, type_: &BasicType, address_space: Option<AddressSpace>, name: &str) -> GlobalValue {
+        assert!(!self.is_frozen(), "Cannot add a global to a frozen Module");
+
         let c_string = CString::new(name).expect("Conversion to CString failed unexpectedly");
 
         let value = unsafe {
@@ -471,6 +715,22 @@ impl Module {
         false
     }
 
+    /// Writes this `Module` to a `MemoryBuffer` as bitcode, whose bytes can be accessed with
+    /// `MemoryBuffer::as_slice`. Bitcode is much faster to parse back in than textual IR, which
+    /// makes it well suited for caching `Module`s.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use inkwell::context::Context;
+    ///
+    /// let context = Context::create();
+    /// let module = context.create_module("my_module");
+    /// let buffer = module.write_bitcode_to_memory();
+    ///
+    /// // Bitcode files start with the magic bytes "BC"
+    /// assert_eq!(&buffer.as_slice()[..2], b"BC");
+    /// ```
     pub fn write_bitcode_to_memory(&self) -> MemoryBuffer {
         let memory_buffer = unsafe {
             LLVMWriteBitcodeToMemoryBuffer(self.module.get())
@@ -481,6 +741,29 @@ impl Module {
 
     /// Ensures that the current `Module` is valid, and returns a `Result`
     /// that describes whether or not it is, returning a LLVM allocated string on error.
+    /// The string contains the verifier's diagnostics.
+    ///
+    /// Creating an `ExecutionEngine` from an invalid `Module` may abort the process, so
+    /// it's best to verify it first. Of the `ExecutionEngine` constructors, only
+    /// `jit_engine_builder` does so itself, and only when `JitEngineBuilder::verify_module`
+    /// is set.
+    ///
+    /// # Example
+    /// ```
+    /// use inkwell::context::Context;
+    ///
+    /// let context = Context::create();
+    /// let module = context.create_module("my_module");
+    /// let fn_type = context.i32_type().fn_type(&[], false);
+    /// let fn_value = module.add_function("my_fn", &fn_type, None);
+    ///
+    /// assert!(module.verify().is_ok());
+    ///
+    /// // A basic block without a terminator is invalid
+    /// context.append_basic_block(&fn_value, "entry");
+    ///
+    /// assert!(module.verify().unwrap_err().to_string().contains("my_fn"));
+    /// ```
     ///
     /// # Remarks
     /// See also: http://llvm.org/doxygen/Analysis_2Analysis_8cpp_source.html
@@ -493,8 +776,123 @@ impl Module {
             LLVMVerifyModule(self.module.get(), action, &mut err_str)
         };
 
-        if code == 1 && !err_str.is_null() {
-            return Err(LLVMString::new(err_str));
+        if code == 1 {
+            let err_str = if err_str.is_null() {
+                let c_string = CString::new("Module verification failed").expect("Conversion to CString failed unexpectedly");
+
+                LLVMString::create(c_string.as_ptr())
+            } else {
+                LLVMString::new(err_str)
+            };
+
+            return Err(err_str);
+        }
+
+        // LLVM allocates an empty message even when the module is valid
+        if !err_str.is_null() {
+            drop(LLVMString::new(err_str));
+        }
+
+        Ok(())
+    }
+
+    /// Links `other` into this `Module`, so that it contains the definitions of both. This is
+    /// useful for optimizing several `Module`s together before JIT compiling them as one.
+    ///
+    /// `other` is destroyed by linking, even if it fails. Both `Module`s must belong to the same
+    /// `Context`, and `other` must not be owned by an `ExecutionEngine`, otherwise an error is returned
+    /// without linking. Errors from linking itself, such as two definitions of the same symbol, are
+    /// returned as LLVM reports them. Symbols which may legitimately be defined in both `Module`s,
+    /// such as vtables, should be given `Linkage::LinkOnceODRLinkage` or `Linkage::WeakODRLinkage`
+    /// so that only one definition is kept.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use inkwell::context::Context;
+    ///
+    /// let context = Context::create();
+    /// let module = context.create_module("main");
+    /// let other = context.create_module("other");
+    /// let builder = context.create_builder();
+    /// let fn_type = context.void_type().fn_type(&[], false);
+    ///
+    /// module.add_function("helper", &fn_type, None);
+    ///
+    /// let helper = other.add_function("helper", &fn_type, None);
+    /// let entry = context.append_basic_block(&helper, "entry");
+    ///
+    /// builder.position_at_end(&entry);
+    /// builder.build_return(None);
+    ///
+    /// module.link_in_module(other).unwrap();
+    ///
+    /// assert_eq!(module.get_function("helper").unwrap().count_basic_blocks(), 1);
+    /// ```
+    ///
+    /// # Panics
+    ///
+    /// Panics if this `Module` has been frozen.
+    // TODO: Comdats (ie get_or_insert_comdat and GlobalValue::set_comdat with a ComdatSelectionKind) once
+    // we support LLVM 7.0, which is the first version with LLVMGetOrInsertComdat and LLVMSetComdat
+    #[cfg(not(any(feature = "llvm3-6", feature = "llvm3-7", feature = "llvm3-8")))]
+    pub fn link_in_module(&self, other: Module) -> Result<(), LLVMString> {
+        use llvm_sys::linker::LLVMLinkModules2;
+
+        assert!(!self.is_frozen(), "Cannot link into a frozen Module");
+
+        if other.state.owned_by_ee.borrow().is_some() {
+            let c_string = CString::new("Cannot link in a module owned by an ExecutionEngine").expect("Conversion to CString failed unexpectedly");
+
+            return Err(LLVMString::create(c_string.as_ptr()));
+        }
+
+        // LLVM destroys the source module, which would leave any other handle to it dangling
+        if Rc::strong_count(&other.state) > 1 {
+            let c_string = CString::new("Cannot link in a module which has other handles to it").expect("Conversion to CString failed unexpectedly");
+
+            return Err(LLVMString::create(c_string.as_ptr()));
+        }
+
+        let context = unsafe {
+            LLVMGetModuleContext(self.module.get())
+        };
+
+        if context != unsafe { LLVMGetModuleContext(other.module.get()) } {
+            let c_string = CString::new("Cannot link in a module from another context").expect("Conversion to CString failed unexpectedly");
+
+            return Err(LLVMString::create(c_string.as_ptr()));
+        }
+
+        // LLVMLinkModules2 only reports errors through the context's diagnostic handler
+        let (code, err_str) = unsafe {
+            capture_diagnostic_error(context, || LLVMLinkModules2(self.module.get(), other.module.get()))
+        };
+
+        // The source module has been destroyed by LLVM, so it must not be disposed of again,
+        // though its state and context still need to be released
+        unsafe {
+            drop(ptr::read(&other.state));
+            drop(ptr::read(&other.non_global_context));
+        }
+
+        forget(other);
+
+        if code == 1 {
+            let err_str = if err_str.is_null() {
+                let c_string = CString::new("Failed to link in module").expect("Conversion to CString failed unexpectedly");
+
+                LLVMString::create(c_string.as_ptr())
+            } else {
+                LLVMString::new(err_str)
+            };
+
+            return Err(err_str);
+        }
+
+        // Free any diagnostic that was captured even though linking succeeded
+        if !err_str.is_null() {
+            drop(LLVMString::new(err_str));
         }
 
         Ok(())
@@ -517,10 +915,18 @@ impl Module {
         DataLayout::new_borrowed(data_layout)
     }
 
+    /// Gets the `DataLayout` of this `Module`, which is empty unless set through `set_data_layout`.
+    /// Creating a JIT `ExecutionEngine` from a `Module` with an empty `DataLayout` will fill it in with
+    /// that of the `ExecutionEngine`'s target.
     pub fn get_data_layout(&self) -> Ref<DataLayout> {
-        Ref::map(self.data_layout.borrow(), |l| l.as_ref().expect("DataLayout should always exist until Drop"))
+        self.refresh_data_layout();
+
+        Ref::map(self.state.data_layout.borrow(), |l| l.as_ref().expect("DataLayout should always exist until Drop"))
     }
 
+    /// Sets the `DataLayout` of this `Module`. It should match the `DataLayout` of the target the
+    /// `Module` is compiled for, ie `TargetData::get_data_layout` or `ExecutionEngine::get_target_data`,
+    /// as struct layouts and type sizes are computed from it.
     // REVIEW: Ensure the replaced string ptr still gets cleaned up by the module (I think it does)
     // valgrind might come in handy once non jemalloc allocators stabilize
     pub fn set_data_layout(&self, data_layout: &DataLayout) {
@@ -528,7 +934,16 @@ impl Module {
             LLVMSetDataLayout(self.module.get(), data_layout.as_ptr());
         }
 
-        *self.data_layout.borrow_mut() = Some(Module::get_borrowed_data_layout(self.module.get()));
+        self.refresh_data_layout();
+    }
+
+    // Creating an ExecutionEngine may replace an empty data layout with its own, which invalidates the
+    // string our borrowed DataLayout points to, so it's refetched whenever it's needed. If a `Ref` from
+    // get_data_layout is still around, it's left alone rather than panicking and refetched next time
+    fn refresh_data_layout(&self) {
+        if let Ok(mut data_layout) = self.state.data_layout.try_borrow_mut() {
+            *data_layout = Some(Module::get_borrowed_data_layout(self.module.get()));
+        }
     }
 
     /// Prints the content of the `Module` to stderr.
@@ -550,9 +965,10 @@ impl Module {
     /// Prints the content of the `Module` to a file.
     pub fn print_to_file<P: AsRef<Path>>(&self, path: P) -> Result<(), LLVMString> {
         let path = path.as_ref().to_str().expect("Did not find a valid Unicode path string");
+        let c_string = CString::new(path).expect("Conversion to CString failed unexpectedly");
         let mut err_string = unsafe { zeroed() };
         let return_code = unsafe {
-            LLVMPrintModuleToFile(self.module.get(), path.as_ptr() as *const i8, &mut err_string)
+            LLVMPrintModuleToFile(self.module.get(), c_string.as_ptr(), &mut err_string)
         };
 
         // TODO: Verify 1 is error code (LLVM can be inconsistent)
@@ -564,6 +980,8 @@ impl Module {
     }
 
     pub fn set_inline_assembly(&self, asm: &str) {
+        assert!(!self.is_frozen(), "Cannot set inline assembly on a frozen Module");
+
         let c_string = CString::new(asm).expect("Conversion to CString failed unexpectedly");
 
         unsafe {
@@ -576,6 +994,8 @@ impl Module {
     // it would be the last item in get_global_metadata I believe
     // TODOC: Appends your metadata to a global MetadataValue<Node> indexed by key
     pub fn add_global_metadata(&self, key: &str, metadata: &MetadataValue) {
+        assert!(!self.is_frozen(), "Cannot add global metadata to a frozen Module");
+
         let c_string = CString::new(key).expect("Conversion to CString failed unexpectedly");
 
         unsafe {
@@ -612,6 +1032,62 @@ impl Module {
         slice.iter().map(|val| MetadataValue::new(*val)).collect()
     }
 
+    /// Creates a `DebugInfoBuilder` for this `Module`, which can be used to describe the source code
+    /// it was generated from. If `allow_unresolved` is false, the builder will not allow unresolved
+    /// (forward declared) debug information nodes.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use inkwell::context::Context;
+    ///
+    /// let context = Context::create();
+    /// let module = context.create_module("my_module");
+    /// let debug_info_builder = module.create_debug_info_builder(true);
+    /// ```
+    #[cfg(not(any(feature = "llvm3-6", feature = "llvm3-7", feature = "llvm3-8", feature = "llvm3-9", feature = "llvm4-0", feature = "llvm5-0")))]
+    pub fn create_debug_info_builder<'a>(&'a self, allow_unresolved: bool) -> DebugInfoBuilder<'a> {
+        DebugInfoBuilder::new(self, allow_unresolved)
+    }
+
+    /// Gets the version of the debug metadata present in this `Module`, or 0 if it has none.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use inkwell::context::Context;
+    ///
+    /// let context = Context::create();
+    /// let module = context.create_module("my_module");
+    ///
+    /// assert_eq!(module.get_debug_metadata_version(), 0);
+    /// ```
+    #[cfg(not(any(feature = "llvm3-6", feature = "llvm3-7", feature = "llvm3-8", feature = "llvm3-9", feature = "llvm4-0", feature = "llvm5-0")))]
+    pub fn get_debug_metadata_version(&self) -> u32 {
+        unsafe {
+            LLVMGetModuleDebugMetadataVersion(self.module.get())
+        }
+    }
+
+    /// Strips all debug information from this `Module`, returning whether any was removed.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use inkwell::context::Context;
+    ///
+    /// let context = Context::create();
+    /// let module = context.create_module("my_module");
+    ///
+    /// assert!(!module.strip_debug_info());
+    /// ```
+    #[cfg(not(any(feature = "llvm3-6", feature = "llvm3-7", feature = "llvm3-8", feature = "llvm3-9", feature = "llvm4-0", feature = "llvm5-0")))]
+    pub fn strip_debug_info(&self) -> bool {
+        unsafe {
+            LLVMStripModuleDebugInfo(self.module.get()) == 1
+        }
+    }
+
     pub fn get_first_global(&self) -> Option<GlobalValue> {
         let value = unsafe {
             LLVMGetFirstGlobal(self.module.get())
@@ -769,6 +1245,29 @@ impl Module {
 }
 
 impl Clone for Module {
+    /// Creates an independent copy of this `Module` in the same `Context`. The copy is never
+    /// owned by an `ExecutionEngine` nor frozen, even if this `Module` is, so it may be freely
+    /// modified, such as by running destructive passes, without affecting this `Module`.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use inkwell::context::Context;
+    ///
+    /// let context = Context::create();
+    /// let module = context.create_module("my_module");
+    /// let fn_type = context.void_type().fn_type(&[], false);
+    ///
+    /// module.add_function("my_fn", &fn_type, None);
+    ///
+    /// let module2 = module.clone();
+    ///
+    /// module2.add_function("my_other_fn", &fn_type, None);
+    ///
+    /// assert!(module2.get_function("my_fn").is_some());
+    /// assert!(module.get_function("my_other_fn").is_none());
+    /// assert_eq!(module.get_context(), module2.get_context());
+    /// ```
     fn clone(&self) -> Self {
         // REVIEW: Is this just a LLVM 6 bug? We could conditionally compile this assertion for affected versions
         let verify = self.verify();
@@ -787,7 +1286,12 @@ impl Clone for Module {
 // which is why DataLayout must be called with `new_borrowed`
 impl Drop for Module {
     fn drop(&mut self) {
-        if self.owned_by_ee.borrow_mut().take().is_none() {
+        // Another handle to the same module will take care of it
+        if Rc::strong_count(&self.state) > 1 {
+            return;
+        }
+
+        if self.state.owned_by_ee.borrow_mut().take().is_none() {
             unsafe {
                  LLVMDisposeModule(self.module.get());
             }
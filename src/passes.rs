@@ -13,6 +13,9 @@ use module::Module;
 use targets::TargetData;
 use values::{AsValueRef, FunctionValue};
 
+use std::borrow::Borrow;
+use std::marker::PhantomData;
+
 // REVIEW: Opt Level might be identical to targets::Option<CodeGenOptLevel>
 #[derive(Debug)]
 pub struct PassManagerBuilder {
@@ -73,22 +76,20 @@ impl PassManagerBuilder {
         }
     }
 
-    // SubType: pass_manager: &PassManager<FunctionValue>
-    pub fn populate_function_pass_manager(&self, pass_manager: &PassManager) {
+    pub fn populate_function_pass_manager(&self, pass_manager: &PassManager<FunctionValue>) {
         unsafe {
             LLVMPassManagerBuilderPopulateFunctionPassManager(self.pass_manager_builder, pass_manager.pass_manager)
         }
     }
 
-    // SubType: pass_manager: &PassManager<Module>
-    pub fn populate_module_pass_manager(&self, pass_manager: &PassManager) {
+    pub fn populate_module_pass_manager(&self, pass_manager: &PassManager<Module>) {
         unsafe {
             LLVMPassManagerBuilderPopulateModulePassManager(self.pass_manager_builder, pass_manager.pass_manager)
         }
     }
 
     // SubType: Need LTO subtype?
-    pub fn populate_lto_pass_manager(&self, pass_manager: &PassManager, internalize: bool, run_inliner: bool) {
+    pub fn populate_lto_pass_manager(&self, pass_manager: &PassManager<Module>, internalize: bool, run_inliner: bool) {
         unsafe {
             LLVMPassManagerBuilderPopulateLTOPassManager(self.pass_manager_builder, pass_manager.pass_manager, internalize as i32, run_inliner as i32)
         }
@@ -103,61 +104,168 @@ impl Drop for PassManagerBuilder {
     }
 }
 
-// SubTypes: PassManager<Module>, PassManager<FunctionValue>
-#[derive(Debug)]
-pub struct PassManager {
-    pub(crate) pass_manager: LLVMPassManagerRef,
+/// Determines what a `PassManager` runs its passes over, either a whole `Module` or a single `FunctionValue`.
+pub trait PassManagerSubType: private::Sealed {
+    type Input;
+
+    unsafe fn create<I: Borrow<Self::Input>>(input: I) -> LLVMPassManagerRef;
+    unsafe fn run_in_pass_manager(&self, pass_manager: &PassManager<Self>) -> bool where Self: Sized;
 }
 
-impl PassManager {
-    pub(crate) fn new(pass_manager: LLVMPassManagerRef) -> PassManager {
-        assert!(!pass_manager.is_null());
+mod private {
+    /// A sealed trait which ensures nobody outside this crate can implement
+    /// `PassManagerSubType`, whose `create` must return a valid pass manager.
+    ///
+    /// See https://rust-lang-nursery.github.io/api-guidelines/future-proofing.html
+    pub trait Sealed {}
+}
 
-        PassManager {
-            pass_manager,
-        }
-    }
+impl private::Sealed for Module {}
+impl private::Sealed for FunctionValue {}
 
-    // SubTypes: PassManager<Module>::create()
-    pub fn create_for_module() -> Self {
-        let pass_manager = unsafe {
-            LLVMCreatePassManager()
-        };
+impl PassManagerSubType for Module {
+    type Input = ();
 
-        PassManager::new(pass_manager)
+    unsafe fn create<I: Borrow<Self::Input>>(_: I) -> LLVMPassManagerRef {
+        LLVMCreatePassManager()
     }
 
-    // SubTypes: PassManager<FunctionValue>::create()
-    pub fn create_for_function(module: &Module) -> Self {
-        let pass_manager = unsafe {
-            LLVMCreateFunctionPassManagerForModule(module.module.get())
-        };
+    unsafe fn run_in_pass_manager(&self, pass_manager: &PassManager<Self>) -> bool {
+        LLVMRunPassManager(pass_manager.pass_manager, self.module.get()) == 1
+    }
+}
 
-        PassManager::new(pass_manager)
+// SubTypes: PassManager<FunctionValue<'m>> should borrow its Module for 'm so that
+// it cannot outlive it. Until then, that requirement is documented on PassManager
+impl PassManagerSubType for FunctionValue {
+    type Input = Module;
+
+    unsafe fn create<I: Borrow<Self::Input>>(input: I) -> LLVMPassManagerRef {
+        LLVMCreateFunctionPassManagerForModule(input.borrow().module.get())
     }
 
-    // return true means some pass modified the module, not an error occurred
+    unsafe fn run_in_pass_manager(&self, pass_manager: &PassManager<Self>) -> bool {
+        LLVMRunFunctionPassManager(pass_manager.pass_manager, self.as_value_ref()) == 1
+    }
+}
+
+/// A `PassManager` runs a set of optimization passes over either a `Module` (`PassManager<Module>`)
+/// or individual `FunctionValue`s (`PassManager<FunctionValue>`).
+///
+/// # Example
+///
+/// ```
+/// use inkwell::context::Context;
+/// use inkwell::module::Module;
+/// use inkwell::passes::PassManager;
+///
+/// let context = Context::create();
+/// let module = context.create_module("my_module");
+/// let pass_manager: PassManager<Module> = PassManager::create(());
+///
+/// pass_manager.add_promote_memory_to_register_pass();
+/// pass_manager.add_instruction_combining_pass();
+/// pass_manager.add_gvn_pass();
+///
+/// pass_manager.run_on(&module);
+/// ```
+///
+/// A `PassManager<FunctionValue>` is created for a particular `Module` and can only be run on
+/// that `Module`'s functions. It must not outlive its `Module`. This makes it possible to optimize
+/// each function as it is generated, such as in a REPL, without reoptimizing the whole `Module`:
+///
+/// ```
+/// use inkwell::context::Context;
+/// use inkwell::passes::PassManager;
+/// use inkwell::values::FunctionValue;
+///
+/// let context = Context::create();
+/// let module = context.create_module("repl");
+/// let builder = context.create_builder();
+/// let fpm: PassManager<FunctionValue> = PassManager::create(&module);
+///
+/// fpm.add_instruction_combining_pass();
+/// fpm.add_reassociate_pass();
+/// fpm.add_gvn_pass();
+/// fpm.add_cfg_simplification_pass();
+/// fpm.initialize();
+///
+/// let i32_type = context.i32_type();
+/// let fn_type = i32_type.fn_type(&[], false);
+/// let function = module.add_function("add", &fn_type, None);
+/// let entry = function.append_basic_block("entry");
+///
+/// builder.position_at_end(&entry);
+///
+/// let one = i32_type.const_int(1, false);
+/// let two = i32_type.const_int(2, false);
+/// let ptr = builder.build_alloca(i32_type, "ptr");
+///
+/// builder.build_store(&ptr, &one);
+///
+/// let loaded = builder.build_load(&ptr, "loaded").into_int_value();
+/// let sum = builder.build_int_add(loaded, two, "sum");
+///
+/// builder.build_return(Some(&sum));
+///
+/// // The loads and stores are folded away by the above passes:
+/// assert!(fpm.run_on(&function));
+///
+/// fpm.finalize();
+/// ```
+#[derive(Debug)]
+pub struct PassManager<T> {
+    pub(crate) pass_manager: LLVMPassManagerRef,
+    sub_type: PhantomData<T>,
+}
+
+impl PassManager<FunctionValue> {
+    /// Runs the initializers of all of this `PassManager`'s passes on its `Module`. This should be
+    /// called once all passes have been added, and before running it on any `FunctionValue`.
+    ///
+    /// Returns true if any of the initializers modified the `Module`, not whether or not an error occurred.
     pub fn initialize(&self) -> bool {
         unsafe {
             LLVMInitializeFunctionPassManager(self.pass_manager) == 1
         }
     }
 
+    /// Runs the finalizers of all of this `PassManager`'s passes on its `Module`. This should be
+    /// called once it will no longer be run on any `FunctionValue`.
+    ///
+    /// Returns true if any of the finalizers modified the `Module`, not whether or not an error occurred.
     pub fn finalize(&self) -> bool {
         unsafe {
             LLVMFinalizeFunctionPassManager(self.pass_manager) == 1
         }
     }
+}
 
-    pub fn run_on_function(&self, fn_value: &FunctionValue) -> bool {
-        unsafe {
-            LLVMRunFunctionPassManager(self.pass_manager, fn_value.as_value_ref()) == 1
+impl<T: PassManagerSubType> PassManager<T> {
+    pub(crate) fn new(pass_manager: LLVMPassManagerRef) -> Self {
+        assert!(!pass_manager.is_null());
+
+        PassManager {
+            pass_manager,
+            sub_type: PhantomData,
         }
     }
 
-    pub fn run_on_module(&self, module: &Module) -> bool {
+    /// Creates a new `PassManager`. A `PassManager<Module>` takes `()` as input, whereas
+    /// a `PassManager<FunctionValue>` takes the `Module` whose functions it will run on.
+    pub fn create<I: Borrow<T::Input>>(input: I) -> Self {
+        let pass_manager = unsafe {
+            T::create(input)
+        };
+
+        PassManager::new(pass_manager)
+    }
+
+    /// Runs all of this `PassManager`'s passes on the input. Returns true if any of the passes
+    /// modified it, not whether or not an error occurred.
+    pub fn run_on(&self, input: &T) -> bool {
         unsafe {
-            LLVMRunPassManager(self.pass_manager, module.module.get()) == 1
+            input.run_in_pass_manager(self)
         }
     }
 
@@ -524,7 +632,7 @@ impl PassManager {
     }
 }
 
-impl Drop for PassManager {
+impl<T> Drop for PassManager<T> {
     fn drop(&mut self) {
         unsafe {
             LLVMDisposePassManager(self.pass_manager)
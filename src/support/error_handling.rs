@@ -1,3 +1,11 @@
+#[cfg(not(any(feature = "llvm3-6", feature = "llvm3-7", feature = "llvm3-8")))]
+use libc::{c_char, c_void};
+#[cfg(not(any(feature = "llvm3-6", feature = "llvm3-7", feature = "llvm3-8")))]
+use llvm_sys::prelude::{LLVMContextRef, LLVMDiagnosticInfoRef};
+
+#[cfg(not(any(feature = "llvm3-6", feature = "llvm3-7", feature = "llvm3-8")))]
+use std::ptr;
+
 // Installs an error handler to be called before LLVM exits
 // REVIEW: Maybe it's possible to have a safe wrapper? If we can
 // wrap the provided function input ptr into a &CStr somehow
@@ -32,3 +40,38 @@ pub fn reset_fatal_error_handler() {
         LLVMResetFatalErrorHandler()
     }
 }
+
+// Runs `f` with a diagnostic handler swapped into `context` which captures the description of the
+// first error LLVM reports, for functions which only report errors that way. The previous handler
+// is restored afterwards. The returned message, if not null, must be freed by the caller.
+#[cfg(not(any(feature = "llvm3-6", feature = "llvm3-7", feature = "llvm3-8")))]
+pub(crate) unsafe fn capture_diagnostic_error<T, F: FnOnce() -> T>(context: LLVMContextRef, f: F) -> (T, *mut c_char) {
+    use llvm_sys::core::{LLVMContextGetDiagnosticContext, LLVMContextGetDiagnosticHandler, LLVMContextSetDiagnosticHandler};
+
+    let mut err_str: *mut c_char = ptr::null_mut();
+    let handler = LLVMContextGetDiagnosticHandler(context);
+    let handler_context = LLVMContextGetDiagnosticContext(context);
+
+    LLVMContextSetDiagnosticHandler(context, Some(get_error_str_diagnostic_handler), &mut err_str as *mut *mut c_char as *mut c_void);
+
+    let result = f();
+
+    LLVMContextSetDiagnosticHandler(context, handler, handler_context);
+
+    (result, err_str)
+}
+
+// Stores the description of the first error LLVM reports into the `*mut c_char` pointed to by `void_ptr`
+#[cfg(not(any(feature = "llvm3-6", feature = "llvm3-7", feature = "llvm3-8")))]
+extern "C" fn get_error_str_diagnostic_handler(diagnostic_info: LLVMDiagnosticInfoRef, void_ptr: *mut c_void) {
+    use llvm_sys::core::{LLVMGetDiagInfoDescription, LLVMGetDiagInfoSeverity};
+    use llvm_sys::LLVMDiagnosticSeverity;
+
+    unsafe {
+        let err_str = void_ptr as *mut *mut c_char;
+
+        if LLVMGetDiagInfoSeverity(diagnostic_info) == LLVMDiagnosticSeverity::LLVMDSError && (*err_str).is_null() {
+            *err_str = LLVMGetDiagInfoDescription(diagnostic_info);
+        }
+    }
+}
@@ -4,21 +4,28 @@ use libc::c_char;
 use llvm_sys::core::{LLVMCreateMessage, LLVMDisposeMessage};
 use llvm_sys::support::LLVMLoadLibraryPermanently;
 
+use std::cell::UnsafeCell;
 use std::error::Error;
 use std::fmt::{self, Debug, Display, Formatter};
 use std::ffi::{CString, CStr};
 use std::ops::Deref;
 
 /// An owned LLVM String. Also known as a LLVM Message
-#[derive(Eq)]
+///
+/// It derefs to a `CStr`, so `to_str` and `to_string_lossy` are available to borrow its contents.
+/// Its `Display` and `AsRef<str>` implementations replace any invalid UTF-8 with
+/// `U+FFFD REPLACEMENT CHARACTER`.
 pub struct LLVMString {
     pub(crate) ptr: *const c_char,
+    // Lossy copy of the contents, only allocated by `as_ref` when they aren't valid UTF-8
+    lossy: UnsafeCell<Option<Box<str>>>,
 }
 
 impl LLVMString {
     pub(crate) fn new(ptr: *const c_char) -> Self {
         LLVMString {
             ptr,
+            lossy: UnsafeCell::new(None),
         }
     }
 
@@ -31,10 +38,21 @@ impl LLVMString {
         (*self).to_string_lossy().into_owned()
     }
 
+    /// Converts this `LLVMString` into a Rust `String`, returning it back
+    /// unchanged if it isn't valid UTF-8.
+    pub fn into_string(self) -> Result<String, LLVMString> {
+        let string = self.to_str().map(|string| string.to_owned());
+
+        match string {
+            Ok(string) => Ok(string),
+            Err(_) => Err(self),
+        }
+    }
+
     /// Don't use this if it's not necessary. You likely need to allocate
     /// a CString as input and then LLVM will likely allocate their own string
     /// anyway.
-    fn create(bytes: *const c_char) -> LLVMString {
+    pub(crate) fn create(bytes: *const c_char) -> LLVMString {
         let ptr = unsafe {
             LLVMCreateMessage(bytes)
         };
@@ -61,7 +79,31 @@ impl Debug for LLVMString {
 
 impl Display for LLVMString {
     fn fmt(&self, f: &mut Formatter) -> Result<(), fmt::Error> {
-        write!(f, "{:?}", self.deref())
+        write!(f, "{}", self.to_string_lossy())
+    }
+}
+
+impl AsRef<str> for LLVMString {
+    fn as_ref(&self) -> &str {
+        if let Ok(string) = self.to_str() {
+            return string;
+        }
+
+        // LLVMString is neither Send nor Sync, and the cache is only written
+        // while it is empty, so no reference into it can be invalidated
+        let lossy = unsafe { &mut *self.lossy.get() };
+
+        if lossy.is_none() {
+            *lossy = Some(self.to_string_lossy().into_owned().into_boxed_str());
+        }
+
+        lossy.as_ref().unwrap()
+    }
+}
+
+impl From<LLVMString> for String {
+    fn from(string: LLVMString) -> String {
+        string.to_string()
     }
 }
 
@@ -71,12 +113,10 @@ impl PartialEq for LLVMString {
     }
 }
 
-impl Error for LLVMString {
-    fn description(&self) -> &str {
-        self.to_str().expect("Could not convert LLVMString to str (likely invalid unicode)")
-    }
+impl Eq for LLVMString {}
 
-    fn cause(&self) -> Option<&Error> {
+impl Error for LLVMString {
+    fn source(&self) -> Option<&(Error + 'static)> {
         None
     }
 }
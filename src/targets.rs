@@ -17,7 +17,7 @@ use std::mem::zeroed;
 use std::path::Path;
 use std::ptr;
 
-#[derive(Debug, PartialEq, Eq)]
+#[derive(Debug, PartialEq, Eq, Copy, Clone)]
 pub enum CodeModel {
     Default,
     JITDefault,
@@ -27,6 +27,19 @@ pub enum CodeModel {
     Large,
 }
 
+impl CodeModel {
+    pub(crate) fn as_llvm_code_model(&self) -> LLVMCodeModel {
+        match *self {
+            CodeModel::Default => LLVMCodeModel::LLVMCodeModelDefault,
+            CodeModel::JITDefault => LLVMCodeModel::LLVMCodeModelJITDefault,
+            CodeModel::Small => LLVMCodeModel::LLVMCodeModelSmall,
+            CodeModel::Kernel => LLVMCodeModel::LLVMCodeModelKernel,
+            CodeModel::Medium => LLVMCodeModel::LLVMCodeModelMedium,
+            CodeModel::Large => LLVMCodeModel::LLVMCodeModelLarge,
+        }
+    }
+}
+
 #[derive(Debug, PartialEq, Eq)]
 pub enum RelocMode {
     Default,
@@ -36,9 +49,12 @@ pub enum RelocMode {
 }
 
 
-#[derive(Debug, PartialEq, Eq)]
+/// Determines the format of the code emitted by a `TargetMachine`.
+#[derive(Debug, PartialEq, Eq, Copy, Clone)]
 pub enum FileType {
+    /// Textual assembly, typically written to a `.s` file.
     Assembly,
+    /// A native object file, typically written to a `.o` file.
     Object,
 }
 
@@ -672,14 +688,7 @@ impl Target {
             OptimizationLevel::Default => LLVMCodeGenOptLevel::LLVMCodeGenLevelDefault,
             OptimizationLevel::Aggressive => LLVMCodeGenOptLevel::LLVMCodeGenLevelAggressive,
         };
-        let code_model = match code_model {
-            CodeModel::Default => LLVMCodeModel::LLVMCodeModelDefault,
-            CodeModel::JITDefault => LLVMCodeModel::LLVMCodeModelJITDefault,
-            CodeModel::Small => LLVMCodeModel::LLVMCodeModelSmall,
-            CodeModel::Kernel => LLVMCodeModel::LLVMCodeModelKernel,
-            CodeModel::Medium => LLVMCodeModel::LLVMCodeModelMedium,
-            CodeModel::Large => LLVMCodeModel::LLVMCodeModelLarge,
-        };
+        let code_model = code_model.as_llvm_code_model();
         let reloc_mode = match reloc_mode {
             RelocMode::Default => LLVMRelocMode::LLVMRelocDefault,
             RelocMode::Static => LLVMRelocMode::LLVMRelocStatic,
@@ -782,13 +791,13 @@ impl Target {
     }
 }
 
-#[derive(Debug)]
+#[derive(Debug, PartialEq, Eq)]
 pub struct TargetMachine {
     target_machine: LLVMTargetMachineRef,
 }
 
 impl TargetMachine {
-    fn new(target_machine: LLVMTargetMachineRef) -> Self {
+    pub(crate) fn new(target_machine: LLVMTargetMachineRef) -> Self {
         assert!(!target_machine.is_null());
 
         TargetMachine {
@@ -841,12 +850,39 @@ impl TargetMachine {
     }
 
     // TODO: Move to PassManager?
-    pub fn add_analysis_passes(&self, pass_manager: &PassManager) {
+    pub fn add_analysis_passes<T>(&self, pass_manager: &PassManager<T>) {
         unsafe {
             LLVMAddAnalysisPasses(self.target_machine, pass_manager.pass_manager)
         }
     }
 
+    /// Emits `module` for this `TargetMachine`'s target to a `MemoryBuffer` in the given `FileType` format.
+    /// Any error encountered while emitting code is returned as LLVM's diagnostic message.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use inkwell::OptimizationLevel;
+    /// use inkwell::context::Context;
+    /// use inkwell::targets::{CodeModel, RelocMode, FileType, Target, InitializationConfig};
+    ///
+    /// Target::initialize_x86(&InitializationConfig::default());
+    ///
+    /// let opt = OptimizationLevel::Default;
+    /// let reloc = RelocMode::Default;
+    /// let model = CodeModel::Default;
+    /// let target = Target::from_name("x86-64").unwrap();
+    /// let target_machine = target.create_target_machine("x86_64-pc-linux-gnu", "x86-64", "+avx2", opt, reloc, model).unwrap();
+    ///
+    /// let context = Context::create();
+    /// let module = context.create_module("my_module");
+    /// let void_type = context.void_type();
+    /// let fn_type = void_type.fn_type(&[], false);
+    ///
+    /// module.add_function("my_fn", &fn_type, None);
+    ///
+    /// let buffer = target_machine.write_to_memory_buffer(&module, FileType::Assembly).unwrap();
+    /// ```
     pub fn write_to_memory_buffer(&self, module: &Module, file_type: FileType) -> Result<MemoryBuffer, LLVMString> {
         let mut memory_buffer = ptr::null_mut();
         let mut err_string = unsafe { zeroed() };
@@ -854,7 +890,6 @@ impl TargetMachine {
             LLVMTargetMachineEmitToMemoryBuffer(self.target_machine, module.module.get(), file_type.as_llvm_file_type(), &mut err_string, &mut memory_buffer)
         };
 
-        // TODO: Verify 1 is error code (LLVM can be inconsistent)
         if return_code == 1 {
             return Err(LLVMString::new(err_string));
         }
@@ -862,15 +897,45 @@ impl TargetMachine {
         Ok(MemoryBuffer::new(memory_buffer))
     }
 
+    /// Emits `module` for this `TargetMachine`'s target to a file, such as a `.s` or `.o`, in the given `FileType` format.
+    /// Any error encountered while emitting code, or opening the file, is returned as LLVM's diagnostic message.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use inkwell::OptimizationLevel;
+    /// use inkwell::context::Context;
+    /// use inkwell::targets::{CodeModel, RelocMode, FileType, Target, InitializationConfig};
+    ///
+    /// use std::env;
+    ///
+    /// Target::initialize_x86(&InitializationConfig::default());
+    ///
+    /// let opt = OptimizationLevel::Default;
+    /// let reloc = RelocMode::Default;
+    /// let model = CodeModel::Default;
+    /// let path = env::temp_dir().join("main.o");
+    /// let target = Target::from_name("x86-64").unwrap();
+    /// let target_machine = target.create_target_machine("x86_64-pc-linux-gnu", "x86-64", "+avx2", opt, reloc, model).unwrap();
+    ///
+    /// let context = Context::create();
+    /// let module = context.create_module("my_module");
+    /// let void_type = context.void_type();
+    /// let fn_type = void_type.fn_type(&[], false);
+    ///
+    /// module.add_function("my_fn", &fn_type, None);
+    ///
+    /// assert!(target_machine.write_to_file(&module, FileType::Object, &path).is_ok());
+    /// ```
     pub fn write_to_file(&self, module: &Module, file_type: FileType, path: &Path) -> Result<(), LLVMString> {
         let path = path.to_str().expect("Did not find a valid Unicode path string");
+        let path = CString::new(path).expect("Conversion to CString failed unexpectedly");
         let mut err_string = unsafe { zeroed() };
         let return_code = unsafe {
             // REVIEW: Why does LLVM need a mutable ptr to path...?
             LLVMTargetMachineEmitToFile(self.target_machine, module.module.get(), path.as_ptr() as *mut i8, file_type.as_llvm_file_type(), &mut err_string)
         };
 
-        // TODO: Verify 1 is error code (LLVM can be inconsistent)
         if return_code == 1 {
             return Err(LLVMString::new(err_string));
         }
@@ -993,6 +1058,24 @@ impl TargetData {
         }
     }
 
+    /// Gets the size of a pointer in bytes, either in the default address space or in `address_space`.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use inkwell::context::Context;
+    /// use inkwell::targets::{InitializationConfig, Target};
+    /// use inkwell::OptimizationLevel;
+    ///
+    /// Target::initialize_native(&InitializationConfig::default()).unwrap();
+    ///
+    /// let context = Context::create();
+    /// let module = context.create_module("test");
+    /// let execution_engine = module.create_jit_execution_engine(OptimizationLevel::None).unwrap();
+    /// let target_data = execution_engine.get_target_data();
+    ///
+    /// assert_eq!(target_data.get_pointer_byte_size(None) as usize, std::mem::size_of::<usize>());
+    /// ```
     pub fn get_pointer_byte_size(&self, address_space: Option<AddressSpace>) -> u32 {
         match address_space {
             Some(address_space) => unsafe { LLVMPointerSizeForAS(self.target_data, address_space as u32) },
@@ -1000,6 +1083,24 @@ impl TargetData {
         }
     }
 
+    /// Gets the maximum number of bytes which may be overwritten by storing a value of `type_`.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use inkwell::context::Context;
+    /// use inkwell::targets::{InitializationConfig, Target};
+    /// use inkwell::OptimizationLevel;
+    ///
+    /// Target::initialize_native(&InitializationConfig::default()).unwrap();
+    ///
+    /// let context = Context::create();
+    /// let module = context.create_module("test");
+    /// let execution_engine = module.create_jit_execution_engine(OptimizationLevel::None).unwrap();
+    /// let target_data = execution_engine.get_target_data();
+    ///
+    /// assert_eq!(target_data.get_store_size(&context.i64_type()), 8);
+    /// ```
     pub fn get_store_size(&self, type_: &AnyType) -> u64 {
         unsafe {
             LLVMStoreSizeOfType(self.target_data, type_.as_type_ref())
@@ -1012,6 +1113,24 @@ impl TargetData {
         }
     }
 
+    /// Gets the minimum alignment in bytes the ABI requires for a value of `type_`.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use inkwell::context::Context;
+    /// use inkwell::targets::{InitializationConfig, Target};
+    /// use inkwell::OptimizationLevel;
+    ///
+    /// Target::initialize_native(&InitializationConfig::default()).unwrap();
+    ///
+    /// let context = Context::create();
+    /// let module = context.create_module("test");
+    /// let execution_engine = module.create_jit_execution_engine(OptimizationLevel::None).unwrap();
+    /// let target_data = execution_engine.get_target_data();
+    ///
+    /// assert_eq!(target_data.get_abi_alignment(&context.i32_type()), 4);
+    /// ```
     pub fn get_abi_alignment(&self, type_: &AnyType) -> u32 {
         unsafe {
             LLVMABIAlignmentOfType(self.target_data, type_.as_type_ref())
@@ -1,4 +1,4 @@
-use llvm_sys::core::{LLVMConstArray, LLVMConstNull, LLVMGetArrayLength};
+use llvm_sys::core::{LLVMConstArray, LLVMConstNull, LLVMGetArrayLength, LLVMGetElementType};
 use llvm_sys::prelude::{LLVMTypeRef, LLVMValueRef};
 
 use AddressSpace;
@@ -52,12 +52,29 @@ impl ArrayType {
         self.array_type.array_type(size)
     }
 
+    /// Creates a constant `ArrayValue` of `values`, which must be of this `ArrayType`'s element type.
+    /// The result is of this `ArrayType` when there are as many `values` as its length.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use inkwell::AddressSpace;
+    /// use inkwell::context::Context;
+    ///
+    /// let context = Context::create();
+    /// let i8_ptr_type = context.i8_type().ptr_type(AddressSpace::Generic);
+    /// let ptr_array_type = i8_ptr_type.array_type(2);
+    /// let ptr_array = ptr_array_type.const_array(&[i8_ptr_type.const_null(), i8_ptr_type.const_null()]);
+    ///
+    /// assert_eq!(ptr_array.get_type(), ptr_array_type);
+    /// ```
     pub fn const_array<V: BasicValue>(&self, values: &[V]) -> ArrayValue {
         let mut values: Vec<LLVMValueRef> = values.iter()
                                                   .map(|val| val.as_value_ref())
                                                   .collect();
+        // LLVMConstArray takes the element type, not the array type
         let value = unsafe {
-            LLVMConstArray(self.as_type_ref(), values.as_mut_ptr(), values.len() as u32)
+            LLVMConstArray(LLVMGetElementType(self.as_type_ref()), values.as_mut_ptr(), values.len() as u32)
         };
 
         ArrayValue::new(value)
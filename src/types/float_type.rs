@@ -1,13 +1,36 @@
-use llvm_sys::core::{LLVMConstReal, LLVMConstNull, LLVMHalfType, LLVMFloatType, LLVMDoubleType, LLVMFP128Type, LLVMPPCFP128Type, LLVMConstRealOfStringAndSize, LLVMX86FP80Type};
+use llvm_sys::core::{LLVMConstArray, LLVMConstBitCast, LLVMConstIntOfArbitraryPrecision, LLVMGetTypeContext, LLVMIntTypeInContext, LLVMConstVector, LLVMConstReal, LLVMConstNull, LLVMHalfType, LLVMFloatType, LLVMDoubleType, LLVMFP128Type, LLVMPPCFP128Type, LLVMConstRealOfStringAndSize, LLVMX86FP80Type, LLVMHalfTypeInContext, LLVMFloatTypeInContext, LLVMDoubleTypeInContext, LLVMX86FP80TypeInContext, LLVMFP128TypeInContext, LLVMPPCFP128TypeInContext};
 use llvm_sys::execution_engine::LLVMCreateGenericValueOfFloat;
-use llvm_sys::prelude::LLVMTypeRef;
+use llvm_sys::prelude::{LLVMTypeRef, LLVMValueRef};
+use llvm_sys::LLVMTypeKind;
+
+use std::f64;
 
 use AddressSpace;
-use context::ContextRef;
+use context::{Context, ContextRef};
 use support::LLVMString;
 use types::traits::AsTypeRef;
 use types::{Type, PointerType, FunctionType, BasicType, ArrayType, VectorType};
-use values::{FloatValue, GenericValue, PointerValue, IntValue};
+use values::{AsValueRef, ArrayValue, FloatValue, GenericValue, PointerValue, IntValue, VectorValue};
+
+/// The floating point format of a `FloatType`.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum FloatKind {
+    /// A 16 bit half precision float
+    F16,
+    /// A 32 bit single precision float
+    F32,
+    /// A 64 bit double precision float
+    F64,
+    /// An 80 bit x87 extended precision float
+    X86F80,
+    /// A 128 bit quad precision float
+    F128,
+    /// A 128 bit PowerPC double-double float
+    PPCF128,
+    /// A float-like type this version of inkwell does not know about, such as those
+    /// added by newer versions of LLVM. It should be rejected rather than guessed at.
+    Unknown,
+}
 
 #[derive(Debug, PartialEq, Eq, Clone, Copy)]
 pub struct FloatType {
@@ -35,6 +58,21 @@ impl FloatType {
         self.float_type.vec_type(size)
     }
 
+    /// Creates a constant `FloatValue` from an `f64`. If this `FloatType` is narrower than
+    /// 64 bits, `value` is rounded to the nearest representable value of this type, so
+    /// an `f32` converted to `f64` will always round trip exactly, but arbitrary `f64`s may not.
+    /// Use `const_float_from_bits` to specify the exact bit pattern instead.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use inkwell::context::Context;
+    ///
+    /// let context = Context::create();
+    /// let f32_type = context.f32_type();
+    ///
+    /// assert_eq!(f32_type.const_float(0.1), f32_type.const_float(0.1f32 as f64));
+    /// ```
     pub fn const_float(&self, value: f64) -> FloatValue {
         let value = unsafe {
             LLVMConstReal(self.float_type.type_, value)
@@ -43,15 +81,227 @@ impl FloatType {
         FloatValue::new(value)
     }
 
-    // REVIEW: What happens when string is invalid? Nullptr?
-    pub fn const_float_from_string(&self, slice: &str) -> FloatValue {
+    /// Creates a constant `FloatValue` whose bit pattern is the lowest `get_bit_width()` bits
+    /// of `bits`, without any rounding. This works the same way for every float type, from
+    /// `f16` up to `f128`.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use inkwell::context::Context;
+    ///
+    /// let context = Context::create();
+    /// let f16_type = context.f16_type();
+    /// let f32_type = context.f32_type();
+    /// let f128_type = context.f128_type();
+    ///
+    /// assert_eq!(f32_type.const_float_from_bits(1.5f32.to_bits() as u128), f32_type.const_float(1.5));
+    /// assert_eq!(f16_type.const_float_from_bits(0x3C00), f16_type.const_float(1.));
+    /// assert_eq!(f128_type.const_float_from_bits(0x3FFF_0000_0000_0000_0000_0000_0000_0000), f128_type.const_float(1.));
+    /// ```
+    pub fn const_float_from_bits(&self, bits: u128) -> FloatValue {
+        let words = [bits as u64, (bits >> 64) as u64];
         let value = unsafe {
-            LLVMConstRealOfStringAndSize(self.as_type_ref(), slice.as_ptr() as *const i8, slice.len() as u32)
+            let int_type = LLVMIntTypeInContext(LLVMGetTypeContext(self.as_type_ref()), self.get_bit_width());
+            let int_value = LLVMConstIntOfArbitraryPrecision(int_type, words.len() as u32, words.as_ptr());
+
+            LLVMConstBitCast(int_value, self.as_type_ref())
         };
 
         FloatValue::new(value)
     }
 
+    /// Creates a constant NaN `FloatValue`, which is either quiet or signaling. A signaling
+    /// `ppc_fp128` NaN is a constant bitcast expression rather than a plain float constant.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use inkwell::context::Context;
+    ///
+    /// let context = Context::create();
+    /// let f32_type = context.f32_type();
+    ///
+    /// assert_eq!(f32_type.const_nan(false), f32_type.const_float_from_bits(0x7FC0_0000));
+    /// assert_eq!(f32_type.const_nan(true), f32_type.const_float_from_bits(0x7F80_0001));
+    /// ```
+    pub fn const_nan(&self, signaling: bool) -> FloatValue {
+        if !signaling {
+            return self.const_float(f64::NAN);
+        }
+
+        // Signaling NaNs may be quieted when converted from an f64, so we have to spell out their bits
+        match self.float_type.get_kind() {
+            LLVMTypeKind::LLVMHalfTypeKind => self.const_float_from_bits(0x7C01),
+            LLVMTypeKind::LLVMFloatTypeKind => self.const_float_from_bits(0x7F80_0001),
+            LLVMTypeKind::LLVMDoubleTypeKind => self.const_float_from_bits(0x7FF0_0000_0000_0001),
+            // x86_fp80 has an explicit integer bit in its significand, which must be set
+            LLVMTypeKind::LLVMX86_FP80TypeKind => self.const_float_from_bits(0x7FFF_8000_0000_0000_0001),
+            LLVMTypeKind::LLVMFP128TypeKind => self.const_float_from_bits(0x7FFF_0000_0000_0000_0000_0000_0000_0001),
+            // ppc_fp128 is a pair of doubles, the first of which determines whether it's a NaN. LLVM won't
+            // constant fold this bitcast, but converting from an f64 would quiet the NaN
+            LLVMTypeKind::LLVMPPC_FP128TypeKind => self.const_float_from_bits(0x7FF0_0000_0000_0001),
+            _ => unreachable!("FloatType should always have a floating point type kind"),
+        }
+    }
+
+    /// Creates a constant infinite `FloatValue`, which is either positive or negative.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use inkwell::context::Context;
+    ///
+    /// let context = Context::create();
+    /// let f32_type = context.f32_type();
+    ///
+    /// assert_eq!(f32_type.const_infinity(false), f32_type.const_float_from_bits(0x7F80_0000));
+    /// assert_eq!(f32_type.const_infinity(true), f32_type.const_float_from_bits(0xFF80_0000));
+    /// ```
+    pub fn const_infinity(&self, negative: bool) -> FloatValue {
+        if negative {
+            self.const_float(f64::NEG_INFINITY)
+        } else {
+            self.const_float(f64::INFINITY)
+        }
+    }
+
+    /// Creates a constant `FloatValue` by parsing `slice`. Returns `None` if `slice` isn't
+    /// a valid float literal or if it doesn't fit in this `FloatType`.
+    ///
+    /// Besides decimal literals, hexadecimal ones with a binary exponent such as `0x1.8p1`
+    /// are accepted, as are `inf`, `INFINITY`, `nan` and `NaN` (optionally negated).
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use inkwell::context::Context;
+    ///
+    /// let context = Context::create();
+    /// let f32_type = context.f32_type();
+    /// let f128_type = context.f128_type();
+    ///
+    /// assert!(f32_type.const_float_from_string("1.5e3").is_some());
+    /// assert!(f32_type.const_float_from_string("0x1.8p1").is_some());
+    /// assert!(f32_type.const_float_from_string("1.5f").is_none());
+    /// assert!(f32_type.const_float_from_string("1e39").is_none());
+    /// assert!(f128_type.const_float_from_string("1e400").is_some());
+    /// ```
+    // LLVM doesn't report malformed strings back to us (and may assert on them in debug builds),
+    // so we make sure the string is well formed before handing it over. Whether it fits is left
+    // to LLVM, since it knows every float kind's range exactly
+    pub fn const_float_from_string(&self, slice: &str) -> Option<FloatValue> {
+        if !is_valid_float_literal(slice) {
+            return None;
+        }
+
+        let value = unsafe {
+            LLVMConstRealOfStringAndSize(self.as_type_ref(), slice.as_ptr() as *const i8, slice.len() as u32)
+        };
+
+        if value.is_null() {
+            return None;
+        }
+
+        let value = FloatValue::new(value);
+
+        // LLVM rounds literals which overflow to infinity, which we only expect when the literal
+        // spells it out. Constants are uniqued, so comparing against infinity is exact for any kind
+        let spells_infinity = slice.ends_with("inf") || slice.ends_with("INFINITY");
+
+        if !spells_infinity && (value == self.const_infinity(false) || value == self.const_infinity(true)) {
+            return None;
+        }
+
+        Some(value)
+    }
+
+    /// Creates a constant `ArrayValue` of `FloatValue`s whose element type is this `FloatType`.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use inkwell::context::Context;
+    ///
+    /// let context = Context::create();
+    /// let f64_type = context.f64_type();
+    /// let f64_array = f64_type.const_array(&[f64_type.const_float(1.), f64_type.const_float(2.)]);
+    ///
+    /// assert_eq!(f64_array.get_type(), f64_type.array_type(2));
+    /// ```
+    pub fn const_array(&self, values: &[FloatValue]) -> ArrayValue {
+        let mut values: Vec<LLVMValueRef> = values.iter()
+                                                  .map(|val| val.as_value_ref())
+                                                  .collect();
+        let value = unsafe {
+            LLVMConstArray(self.as_type_ref(), values.as_mut_ptr(), values.len() as u32)
+        };
+
+        ArrayValue::new(value)
+    }
+
+    /// Creates a constant `VectorValue` of `FloatValue`s whose element type is this `FloatType`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `values` is empty, as LLVM vectors must have at least one element, or if any of
+    /// the `values` are not of this `FloatType`.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use inkwell::context::Context;
+    ///
+    /// let context = Context::create();
+    /// let f32_type = context.f32_type();
+    /// let f32_vec = f32_type.const_vector(&[f32_type.const_float(1.), f32_type.const_float(2.)]);
+    ///
+    /// assert_eq!(f32_vec.get_type(), f32_type.vec_type(2));
+    /// ```
+    pub fn const_vector(&self, values: &[FloatValue]) -> VectorValue {
+        assert!(!values.is_empty(), "A constant vector must have at least one element");
+        assert!(values.iter().all(|val| val.get_type() == *self), "All values must be of this FloatType");
+
+        let mut values: Vec<LLVMValueRef> = values.iter()
+                                                  .map(|val| val.as_value_ref())
+                                                  .collect();
+        let vec_value = unsafe {
+            LLVMConstVector(values.as_mut_ptr(), values.len() as u32)
+        };
+
+        VectorValue::new(vec_value)
+    }
+
+    /// Creates a constant `VectorValue` with `lanes` elements, every one of which is `value`
+    /// as created by `const_float`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `lanes` is 0, as LLVM vectors must have at least one element.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use inkwell::context::Context;
+    ///
+    /// let context = Context::create();
+    /// let f32_type = context.f32_type();
+    /// let f32_splat = f32_type.const_splat(0.5, 4);
+    ///
+    /// assert_eq!(f32_splat.get_type(), f32_type.vec_type(4));
+    /// assert_eq!(f32_splat, f32_type.const_vector(&[f32_type.const_float(0.5); 4]));
+    /// ```
+    pub fn const_splat(&self, value: f64, lanes: u32) -> VectorValue {
+        assert!(lanes > 0, "A constant vector must have at least one element");
+
+        let mut values = vec![self.const_float(value).as_value_ref(); lanes as usize];
+        let vec_value = unsafe {
+            LLVMConstVector(values.as_mut_ptr(), lanes)
+        };
+
+        VectorValue::new(vec_value)
+    }
+
     pub fn const_null_ptr(&self) -> PointerValue {
         self.float_type.const_null_ptr()
     }
@@ -64,6 +314,103 @@ impl FloatType {
         FloatValue::new(null)
     }
 
+    /// Creates a constant positive zero of this `FloatType`. This is the same value as `const_null`.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use inkwell::context::Context;
+    ///
+    /// let context = Context::create();
+    /// let f64_type = context.f64_type();
+    ///
+    /// assert_eq!(f64_type.const_zero(), f64_type.const_float(0.));
+    /// ```
+    pub fn const_zero(&self) -> FloatValue {
+        self.const_null()
+    }
+
+    /// Gets the bit width of this `FloatType`.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use inkwell::context::Context;
+    ///
+    /// let context = Context::create();
+    ///
+    /// assert_eq!(context.f32_type().get_bit_width(), 32);
+    /// assert_eq!(context.x86_f80_type().get_bit_width(), 80);
+    /// assert_eq!(context.ppc_f128_type().get_bit_width(), 128);
+    /// ```
+    pub fn get_bit_width(&self) -> u32 {
+        match self.float_type.get_kind() {
+            LLVMTypeKind::LLVMHalfTypeKind => 16,
+            LLVMTypeKind::LLVMFloatTypeKind => 32,
+            LLVMTypeKind::LLVMDoubleTypeKind => 64,
+            LLVMTypeKind::LLVMX86_FP80TypeKind => 80,
+            LLVMTypeKind::LLVMFP128TypeKind |
+            LLVMTypeKind::LLVMPPC_FP128TypeKind => 128,
+            _ => unreachable!("FloatType should always have a floating point type kind"),
+        }
+    }
+
+    /// Gets the `FloatKind` of this `FloatType`, which is `FloatKind::Unknown` for float
+    /// types that aren't otherwise supported.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use inkwell::context::Context;
+    /// use inkwell::types::FloatKind;
+    ///
+    /// let context = Context::create();
+    ///
+    /// assert_eq!(context.f32_type().get_float_kind(), FloatKind::F32);
+    /// assert_eq!(context.ppc_f128_type().get_float_kind(), FloatKind::PPCF128);
+    /// ```
+    pub fn get_float_kind(&self) -> FloatKind {
+        match self.float_type.get_kind() {
+            LLVMTypeKind::LLVMHalfTypeKind => FloatKind::F16,
+            LLVMTypeKind::LLVMFloatTypeKind => FloatKind::F32,
+            LLVMTypeKind::LLVMDoubleTypeKind => FloatKind::F64,
+            LLVMTypeKind::LLVMX86_FP80TypeKind => FloatKind::X86F80,
+            LLVMTypeKind::LLVMFP128TypeKind => FloatKind::F128,
+            LLVMTypeKind::LLVMPPC_FP128TypeKind => FloatKind::PPCF128,
+            _ => FloatKind::Unknown,
+        }
+    }
+
+    /// Determines whether or not this `FloatType` is a 16 bit half precision float.
+    pub fn is_f16(&self) -> bool {
+        self.float_type.get_kind() == LLVMTypeKind::LLVMHalfTypeKind
+    }
+
+    /// Determines whether or not this `FloatType` is a 32 bit single precision float.
+    pub fn is_f32(&self) -> bool {
+        self.float_type.get_kind() == LLVMTypeKind::LLVMFloatTypeKind
+    }
+
+    /// Determines whether or not this `FloatType` is a 64 bit double precision float.
+    pub fn is_f64(&self) -> bool {
+        self.float_type.get_kind() == LLVMTypeKind::LLVMDoubleTypeKind
+    }
+
+    /// Determines whether or not this `FloatType` is an 80 bit x87 extended precision float.
+    pub fn is_x86_f80(&self) -> bool {
+        self.float_type.get_kind() == LLVMTypeKind::LLVMX86_FP80TypeKind
+    }
+
+    /// Determines whether or not this `FloatType` is a 128 bit quad precision float.
+    pub fn is_f128(&self) -> bool {
+        self.float_type.get_kind() == LLVMTypeKind::LLVMFP128TypeKind
+    }
+
+    /// Determines whether or not this `FloatType` is a 128 bit PowerPC double-double float.
+    pub fn is_ppc_f128(&self) -> bool {
+        self.float_type.get_kind() == LLVMTypeKind::LLVMPPC_FP128TypeKind
+    }
+
     // REVIEW: Always true -> const fn?
     pub fn is_sized(&self) -> bool {
         self.float_type.is_sized()
@@ -77,6 +424,33 @@ impl FloatType {
         self.float_type.get_context()
     }
 
+    /// Creates a `FloatType` of the same kind as this one, but in the given context.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use inkwell::context::Context;
+    ///
+    /// let context = Context::create();
+    /// let context2 = Context::create();
+    /// let x86_f80_type = context.x86_f80_type();
+    /// let x86_f80_type2 = x86_f80_type.in_context(&context2);
+    ///
+    /// assert_eq!(*x86_f80_type2.get_context(), context2);
+    /// assert_eq!(x86_f80_type2, context2.x86_f80_type());
+    /// ```
+    pub fn in_context(&self, context: &Context) -> Self {
+        match self.float_type.get_kind() {
+            LLVMTypeKind::LLVMHalfTypeKind => FloatType::f16_type_in_context(context),
+            LLVMTypeKind::LLVMFloatTypeKind => FloatType::f32_type_in_context(context),
+            LLVMTypeKind::LLVMDoubleTypeKind => FloatType::f64_type_in_context(context),
+            LLVMTypeKind::LLVMX86_FP80TypeKind => FloatType::x86_f80_type_in_context(context),
+            LLVMTypeKind::LLVMFP128TypeKind => FloatType::f128_type_in_context(context),
+            LLVMTypeKind::LLVMPPC_FP128TypeKind => FloatType::ppc_f128_type_in_context(context),
+            _ => unreachable!("FloatType should always have a floating point type kind"),
+        }
+    }
+
     pub fn ptr_type(&self, address_space: AddressSpace) -> PointerType {
         self.float_type.ptr_type(address_space)
     }
@@ -101,6 +475,31 @@ impl FloatType {
         FloatType::new(float_type)
     }
 
+    /// Creates a new `FloatType` which represents sixteen bits (two bytes) for the given context.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use inkwell::context::Context;
+    /// use inkwell::types::FloatType;
+    ///
+    /// let context = Context::create();
+    /// let f16_type = FloatType::f16_type_in_context(&context);
+    ///
+    /// assert_eq!(*f16_type.get_context(), context);
+    /// ```
+    pub fn f16_type_in_context(context: &Context) -> Self {
+        let float_type = unsafe {
+            LLVMHalfTypeInContext(*context.context)
+        };
+
+        FloatType::new(float_type)
+    }
+
+    // TODO: bf16_type() and bf16_type_in_context() via LLVMBFloatType & LLVMBFloatTypeInContext. These were
+    // only added to the C API in LLVM 11, so none of the versions we currently support can provide them.
+    // get_bit_width and the AnyTypeEnum/BasicTypeEnum constructors will also need to handle LLVMBFloatTypeKind
+
     /// Creates a new `FloatType` which represents thirty two bits (four bytes) for the global context.
     ///
     /// # Example
@@ -121,6 +520,27 @@ impl FloatType {
         FloatType::new(float_type)
     }
 
+    /// Creates a new `FloatType` which represents thirty two bits (four bytes) for the given context.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use inkwell::context::Context;
+    /// use inkwell::types::FloatType;
+    ///
+    /// let context = Context::create();
+    /// let f32_type = FloatType::f32_type_in_context(&context);
+    ///
+    /// assert_eq!(*f32_type.get_context(), context);
+    /// ```
+    pub fn f32_type_in_context(context: &Context) -> Self {
+        let float_type = unsafe {
+            LLVMFloatTypeInContext(*context.context)
+        };
+
+        FloatType::new(float_type)
+    }
+
     /// Creates a new `FloatType` which represents sixty four bits (eight bytes) for the global context.
     ///
     /// # Example
@@ -141,6 +561,27 @@ impl FloatType {
         FloatType::new(float_type)
     }
 
+    /// Creates a new `FloatType` which represents sixty four bits (eight bytes) for the given context.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use inkwell::context::Context;
+    /// use inkwell::types::FloatType;
+    ///
+    /// let context = Context::create();
+    /// let f64_type = FloatType::f64_type_in_context(&context);
+    ///
+    /// assert_eq!(*f64_type.get_context(), context);
+    /// ```
+    pub fn f64_type_in_context(context: &Context) -> Self {
+        let float_type = unsafe {
+            LLVMDoubleTypeInContext(*context.context)
+        };
+
+        FloatType::new(float_type)
+    }
+
     /// Gets the `FloatType` representing a 80 bit width. It will be assigned the global context.
     ///
     /// # Example
@@ -161,6 +602,27 @@ impl FloatType {
         FloatType::new(f128_type)
     }
 
+    /// Creates a new `FloatType` which represents eighty bits (ten bytes) for the given context.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use inkwell::context::Context;
+    /// use inkwell::types::FloatType;
+    ///
+    /// let context = Context::create();
+    /// let x86_f80_type = FloatType::x86_f80_type_in_context(&context);
+    ///
+    /// assert_eq!(*x86_f80_type.get_context(), context);
+    /// ```
+    pub fn x86_f80_type_in_context(context: &Context) -> Self {
+        let float_type = unsafe {
+            LLVMX86FP80TypeInContext(*context.context)
+        };
+
+        FloatType::new(float_type)
+    }
+
     /// Creates a new `FloatType` which represents one hundred and twenty eight bits (sixteen bytes) for the global context.
     ///
     /// # Example
@@ -182,6 +644,27 @@ impl FloatType {
         FloatType::new(float_type)
     }
 
+    /// Creates a new `FloatType` which represents one hundred and twenty eight bits (sixteen bytes) for the given context.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use inkwell::context::Context;
+    /// use inkwell::types::FloatType;
+    ///
+    /// let context = Context::create();
+    /// let f128_type = FloatType::f128_type_in_context(&context);
+    ///
+    /// assert_eq!(*f128_type.get_context(), context);
+    /// ```
+    pub fn f128_type_in_context(context: &Context) -> Self {
+        let float_type = unsafe {
+            LLVMFP128TypeInContext(*context.context)
+        };
+
+        FloatType::new(float_type)
+    }
+
     /// Creates a new `FloatType` which represents one hundred and twenty eight bits (sixteen bytes) for the current context. PPC is two 64 bits side by side rather than one single 128 bit float.
     ///
     /// # Example
@@ -203,6 +686,27 @@ impl FloatType {
         FloatType::new(float_type)
     }
 
+    /// Creates a new `FloatType` which represents one hundred and twenty eight bits (sixteen bytes), as two 64 bit floats side by side, for the given context.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use inkwell::context::Context;
+    /// use inkwell::types::FloatType;
+    ///
+    /// let context = Context::create();
+    /// let ppc_f128_type = FloatType::ppc_f128_type_in_context(&context);
+    ///
+    /// assert_eq!(*ppc_f128_type.get_context(), context);
+    /// ```
+    pub fn ppc_f128_type_in_context(context: &Context) -> Self {
+        let float_type = unsafe {
+            LLVMPPCFP128TypeInContext(*context.context)
+        };
+
+        FloatType::new(float_type)
+    }
+
     pub fn print_to_string(&self) -> LLVMString {
         self.float_type.print_to_string()
     }
@@ -226,6 +730,48 @@ impl FloatType {
     }
 }
 
+// Checks that `slice` is a float literal LLVM's parser accepts: a decimal literal, a hexadecimal
+// literal with a mandatory binary exponent, or one of the special values it recognizes
+fn is_valid_float_literal(slice: &str) -> bool {
+    const SPECIALS: [&str; 8] = ["inf", "-inf", "INFINITY", "-INFINITY", "nan", "-nan", "NaN", "-NaN"];
+
+    if SPECIALS.contains(&slice) {
+        return true;
+    }
+
+    let unsigned = if slice.starts_with('-') || slice.starts_with('+') { &slice[1..] } else { slice };
+    let is_hex = unsigned.starts_with("0x") || unsigned.starts_with("0X");
+    let (digits, exponent_markers): (&str, &[char]) = if is_hex {
+        (&unsigned[2..], &['p', 'P'])
+    } else {
+        (unsigned, &['e', 'E'])
+    };
+
+    let (significand, exponent) = match digits.find(exponent_markers) {
+        Some(index) => (&digits[..index], Some(&digits[index + 1..])),
+        None if is_hex => return false,
+        None => (digits, None),
+    };
+
+    let is_digit = |byte: u8| if is_hex { byte.is_ascii_hexdigit() } else { byte.is_ascii_digit() };
+    let has_digits = significand.bytes().any(&is_digit);
+    let is_significand_valid = significand.bytes().all(|byte| byte == b'.' || is_digit(byte))
+        && significand.bytes().filter(|&byte| byte == b'.').count() <= 1;
+
+    if !has_digits || !is_significand_valid {
+        return false;
+    }
+
+    match exponent {
+        Some(exponent) => {
+            let exponent = if exponent.starts_with('-') || exponent.starts_with('+') { &exponent[1..] } else { exponent };
+
+            !exponent.is_empty() && exponent.bytes().all(|byte| byte.is_ascii_digit())
+        },
+        None => true,
+    }
+}
+
 impl AsTypeRef for FloatType {
     fn as_type_ref(&self) -> LLVMTypeRef {
         self.float_type.type_
@@ -1,13 +1,16 @@
-use llvm_sys::core::{LLVMInt1Type, LLVMInt8Type, LLVMInt16Type, LLVMInt32Type, LLVMInt64Type, LLVMConstInt, LLVMConstNull, LLVMConstAllOnes, LLVMIntType, LLVMGetIntTypeWidth, LLVMConstIntOfStringAndSize, LLVMConstIntOfArbitraryPrecision};
+use llvm_sys::core::{LLVMInt1Type, LLVMInt8Type, LLVMInt16Type, LLVMInt32Type, LLVMInt64Type, LLVMConstInt, LLVMConstNull, LLVMConstAllOnes, LLVMIntType, LLVMGetIntTypeWidth, LLVMConstIntOfStringAndSize, LLVMConstIntOfArbitraryPrecision, LLVMConstArray};
 use llvm_sys::execution_engine::LLVMCreateGenericValueOfInt;
-use llvm_sys::prelude::LLVMTypeRef;
+use llvm_sys::prelude::{LLVMTypeRef, LLVMValueRef};
 
 use AddressSpace;
 use context::ContextRef;
 use support::LLVMString;
 use types::traits::AsTypeRef;
 use types::{Type, ArrayType, BasicType, VectorType, PointerType, FunctionType};
-use values::{GenericValue, IntValue, PointerValue};
+use values::{AsValueRef, ArrayValue, GenericValue, IntValue, PointerValue};
+
+/// The maximum bit width LLVM supports for an `IntType`.
+pub(crate) const MAX_INT_BITS: u32 = (1 << 24) - 1;
 
 #[derive(Debug, PartialEq, Eq, Clone, Copy)]
 pub struct IntType {
@@ -158,6 +161,10 @@ impl IntType {
     ///
     /// To use your own `Context`, see [inkwell::context::custom_width_int_type()](../context/struct.Context.html#method.custom_width_int_type)
     ///
+    /// # Panics
+    ///
+    /// Panics if `bits` is 0 or greater than the maximum width LLVM supports, 2^24 - 1.
+    ///
     /// # Example
     /// ```no_run
     /// use inkwell::context::Context;
@@ -169,6 +176,8 @@ impl IntType {
     /// assert_eq!(i42_type.get_context(), Context::get_global());
     /// ```
     pub fn custom_width_int_type(bits: u32) -> Self {
+        assert!(bits > 0 && bits <= MAX_INT_BITS, "IntType bit width must be between 1 and 2^24 - 1");
+
         let type_ = unsafe {
             LLVMIntType(bits)
         };
@@ -243,6 +252,40 @@ impl IntType {
         IntValue::new(value)
     }
 
+    /// Creates an `IntValue` representing a constant one of this `IntType`, which is `true` for a `bool_type`.
+    ///
+    /// # Example
+    /// ```
+    /// use inkwell::context::Context;
+    ///
+    /// let context = Context::create();
+    /// let bool_type = context.bool_type();
+    /// let true_value = bool_type.const_true();
+    ///
+    /// assert_eq!(true_value, bool_type.const_int(1, false));
+    /// assert!(!true_value.is_null());
+    /// ```
+    pub fn const_true(&self) -> IntValue {
+        self.const_int(1, false)
+    }
+
+    /// Creates an `IntValue` representing a constant zero of this `IntType`, which is `false` for a `bool_type`.
+    ///
+    /// # Example
+    /// ```
+    /// use inkwell::context::Context;
+    ///
+    /// let context = Context::create();
+    /// let bool_type = context.bool_type();
+    /// let false_value = bool_type.const_false();
+    ///
+    /// assert_eq!(false_value, bool_type.const_int(0, false));
+    /// assert!(false_value.is_null());
+    /// ```
+    pub fn const_false(&self) -> IntValue {
+        self.const_null()
+    }
+
     /// Creates a `PointerValue` representing a constant value of zero (null pointer) pointing to this `IntType`. It will be automatically assigned this `IntType`'s `Context`.
     ///
     /// # Example
@@ -271,6 +314,30 @@ impl IntType {
         IntValue::new(null)
     }
 
+    /// Creates a constant `ArrayValue` of `IntValue`s whose element type is this `IntType`.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use inkwell::context::Context;
+    ///
+    /// let context = Context::create();
+    /// let i8_type = context.i8_type();
+    /// let i8_array = i8_type.const_array(&[i8_type.const_int(1, false), i8_type.const_int(2, false)]);
+    ///
+    /// assert_eq!(i8_array.get_type(), i8_type.array_type(2));
+    /// ```
+    pub fn const_array(&self, values: &[IntValue]) -> ArrayValue {
+        let mut values: Vec<LLVMValueRef> = values.iter()
+                                                  .map(|val| val.as_value_ref())
+                                                  .collect();
+        let value = unsafe {
+            LLVMConstArray(self.as_type_ref(), values.as_mut_ptr(), values.len() as u32)
+        };
+
+        ArrayValue::new(value)
+    }
+
     pub fn fn_type(&self, param_types: &[&BasicType], is_var_args: bool) -> FunctionType {
         self.int_type.fn_type(param_types, is_var_args)
     }
@@ -300,6 +367,18 @@ impl IntType {
         self.int_type.ptr_type(address_space)
     }
 
+    /// Gets the bit width of this `IntType`.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use inkwell::context::Context;
+    ///
+    /// let context = Context::create();
+    ///
+    /// assert_eq!(context.bool_type().get_bit_width(), 1);
+    /// assert_eq!(context.custom_width_int_type(42).get_bit_width(), 42);
+    /// ```
     pub fn get_bit_width(&self) -> u32 {
         unsafe {
             LLVMGetIntTypeWidth(self.as_type_ref())
@@ -320,6 +399,22 @@ impl IntType {
         IntValue::new(self.int_type.get_undef())
     }
 
+    /// Creates a `GenericValue` of this `IntType`, such as an argument for `ExecutionEngine::run_function`.
+    /// `value` is truncated to this `IntType`'s bit width. Negative values should be passed in as their
+    /// two's complement, ie `-1i64 as u64`, with `is_signed` set to true.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use inkwell::context::Context;
+    ///
+    /// let context = Context::create();
+    /// let i32_type = context.i32_type();
+    /// let generic_value = i32_type.create_generic_value(-42i64 as u64, true);
+    ///
+    /// assert_eq!(generic_value.int_width(), i32_type.get_bit_width());
+    /// assert_eq!(generic_value.as_int(true) as i64, -42);
+    /// ```
     pub fn create_generic_value(&self, value: u64, is_signed: bool) -> GenericValue {
         let value = unsafe {
             LLVMCreateGenericValueOfInt(self.as_type_ref(), value, is_signed as i32)
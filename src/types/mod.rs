@@ -11,9 +11,10 @@ mod void_type;
 
 pub use types::array_type::ArrayType;
 pub use types::enums::{AnyTypeEnum, BasicTypeEnum};
-pub use types::float_type::FloatType;
+pub use types::float_type::{FloatKind, FloatType};
 pub use types::fn_type::FunctionType;
 pub use types::int_type::IntType;
+pub(crate) use types::int_type::MAX_INT_BITS;
 pub use types::ptr_type::PointerType;
 pub use types::struct_type::StructType;
 pub use types::traits::{AnyType, BasicType, IntMathType, FloatMathType, PointerMathType};
@@ -1,4 +1,6 @@
+use libc::c_void;
 use llvm_sys::core::{LLVMGetPointerAddressSpace, LLVMConstNull};
+use llvm_sys::execution_engine::LLVMCreateGenericValueOfPointer;
 use llvm_sys::prelude::LLVMTypeRef;
 
 use AddressSpace;
@@ -6,7 +8,7 @@ use context::ContextRef;
 use support::LLVMString;
 use types::traits::AsTypeRef;
 use types::{Type, BasicType, ArrayType, FunctionType, VectorType};
-use values::{PointerValue, IntValue};
+use values::{GenericValue, PointerValue, IntValue};
 
 #[derive(Debug, PartialEq, Eq, Clone, Copy)]
 pub struct PointerType {
@@ -81,6 +83,33 @@ impl PointerType {
     pub fn vec_type(&self, size: u32) -> VectorType {
         self.ptr_type.vec_type(size)
     }
+
+    /// Creates a `GenericValue` holding `value`, such as an argument for `ExecutionEngine::run_function`.
+    ///
+    /// This is unsafe for the same reason as `GenericValue::create_generic_value_of_pointer`: the returned
+    /// `GenericValue` does not borrow what `value` points to, so it is up to the caller to keep it alive,
+    /// unmoved and otherwise unaccessed for as long as the `GenericValue` (or any function it's passed to)
+    /// uses it.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use inkwell::AddressSpace;
+    /// use inkwell::context::Context;
+    ///
+    /// let context = Context::create();
+    /// let i8_ptr_type = context.i8_type().ptr_type(AddressSpace::Generic);
+    /// let mut bytes = [7u8, 8, 9];
+    /// let generic_value = unsafe { i8_ptr_type.create_generic_value(bytes.as_mut_ptr()) };
+    ///
+    /// assert_eq!(generic_value.as_pointer::<u8>(), bytes.as_mut_ptr());
+    /// ```
+    // REVIEW: The pointee isn't tied to this type at all, should we try to check it somehow?
+    pub unsafe fn create_generic_value<T>(&self, value: *mut T) -> GenericValue {
+        let value = LLVMCreateGenericValueOfPointer(value as *mut c_void);
+
+        GenericValue::new(value)
+    }
 }
 
 impl AsTypeRef for PointerType {
@@ -198,7 +198,27 @@ impl StructType {
 
     // REVIEW: SubTypes should allow this to only be implemented for StructType<Opaque> one day
     // but would have to return StructType<Tys>
-    // REVIEW: What happens if called with &[]? Should that still be opaque? Does the call break?
+    /// Defines the body of an opaque `StructType`, such as one created by `Context::opaque_struct_type`.
+    /// Since a `StructType` may refer to itself before its body is set, this is how recursive types are
+    /// expressed. Returns false, without modifying it, if this `StructType` already had a body.
+    /// An empty body is allowed and makes the `StructType` an empty, non opaque struct.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use inkwell::AddressSpace;
+    /// use inkwell::context::Context;
+    ///
+    /// let context = Context::create();
+    /// let i32_type = context.i32_type();
+    /// let node_type = context.opaque_struct_type("node");
+    /// let node_ptr_type = node_type.ptr_type(AddressSpace::Generic);
+    ///
+    /// assert!(node_type.is_opaque());
+    /// assert!(node_type.set_body(&[&i32_type, &node_ptr_type], false));
+    /// assert!(!node_type.is_opaque());
+    /// assert_eq!(node_type.count_fields(), 2);
+    /// ```
     pub fn set_body(&self, field_types: &[&BasicType], packed: bool) -> bool {
         let is_opaque = self.is_opaque();
         let mut field_types: Vec<LLVMTypeRef> = field_types.iter()
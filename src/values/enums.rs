@@ -6,6 +6,8 @@ use types::{AnyTypeEnum, BasicTypeEnum};
 use values::traits::AsValueRef;
 use values::{IntValue, FunctionValue, PointerValue, VectorValue, ArrayValue, StructValue, FloatValue, PhiValue, InstructionValue, MetadataValue};
 
+use std::ffi::CStr;
+
 macro_rules! enum_value_set {
     ($enum_name:ident: $($args:ident),*) => (
         #[derive(Debug, EnumAsGetters, EnumIntoGetters, EnumIsA, Clone, Copy)]
@@ -117,6 +119,49 @@ impl BasicValueEnum {
             BasicValueEnum::VectorValue(ref val) => val.as_instruction(),
         }
     }
+
+    /// Gets the name of this `BasicValueEnum`, which is empty if it is unnamed.
+    pub fn get_name(&self) -> &CStr {
+        match *self {
+            BasicValueEnum::ArrayValue(ref val) => val.get_name(),
+            BasicValueEnum::IntValue(ref val) => val.get_name(),
+            BasicValueEnum::FloatValue(ref val) => val.get_name(),
+            BasicValueEnum::StructValue(ref val) => val.get_name(),
+            BasicValueEnum::PointerValue(ref val) => val.get_name(),
+            BasicValueEnum::VectorValue(ref val) => val.get_name(),
+        }
+    }
+
+    /// Sets the name of this `BasicValueEnum`, such as that of a function parameter.
+    /// Names only serve to make the IR more readable.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use inkwell::context::Context;
+    ///
+    /// let context = Context::create();
+    /// let module = context.create_module("my_mod");
+    /// let i32_type = context.i32_type();
+    /// let fn_type = i32_type.fn_type(&[&i32_type, &i32_type], false);
+    /// let fn_value = module.add_function("add", &fn_type, None);
+    ///
+    /// for (param, name) in fn_value.get_params().iter().zip(&["lhs", "rhs"]) {
+    ///     param.set_name(name);
+    /// }
+    ///
+    /// assert_eq!(fn_value.get_nth_param(1).unwrap().get_name().to_str(), Ok("rhs"));
+    /// ```
+    pub fn set_name(&self, name: &str) {
+        match *self {
+            BasicValueEnum::ArrayValue(ref val) => val.set_name(name),
+            BasicValueEnum::IntValue(ref val) => val.set_name(name),
+            BasicValueEnum::FloatValue(ref val) => val.set_name(name),
+            BasicValueEnum::StructValue(ref val) => val.set_name(name),
+            BasicValueEnum::PointerValue(ref val) => val.set_name(name),
+            BasicValueEnum::VectorValue(ref val) => val.set_name(name),
+        }
+    }
 }
 
 impl AggregateValueEnum {
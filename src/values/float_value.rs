@@ -1,4 +1,4 @@
-use llvm_sys::core::{LLVMConstFNeg, LLVMConstFAdd, LLVMConstFSub, LLVMConstFMul, LLVMConstFDiv, LLVMConstFRem, LLVMConstFPCast, LLVMConstFPToUI, LLVMConstFPToSI, LLVMConstFPTrunc, LLVMConstFPExt, LLVMConstFCmp};
+use llvm_sys::core::{LLVMConstFNeg, LLVMConstFAdd, LLVMConstFSub, LLVMConstFMul, LLVMConstFDiv, LLVMConstFRem, LLVMConstFPCast, LLVMConstFPToUI, LLVMConstFPToSI, LLVMConstFPTrunc, LLVMConstFPExt, LLVMConstFCmp, LLVMConstRealGetDouble, LLVMIsAConstantFP};
 use llvm_sys::prelude::LLVMValueRef;
 
 use std::ffi::CStr;
@@ -147,6 +147,73 @@ impl FloatValue {
         FloatValue::new(value)
     }
 
+    /// Obtains a constant `FloatValue`'s value as an `f64`, along with whether or not
+    /// any precision was lost in the conversion. Returns `None` if this `FloatValue`
+    /// isn't a plain float constant.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use inkwell::context::Context;
+    ///
+    /// let context = Context::create();
+    /// let f32_type = context.f32_type();
+    /// let x86_f80_type = context.x86_f80_type();
+    ///
+    /// assert_eq!(f32_type.const_float(1.5).get_constant(), Some((1.5, false)));
+    /// assert_eq!(x86_f80_type.const_float_from_string("0.1").unwrap().get_constant(), Some((0.1, true)));
+    /// ```
+    pub fn get_constant(&self) -> Option<(f64, bool)> {
+        let is_constant_fp = unsafe {
+            !LLVMIsAConstantFP(self.as_value_ref()).is_null()
+        };
+
+        if !is_constant_fp {
+            return None;
+        }
+
+        let mut loses_info = 0;
+        let value = unsafe {
+            LLVMConstRealGetDouble(self.as_value_ref(), &mut loses_info)
+        };
+
+        Some((value, loses_info == 1))
+    }
+
+    /// Determines whether or not this `FloatValue` is a constant NaN.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use inkwell::context::Context;
+    ///
+    /// let context = Context::create();
+    /// let f64_type = context.f64_type();
+    ///
+    /// assert!(f64_type.const_nan(false).is_nan());
+    /// assert!(!f64_type.const_float(1.).is_nan());
+    /// ```
+    pub fn is_nan(&self) -> bool {
+        self.get_constant().map_or(false, |(value, _)| value.is_nan())
+    }
+
+    /// Determines whether or not this `FloatValue` is a constant positive or negative infinity.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use inkwell::context::Context;
+    ///
+    /// let context = Context::create();
+    /// let f64_type = context.f64_type();
+    ///
+    /// assert!(f64_type.const_infinity(true).is_infinity());
+    /// assert!(!f64_type.const_float(1.).is_infinity());
+    /// ```
+    pub fn is_infinity(&self) -> bool {
+        self.get_constant().map_or(false, |(value, _)| value.is_infinite())
+    }
+
     pub fn has_metadata(&self) -> bool {
         self.float_value.has_metadata()
     }
@@ -1,19 +1,23 @@
 use llvm_sys::analysis::{LLVMVerifierFailureAction, LLVMVerifyFunction, LLVMViewFunctionCFG, LLVMViewFunctionCFGOnly};
-use llvm_sys::core::{LLVMIsAFunction, LLVMIsConstant, LLVMGetLinkage, LLVMTypeOf, LLVMGetPreviousFunction, LLVMGetNextFunction, LLVMGetParam, LLVMCountParams, LLVMGetLastParam, LLVMCountBasicBlocks, LLVMGetFirstParam, LLVMGetNextParam, LLVMGetBasicBlocks, LLVMGetReturnType, LLVMAppendBasicBlock, LLVMDeleteFunction, LLVMGetElementType, LLVMGetLastBasicBlock, LLVMGetFirstBasicBlock, LLVMGetEntryBasicBlock, LLVMGetIntrinsicID, LLVMGetFunctionCallConv, LLVMSetFunctionCallConv, LLVMGetGC, LLVMSetGC, LLVMSetLinkage};
+use llvm_sys::core::{LLVMIsAFunction, LLVMIsConstant, LLVMGetLinkage, LLVMTypeOf, LLVMGetPreviousFunction, LLVMGetNextFunction, LLVMGetParam, LLVMCountParams, LLVMGetLastParam, LLVMCountBasicBlocks, LLVMGetFirstParam, LLVMGetNextParam, LLVMGetBasicBlocks, LLVMGetReturnType, LLVMAppendBasicBlock, LLVMDeleteFunction, LLVMGetElementType, LLVMGetLastBasicBlock, LLVMGetFirstBasicBlock, LLVMGetEntryBasicBlock, LLVMGetIntrinsicID, LLVMGetFunctionCallConv, LLVMSetFunctionCallConv, LLVMGetGC, LLVMSetGC, LLVMSetLinkage, LLVMBlockAddress};
 #[cfg(not(feature = "llvm3-6"))]
 use llvm_sys::core::{LLVMGetPersonalityFn, LLVMSetPersonalityFn};
+#[cfg(not(any(feature = "llvm3-6", feature = "llvm3-7", feature = "llvm3-8")))]
+use llvm_sys::core::{LLVMAddAttributeAtIndex, LLVMGetAttributeCountAtIndex, LLVMGetEnumAttributeAtIndex, LLVMGetStringAttributeAtIndex};
 use llvm_sys::prelude::{LLVMValueRef, LLVMBasicBlockRef};
 
 use std::ffi::{CStr, CString};
 use std::mem::forget;
 use std::fmt;
 
+#[cfg(not(any(feature = "llvm3-6", feature = "llvm3-7", feature = "llvm3-8")))]
+use attributes::{Attribute, AttributeLoc};
 use basic_block::BasicBlock;
 use module::Linkage;
 use support::LLVMString;
 use types::{BasicTypeEnum, FunctionType};
 use values::traits::AsValueRef;
-use values::{BasicValueEnum, Value, MetadataValue};
+use values::{BasicValueEnum, Value, MetadataValue, PointerValue};
 
 #[derive(PartialEq, Eq, Clone, Copy)]
 pub struct FunctionValue {
@@ -33,6 +37,7 @@ impl FunctionValue {
         Some(FunctionValue { fn_value: Value::new(value) })
     }
 
+    /// Gets the `Linkage` of this `FunctionValue`, which is `ExternalLinkage` unless otherwise specified.
     pub fn get_linkage(&self) -> Linkage {
         let linkage = unsafe {
             LLVMGetLinkage(self.as_value_ref())
@@ -41,6 +46,38 @@ impl FunctionValue {
         Linkage::new(linkage)
     }
 
+    /// Sets the `Linkage` of this `FunctionValue`. Functions with `InternalLinkage` or `PrivateLinkage`
+    /// are not exported from their module, so an `ExecutionEngine` won't find them with `get_function`,
+    /// which makes them suitable for helpers that only the module's other functions should call.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use inkwell::OptimizationLevel;
+    /// use inkwell::context::Context;
+    /// use inkwell::execution_engine::FunctionLookupError;
+    /// use inkwell::module::Linkage;
+    /// use inkwell::targets::{InitializationConfig, Target};
+    ///
+    /// Target::initialize_native(&InitializationConfig::default()).unwrap();
+    ///
+    /// let context = Context::create();
+    /// let module = context.create_module("my_module");
+    /// let builder = context.create_builder();
+    /// let fn_type = context.void_type().fn_type(&[], false);
+    /// let helper = module.add_function("helper", &fn_type, None);
+    /// let entry = context.append_basic_block(&helper, "entry");
+    ///
+    /// builder.position_at_end(&entry);
+    /// builder.build_return(None);
+    ///
+    /// helper.set_linkage(Linkage::InternalLinkage);
+    ///
+    /// let execution_engine = module.create_jit_execution_engine(OptimizationLevel::None).unwrap();
+    ///
+    /// assert_eq!(helper.get_linkage(), Linkage::InternalLinkage);
+    /// assert_eq!(unsafe { execution_engine.get_function_address("helper") }, Err(FunctionLookupError::FunctionNotFound));
+    /// ```
     pub fn set_linkage(&self, linkage: Linkage) {
         unsafe {
             LLVMSetLinkage(self.as_value_ref(), linkage.as_llvm_linkage())
@@ -63,6 +100,32 @@ impl FunctionValue {
         self.fn_value.print_to_stderr()
     }
 
+    /// Verifies this `FunctionValue` on its own, without verifying the rest of its `Module`,
+    /// and returns whether or not it is well formed. If `print` is true, LLVM will print a
+    /// description of any problem it finds to stderr. A `FunctionValue` without any `BasicBlock`s
+    /// is a declaration, which is always valid.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use inkwell::context::Context;
+    ///
+    /// let context = Context::create();
+    /// let module = context.create_module("my_mod");
+    /// let builder = context.create_builder();
+    /// let void_type = context.void_type();
+    /// let fn_type = void_type.fn_type(&[], false);
+    /// let fn_value = module.add_function("my_fn", &fn_type, None);
+    /// let entry = fn_value.append_basic_block("entry");
+    ///
+    /// // The entry block has no terminator yet
+    /// assert!(!fn_value.verify(false));
+    ///
+    /// builder.position_at_end(&entry);
+    /// builder.build_return(None);
+    ///
+    /// assert!(fn_value.verify(false));
+    /// ```
     // TODO: Maybe support LLVMAbortProcessAction?
     // FIXME: Better error returns, code 1 is error
     pub fn verify(&self, print: bool) -> bool {
@@ -156,10 +219,11 @@ impl FunctionValue {
         BasicBlock::new(bb).expect("Appending basic block should never fail")
     }
 
+    /// Gets the parameter at index `nth` (0-indexed) of this `FunctionValue`, if it exists.
     pub fn get_nth_param(&self, nth: u32) -> Option<BasicValueEnum> {
         let count = self.count_params();
 
-        if nth + 1 > count {
+        if nth >= count {
             return None;
         }
 
@@ -170,6 +234,7 @@ impl FunctionValue {
         Some(BasicValueEnum::new(param))
     }
 
+    /// Counts the number of parameters this `FunctionValue` takes.
     pub fn count_params(&self) -> u32 {
         unsafe {
             LLVMCountParams(self.fn_value.value)
@@ -206,6 +271,30 @@ impl FunctionValue {
         BasicTypeEnum::new(type_)
     }
 
+    /// Gets all of the parameters of this `FunctionValue`, in order.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use inkwell::context::Context;
+    ///
+    /// let context = Context::create();
+    /// let module = context.create_module("my_mod");
+    /// let i32_type = context.i32_type();
+    /// let f64_type = context.f64_type();
+    /// let fn_type = i32_type.fn_type(&[&i32_type, &f64_type], false);
+    /// let fn_value = module.add_function("my_fn", &fn_type, None);
+    /// let params = fn_value.get_params();
+    ///
+    /// assert_eq!(params.len(), 2);
+    /// assert!(params[0].is_int_value());
+    /// assert!(params[1].is_float_value());
+    /// ```
+    pub fn get_params(&self) -> Vec<BasicValueEnum> {
+        self.params().collect()
+    }
+
+    /// Iterates over the parameters of this `FunctionValue`, in order.
     pub fn params(&self) -> ParamValueIter {
         ParamValueIter {
             param_iter_value: self.fn_value.value,
@@ -225,6 +314,38 @@ impl FunctionValue {
         self.fn_value.get_name()
     }
 
+    /// Gets the address of one of this `FunctionValue`'s `BasicBlock`s, which may be used as a
+    /// destination of `Builder::build_indirect_branch`. Returns `None` if the `BasicBlock` belongs to
+    /// another function or is the entry block, whose address cannot be taken.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use inkwell::context::Context;
+    ///
+    /// let context = Context::create();
+    /// let module = context.create_module("my_mod");
+    /// let void_type = context.void_type();
+    /// let fn_type = void_type.fn_type(&[], false);
+    /// let fn_value = module.add_function("my_fn", &fn_type, None);
+    /// let entry = fn_value.append_basic_block("entry");
+    /// let next = fn_value.append_basic_block("next");
+    ///
+    /// assert!(fn_value.get_block_address(&entry).is_none());
+    /// assert!(fn_value.get_block_address(&next).is_some());
+    /// ```
+    pub fn get_block_address(&self, basic_block: &BasicBlock) -> Option<PointerValue> {
+        if basic_block.get_parent() != Some(*self) || self.get_entry_basic_block().as_ref() == Some(basic_block) {
+            return None;
+        }
+
+        let value = unsafe {
+            LLVMBlockAddress(self.as_value_ref(), basic_block.basic_block)
+        };
+
+        Some(PointerValue::new(value))
+    }
+
     pub fn view_function_config(&self) {
         unsafe {
             LLVMViewFunctionCFG(self.as_value_ref())
@@ -286,18 +407,59 @@ impl FunctionValue {
         }
     }
 
+    /// Gets the id of the LLVM intrinsic this `FunctionValue` declares, or 0 if it is not an intrinsic.
+    /// Intrinsics are recognized by name, so overloaded ones must currently be declared with their
+    /// mangled name, ie "llvm.sqrt.f64".
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use inkwell::context::Context;
+    ///
+    /// let context = Context::create();
+    /// let module = context.create_module("my_mod");
+    /// let f64_type = context.f64_type();
+    /// let fn_type = f64_type.fn_type(&[&f64_type], false);
+    /// let sqrt_fn = module.add_function("llvm.sqrt.f64", &fn_type, None);
+    /// let my_fn = module.add_function("my_sqrt", &fn_type, None);
+    ///
+    /// assert_ne!(sqrt_fn.get_intrinsic_id(), 0);
+    /// assert_eq!(my_fn.get_intrinsic_id(), 0);
+    /// ```
     pub fn get_intrinsic_id(&self) -> u32 {
         unsafe {
             LLVMGetIntrinsicID(self.as_value_ref())
         }
     }
 
+    /// Gets the calling convention of this `FunctionValue`, which is `CallConv::C` unless otherwise specified.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use inkwell::CallConv;
+    /// use inkwell::context::Context;
+    ///
+    /// let context = Context::create();
+    /// let module = context.create_module("my_module");
+    /// let fn_type = context.void_type().fn_type(&[], false);
+    /// let fn_value = module.add_function("my_fn", &fn_type, None);
+    ///
+    /// assert_eq!(fn_value.get_call_conventions(), CallConv::C as u32);
+    ///
+    /// fn_value.set_call_conventions(CallConv::Fast as u32);
+    ///
+    /// assert_eq!(fn_value.get_call_conventions(), CallConv::Fast as u32);
+    /// ```
     pub fn get_call_conventions(&self) -> u32 {
         unsafe {
             LLVMGetFunctionCallConv(self.as_value_ref())
         }
     }
 
+    /// Sets the calling convention of this `FunctionValue`, such as one of the `CallConv`s. Calls to
+    /// it must use the same calling convention, and `ExecutionEngine::get_function` will only find it
+    /// if it uses `CallConv::C`.
     pub fn set_call_conventions(&self, call_conventions: u32) {
         unsafe {
             LLVMSetFunctionCallConv(self.as_value_ref(), call_conventions)
@@ -321,6 +483,90 @@ impl FunctionValue {
     pub fn replace_all_uses_with(&self, other: &FunctionValue) {
         self.fn_value.replace_all_uses_with(other.as_value_ref())
     }
+
+    /// Adds an `Attribute` to a particular location in this `FunctionValue`.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use inkwell::attributes::{Attribute, AttributeLoc};
+    /// use inkwell::context::Context;
+    ///
+    /// let context = Context::create();
+    /// let module = context.create_module("my_mod");
+    /// let void_type = context.void_type();
+    /// let fn_type = void_type.fn_type(&[], false);
+    /// let fn_value = module.add_function("my_fn", &fn_type, None);
+    /// let noinline = context.create_enum_attribute(Attribute::get_named_enum_kind_id("noinline"), 0);
+    /// let string_attribute = context.create_string_attribute("my_key", "my_val");
+    ///
+    /// fn_value.add_attribute(AttributeLoc::Function, noinline);
+    /// fn_value.add_attribute(AttributeLoc::Function, string_attribute);
+    ///
+    /// assert_eq!(fn_value.count_attributes(AttributeLoc::Function), 2);
+    /// ```
+    #[cfg(not(any(feature = "llvm3-6", feature = "llvm3-7", feature = "llvm3-8")))]
+    pub fn add_attribute(&self, loc: AttributeLoc, attribute: Attribute) {
+        unsafe {
+            LLVMAddAttributeAtIndex(self.as_value_ref(), loc.get_index(), attribute.attribute)
+        }
+    }
+
+    /// Counts the number of `Attribute`s at a particular location in this `FunctionValue`.
+    #[cfg(not(any(feature = "llvm3-6", feature = "llvm3-7", feature = "llvm3-8")))]
+    pub fn count_attributes(&self, loc: AttributeLoc) -> u32 {
+        unsafe {
+            LLVMGetAttributeCountAtIndex(self.as_value_ref(), loc.get_index())
+        }
+    }
+
+    /// Gets an enum `Attribute` of the given kind id at a particular location in this `FunctionValue`, if it exists.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use inkwell::attributes::{Attribute, AttributeLoc};
+    /// use inkwell::context::Context;
+    ///
+    /// let context = Context::create();
+    /// let module = context.create_module("my_mod");
+    /// let i32_type = context.i32_type();
+    /// let fn_type = i32_type.fn_type(&[&i32_type], false);
+    /// let fn_value = module.add_function("my_fn", &fn_type, None);
+    /// let kind_id = Attribute::get_named_enum_kind_id("zeroext");
+    /// let zeroext = context.create_enum_attribute(kind_id, 0);
+    ///
+    /// fn_value.add_attribute(AttributeLoc::Param(0), zeroext);
+    ///
+    /// assert_eq!(fn_value.get_enum_attribute(AttributeLoc::Param(0), kind_id), Some(zeroext));
+    /// assert_eq!(fn_value.get_enum_attribute(AttributeLoc::Return, kind_id), None);
+    /// ```
+    #[cfg(not(any(feature = "llvm3-6", feature = "llvm3-7", feature = "llvm3-8")))]
+    pub fn get_enum_attribute(&self, loc: AttributeLoc, kind_id: u32) -> Option<Attribute> {
+        let ptr = unsafe {
+            LLVMGetEnumAttributeAtIndex(self.as_value_ref(), loc.get_index(), kind_id)
+        };
+
+        if ptr.is_null() {
+            return None;
+        }
+
+        Some(Attribute::new(ptr))
+    }
+
+    /// Gets a string `Attribute` with the given key at a particular location in this `FunctionValue`, if it exists.
+    #[cfg(not(any(feature = "llvm3-6", feature = "llvm3-7", feature = "llvm3-8")))]
+    pub fn get_string_attribute(&self, loc: AttributeLoc, key: &str) -> Option<Attribute> {
+        let ptr = unsafe {
+            LLVMGetStringAttributeAtIndex(self.as_value_ref(), loc.get_index(), key.as_ptr() as *const _, key.len() as u32)
+        };
+
+        if ptr.is_null() {
+            return None;
+        }
+
+        Some(Attribute::new(ptr))
+    }
 }
 
 impl AsValueRef for FunctionValue {
@@ -3,6 +3,10 @@ use llvm_sys::execution_engine::{LLVMCreateGenericValueOfPointer, LLVMDisposeGen
 
 use types::{AsTypeRef, FloatType};
 
+use std::slice::from_raw_parts;
+
+/// A value passed to or returned from `ExecutionEngine::run_function`. The underlying
+/// LLVM value is disposed of when the `GenericValue` is dropped.
 // SubTypes: GenericValue<IntValue, FloatValue, or PointerValue>
 #[derive(Debug)]
 pub struct GenericValue {
@@ -18,6 +22,7 @@ impl GenericValue {
         }
     }
 
+    /// Gets the bit width of the integer held by this `GenericValue`.
     // SubType: GenericValue<IntValue> only
     pub fn int_width(&self) -> u32 {
         unsafe {
@@ -33,6 +38,54 @@ impl GenericValue {
         GenericValue::new(value)
     }
 
+    /// Creates a pointer `GenericValue` which points to the start of `data`. This is how vectors
+    /// and arrays can be passed to `ExecutionEngine::run_function`, as a pointer to their elements.
+    ///
+    /// This is unsafe for the same reason as `create_generic_value_of_pointer`: the returned
+    /// `GenericValue` does not borrow `data`, so it is up to the caller to keep `data` alive, unmoved
+    /// and otherwise unaccessed for as long as the `GenericValue` (or any function it's passed to)
+    /// uses it. `data` is borrowed mutably since the function may write to it through the pointer.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use inkwell::AddressSpace;
+    /// use inkwell::context::Context;
+    /// use inkwell::values::GenericValue;
+    ///
+    /// let context = Context::create();
+    /// let module = context.create_module("test");
+    /// let builder = context.create_builder();
+    /// let i32_type = context.i32_type();
+    /// let i32_ptr_type = i32_type.ptr_type(AddressSpace::Generic);
+    /// let fn_type = i32_type.fn_type(&[&i32_ptr_type], false);
+    /// let fn_value = module.add_function("third", &fn_type, None);
+    /// let entry = context.append_basic_block(&fn_value, "entry");
+    ///
+    /// builder.position_at_end(&entry);
+    ///
+    /// let array_ptr = fn_value.get_first_param().unwrap().into_pointer_value();
+    /// let third_ptr = unsafe { builder.build_gep(&array_ptr, &[i32_type.const_int(2, false)], "third_ptr") };
+    /// let third = builder.build_load(&third_ptr, "third");
+    ///
+    /// builder.build_return(Some(&third));
+    ///
+    /// let execution_engine = module.create_interpreter_execution_engine().unwrap();
+    /// let mut data = [1i32, 2, 3, 4];
+    /// let arg = unsafe { GenericValue::from_slice(&mut data) };
+    /// let result = unsafe { execution_engine.run_function(&fn_value, &[&arg]) }.unwrap();
+    ///
+    /// assert_eq!(result.as_int(true), 3);
+    /// ```
+    // SubType: GenericValue<PointerValue, T>
+    pub unsafe fn from_slice<T>(data: &mut [T]) -> Self {
+        let value = LLVMCreateGenericValueOfPointer(data.as_mut_ptr() as *mut c_void);
+
+        GenericValue::new(value)
+    }
+
+    /// Gets the integer held by this `GenericValue`, sign extending it to 64 bits
+    /// when `is_signed` is true and zero extending it otherwise.
     // SubType: impl only for GenericValue<IntValue>
     pub fn as_int(&self, is_signed: bool) -> u64 {
         unsafe {
@@ -40,6 +93,12 @@ impl GenericValue {
         }
     }
 
+    /// Consumes this `GenericValue`, returning its integer as with `as_int`.
+    // SubType: impl only for GenericValue<IntValue>
+    pub fn into_int(self, is_signed: bool) -> u64 {
+        self.as_int(is_signed)
+    }
+
     // SubType: impl only for GenericValue<FloatValue>
     pub fn as_float(&self, float_type: &FloatType) -> f64 {
         unsafe {
@@ -47,6 +106,85 @@ impl GenericValue {
         }
     }
 
+    /// Consumes this `GenericValue`, returning its float as with `as_float`.
+    // SubType: impl only for GenericValue<FloatValue>
+    pub fn into_float(self, float_type: &FloatType) -> f64 {
+        self.as_float(float_type)
+    }
+
+    /// Gets the pointer held by this `GenericValue`, cast to a `*mut T`. Dereferencing it is only
+    /// valid while whatever it points to is still alive, and only as a `T` if that is what it points to.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use inkwell::values::GenericValue;
+    ///
+    /// let mut value = 42u32;
+    /// let generic_value = unsafe { GenericValue::create_generic_value_of_pointer(&mut value) };
+    /// let ptr = generic_value.as_pointer::<u32>();
+    ///
+    /// assert_eq!(ptr, &mut value as *mut u32);
+    /// assert_eq!(unsafe { *ptr }, 42);
+    /// ```
+    // SubType: impl only for GenericValue<PointerValue, T>
+    pub fn as_pointer<T>(&self) -> *mut T {
+        unsafe {
+            LLVMGenericValueToPointer(self.generic_value) as *mut T
+        }
+    }
+
+    /// Gets the first `len` bytes pointed to by this pointer `GenericValue`.
+    ///
+    /// `ExecutionEngine::run_function` can't return aggregates such as structs by value, so
+    /// functions which produce one should instead write it through an `sret`-style pointer
+    /// parameter. The caller then passes a pointer to a buffer of their own, and can read the
+    /// result back out of it with this method (or directly from the buffer).
+    ///
+    /// This is unsafe because the pointer must be valid for reads of `len` bytes for as long as
+    /// the returned slice is in use, which cannot be checked.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use inkwell::AddressSpace;
+    /// use inkwell::context::Context;
+    /// use inkwell::values::GenericValue;
+    ///
+    /// let context = Context::create();
+    /// let module = context.create_module("test");
+    /// let builder = context.create_builder();
+    /// let i32_type = context.i32_type();
+    /// let struct_type = context.struct_type(&[i32_type.into(), i32_type.into()], false);
+    /// let struct_ptr_type = struct_type.ptr_type(AddressSpace::Generic);
+    /// let fn_type = struct_ptr_type.fn_type(&[&struct_ptr_type], false);
+    /// let fn_value = module.add_function("make_pair", &fn_type, None);
+    /// let entry = context.append_basic_block(&fn_value, "entry");
+    ///
+    /// builder.position_at_end(&entry);
+    ///
+    /// let out_ptr = fn_value.get_first_param().unwrap().into_pointer_value();
+    /// let first_ptr = builder.build_struct_gep(&out_ptr, 0, "first_ptr").unwrap();
+    /// let second_ptr = builder.build_struct_gep(&out_ptr, 1, "second_ptr").unwrap();
+    ///
+    /// builder.build_store(&first_ptr, &i32_type.const_int(1, false));
+    /// builder.build_store(&second_ptr, &i32_type.const_int(2, false));
+    /// builder.build_return(Some(&out_ptr));
+    ///
+    /// let execution_engine = module.create_interpreter_execution_engine().unwrap();
+    /// let mut pair = [0i32; 2];
+    /// let arg = unsafe { GenericValue::create_generic_value_of_pointer(&mut pair) };
+    /// let result = unsafe { execution_engine.run_function(&fn_value, &[&arg]) }.unwrap();
+    /// let bytes = unsafe { result.as_bytes(8) };
+    ///
+    /// assert_eq!(bytes[..4], 1i32.to_ne_bytes());
+    /// assert_eq!(bytes[4..], 2i32.to_ne_bytes());
+    /// ```
+    // SubType: impl only for GenericValue<PointerValue, T>
+    pub unsafe fn as_bytes(&self, len: usize) -> &[u8] {
+        from_raw_parts(self.as_pointer::<u8>(), len)
+    }
+
     // SubType: impl only for GenericValue<PointerValue, T>
     // REVIEW: How safe is this really?
     pub unsafe fn into_pointer<T>(self) -> *mut T {
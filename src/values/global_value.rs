@@ -1,5 +1,5 @@
 use llvm_sys::LLVMThreadLocalMode;
-use llvm_sys::core::{LLVMGetVisibility, LLVMSetVisibility, LLVMGetSection, LLVMSetSection, LLVMIsExternallyInitialized, LLVMSetExternallyInitialized, LLVMDeleteGlobal, LLVMIsGlobalConstant, LLVMSetGlobalConstant, LLVMGetPreviousGlobal, LLVMGetNextGlobal, LLVMHasUnnamedAddr, LLVMSetUnnamedAddr, LLVMIsThreadLocal, LLVMSetThreadLocal, LLVMGetThreadLocalMode, LLVMSetThreadLocalMode, LLVMGetInitializer, LLVMSetInitializer, LLVMIsDeclaration, LLVMGetDLLStorageClass, LLVMSetDLLStorageClass};
+use llvm_sys::core::{LLVMGetVisibility, LLVMSetVisibility, LLVMGetSection, LLVMSetSection, LLVMIsExternallyInitialized, LLVMSetExternallyInitialized, LLVMDeleteGlobal, LLVMIsGlobalConstant, LLVMSetGlobalConstant, LLVMGetPreviousGlobal, LLVMGetNextGlobal, LLVMHasUnnamedAddr, LLVMSetUnnamedAddr, LLVMIsThreadLocal, LLVMSetThreadLocal, LLVMGetThreadLocalMode, LLVMSetThreadLocalMode, LLVMGetInitializer, LLVMSetInitializer, LLVMIsDeclaration, LLVMGetDLLStorageClass, LLVMSetDLLStorageClass, LLVMIsConstant, LLVMTypeOf, LLVMGetElementType, LLVMGetAlignment, LLVMSetAlignment};
 use llvm_sys::prelude::LLVMValueRef;
 
 use std::ffi::{CString, CStr};
@@ -62,6 +62,9 @@ impl GlobalValue {
         }
     }
 
+    /// Gets the constant this `GlobalValue` is initialized with, if any. A `GlobalValue` without
+    /// an initializer is only a declaration and must be defined elsewhere, ie by another `Module`
+    /// or through an `ExecutionEngine` global mapping.
     pub fn get_initializer(&self) -> Option<BasicValueEnum> {
         let value = unsafe {
             LLVMGetInitializer(self.as_value_ref())
@@ -74,26 +77,56 @@ impl GlobalValue {
         Some(BasicValueEnum::new(value))
     }
 
+    /// Sets the constant this `GlobalValue` is initialized with, turning it into a definition.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `value` is not a constant or if its type does not match the type
+    /// this `GlobalValue` was created with.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use inkwell::context::Context;
+    ///
+    /// let context = Context::create();
+    /// let module = context.create_module("my_mod");
+    /// let i32_type = context.i32_type();
+    /// let global = module.add_global(&i32_type, None, "my_global");
+    ///
+    /// assert!(global.get_initializer().is_none());
+    ///
+    /// global.set_initializer(&i32_type.const_int(42, false));
+    ///
+    /// assert_eq!(global.get_initializer().unwrap().into_int_value(), i32_type.const_int(42, false));
+    /// ```
     // SubType: This input type should be tied to the BasicType
     pub fn set_initializer(&self, value: &BasicValue) {
         unsafe {
+            assert!(LLVMIsConstant(value.as_value_ref()) == 1, "Global initializer must be a constant");
+            assert_eq!(LLVMTypeOf(value.as_value_ref()), LLVMGetElementType(LLVMTypeOf(self.as_value_ref())), "Global initializer type must match the global's type");
+
             LLVMSetInitializer(self.as_value_ref(), value.as_value_ref())
         }
     }
 
+    /// Determines whether or not this `GlobalValue` is thread local, in which case each thread has its own copy of it.
     pub fn is_thread_local(&self) -> bool {
         unsafe {
             LLVMIsThreadLocal(self.as_value_ref()) == 1
         }
     }
 
-    // TODOC: Setting this to true is the same as setting GeneralDynamicTLSModel
+    /// Sets whether or not this `GlobalValue` is thread local. Setting this to true is the same
+    /// as calling `set_thread_local_mode` with `ThreadLocalMode::GeneralDynamicTLSModel`, and
+    /// setting it to false is the same as calling it with `None`.
     pub fn set_thread_local(&self, is_thread_local: bool) {
         unsafe {
             LLVMSetThreadLocal(self.as_value_ref(), is_thread_local as i32)
         }
     }
 
+    /// Gets the `ThreadLocalMode` of this `GlobalValue`, or `None` if it is not thread local.
     pub fn get_thread_local_mode(&self) -> Option<ThreadLocalMode> {
         let thread_local_mode = unsafe {
             LLVMGetThreadLocalMode(self.as_value_ref())
@@ -102,8 +135,31 @@ impl GlobalValue {
         ThreadLocalMode::new(thread_local_mode)
     }
 
-    // REVIEW: Does this have any bad behavior if it isn't thread local or just a noop?
-    // or should it call self.set_thread_local(true)?
+    /// Sets the `ThreadLocalMode` of this `GlobalValue`. Providing a mode will also make a `GlobalValue`
+    /// thread local if it wasn't already, whereas `None` will make it no longer thread local.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use inkwell::{AddressSpace, ThreadLocalMode};
+    /// use inkwell::context::Context;
+    ///
+    /// let context = Context::create();
+    /// let module = context.create_module("my_mod");
+    /// let i32_type = context.i32_type();
+    /// let global = module.add_global(&i32_type, Some(AddressSpace::Generic), "my_global");
+    ///
+    /// assert!(!global.is_thread_local());
+    ///
+    /// global.set_thread_local_mode(Some(ThreadLocalMode::InitialExecTLSModel));
+    ///
+    /// assert!(global.is_thread_local());
+    /// assert_eq!(global.get_thread_local_mode(), Some(ThreadLocalMode::InitialExecTLSModel));
+    ///
+    /// global.set_thread_local_mode(None);
+    ///
+    /// assert!(!global.is_thread_local());
+    /// ```
     pub fn set_thread_local_mode(&self, thread_local_mode: Option<ThreadLocalMode>) {
         let thread_local_mode = match thread_local_mode {
             Some(mode) => mode.as_llvm_mode(),
@@ -135,12 +191,37 @@ impl GlobalValue {
         }
     }
 
+    /// Determines whether or not this `GlobalValue` is constant, meaning its initializer will never be modified.
     pub fn is_constant(&self) -> bool {
         unsafe {
             LLVMIsGlobalConstant(self.as_value_ref()) == 1
         }
     }
 
+    /// Sets whether or not this `GlobalValue` is constant. Constant globals may be placed in read-only
+    /// memory and LLVM will assume loads from them always produce the initializer, so writing to a
+    /// constant global is undefined behavior.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use inkwell::context::Context;
+    ///
+    /// let context = Context::create();
+    /// let module = context.create_module("my_mod");
+    /// let i32_type = context.i32_type();
+    /// let table_type = i32_type.array_type(2);
+    /// let table = module.add_global(&table_type, None, "table");
+    ///
+    /// table.set_initializer(&table_type.const_array(&[i32_type.const_int(1, false), i32_type.const_int(2, false)]));
+    /// table.set_constant(true);
+    /// table.set_alignment(16);
+    /// table.set_section(".rodata.table");
+    ///
+    /// assert!(table.is_constant());
+    /// assert_eq!(table.get_alignment(), 16);
+    /// assert_eq!(table.get_section().to_str(), Ok(".rodata.table"));
+    /// ```
     pub fn set_constant(&self, is_constant: bool) {
         unsafe {
             LLVMSetGlobalConstant(self.as_value_ref(), is_constant as i32)
@@ -159,12 +240,15 @@ impl GlobalValue {
         }
     }
 
+    /// Sets the `GlobalVisibility` of this `GlobalValue`, which determines how it can be seen by other modules
+    /// once linked. Globals with internal or private linkage must keep the default visibility.
     pub fn set_visibility(&self, visibility: GlobalVisibility) {
         unsafe {
             LLVMSetVisibility(self.as_value_ref(), visibility.as_llvm_visibility())
         }
     }
 
+    /// Gets the `GlobalVisibility` of this `GlobalValue`, which is `GlobalVisibility::Default` unless otherwise specified.
     pub fn get_visibility(&self) -> GlobalVisibility {
         let visibility = unsafe {
             LLVMGetVisibility(self.as_value_ref())
@@ -173,12 +257,45 @@ impl GlobalValue {
         GlobalVisibility::new(visibility)
     }
 
+    // TODO: Metadata attachment (ie get_metadata/set_metadata) once we support LLVM 8.0, which is the first
+    // version with LLVMGlobalSetMetadata. LLVMSetMetadata is only valid on instructions, so Value's
+    // metadata methods must not be exposed here
+    /// Gets the alignment of this `GlobalValue` in bytes, which is 0 if it should use the target's
+    /// preferred alignment for its type.
+    pub fn get_alignment(&self) -> u32 {
+        unsafe {
+            LLVMGetAlignment(self.as_value_ref())
+        }
+    }
+
+    /// Sets the alignment of this `GlobalValue` in bytes. It must be 0 or a power of two.
+    pub fn set_alignment(&self, alignment: u32) {
+        assert!(alignment == 0 || alignment.is_power_of_two(), "Alignment must be 0 or a power of two");
+
+        unsafe {
+            LLVMSetAlignment(self.as_value_ref(), alignment)
+        }
+    }
+
+    /// Gets the name of the section this `GlobalValue` will be placed in, which is empty unless otherwise specified.
     pub fn get_section(&self) -> &CStr {
+        let section = unsafe {
+            LLVMGetSection(self.as_value_ref())
+        };
+
+        // Newer LLVM versions return a null ptr rather than an empty string when no section is set
+        if section.is_null() {
+            return unsafe {
+                CStr::from_bytes_with_nul_unchecked(b"\0")
+            };
+        }
+
         unsafe {
-            CStr::from_ptr(LLVMGetSection(self.as_value_ref()))
+            CStr::from_ptr(section)
         }
     }
 
+    /// Sets the name of the section this `GlobalValue` will be placed in, ie ".rodata".
     pub fn set_section(&self, section: &str) {
         let c_string = CString::new(section).expect("Conversion to CString failed unexpectedly");
 
@@ -1,10 +1,15 @@
-use llvm_sys::core::{LLVMGetInstructionOpcode, LLVMIsTailCall, LLVMGetPreviousInstruction, LLVMGetNextInstruction, LLVMGetInstructionParent, LLVMInstructionEraseFromParent, LLVMInstructionClone, LLVMSetVolatile, LLVMGetVolatile};
-use llvm_sys::LLVMOpcode;
+use either::Either;
+use llvm_sys::core::{LLVMGetInstructionOpcode, LLVMGetTypeKind, LLVMTypeOf, LLVMGetNumOperands, LLVMGetOperand, LLVMSetOperand, LLVMValueAsBasicBlock, LLVMValueIsBasicBlock, LLVMIsTailCall, LLVMGetPreviousInstruction, LLVMGetNextInstruction, LLVMGetInstructionParent, LLVMInstructionEraseFromParent, LLVMInstructionClone, LLVMSetVolatile, LLVMGetVolatile};
+#[cfg(not(any(feature = "llvm3-6", feature = "llvm3-7", feature = "llvm3-8")))]
+use llvm_sys::core::{LLVMAddCallSiteAttribute, LLVMGetCallSiteAttributeCount, LLVMGetCallSiteEnumAttribute};
+use llvm_sys::{LLVMOpcode, LLVMTypeKind};
 use llvm_sys::prelude::LLVMValueRef;
 
+#[cfg(not(any(feature = "llvm3-6", feature = "llvm3-7", feature = "llvm3-8")))]
+use attributes::{Attribute, AttributeLoc};
 use basic_block::BasicBlock;
 use values::traits::AsValueRef;
-use values::Value;
+use values::{BasicValue, BasicValueEnum, MetadataValue, Value};
 
 // REVIEW: Split up into structs for SubTypes on InstructionValues?
 // REVIEW: This should maybe be split up into InstructionOpcode and ConstOpcode?
@@ -251,6 +256,7 @@ impl InstructionValue {
         }
     }
 
+    /// Gets the `InstructionOpcode` of this `InstructionValue`, which identifies what kind of instruction it is.
     pub fn get_opcode(&self) -> InstructionOpcode {
         let opcode = unsafe {
             LLVMGetInstructionOpcode(self.as_value_ref())
@@ -315,6 +321,182 @@ impl InstructionValue {
         self.instruction_value.replace_all_uses_with(other.as_value_ref())
     }
 
+    /// Gets the number of operands this `InstructionValue` has. For example, a binary operator has
+    /// two operands, a store has the stored value and the pointer, and a call has its arguments
+    /// followed by the called function.
+    pub fn get_num_operands(&self) -> u32 {
+        unsafe {
+            LLVMGetNumOperands(self.as_value_ref()) as u32
+        }
+    }
+
+    // TODO: Return metadata operands, such as those of debug intrinsic calls, as MetadataValues
+    /// Gets the operand at the given index, or `None` if the index is out of range. Operands which
+    /// are `BasicBlock`s, such as the destinations of a branch, are returned on the right.
+    /// Operands which are neither basic values nor `BasicBlock`s, such as metadata or tokens,
+    /// are also `None`.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use inkwell::AddressSpace;
+    /// use inkwell::context::Context;
+    /// use inkwell::values::InstructionOpcode;
+    ///
+    /// let context = Context::create();
+    /// let module = context.create_module("my_mod");
+    /// let builder = context.create_builder();
+    /// let i32_type = context.i32_type();
+    /// let i32_ptr_type = i32_type.ptr_type(AddressSpace::Generic);
+    /// let fn_type = context.void_type().fn_type(&[&i32_ptr_type], false);
+    /// let fn_value = module.add_function("my_fn", &fn_type, None);
+    /// let entry = fn_value.append_basic_block("entry");
+    /// let exit = fn_value.append_basic_block("exit");
+    /// let ptr = fn_value.get_first_param().unwrap().into_pointer_value();
+    ///
+    /// builder.position_at_end(&entry);
+    ///
+    /// let store = builder.build_store(&ptr, &i32_type.const_int(1, false));
+    /// let branch = builder.build_unconditional_branch(&exit);
+    ///
+    /// assert_eq!(store.get_opcode(), InstructionOpcode::Store);
+    /// assert_eq!(store.get_num_operands(), 2);
+    /// assert_eq!(store.get_operand(1).unwrap().left().unwrap().into_pointer_value(), ptr);
+    /// assert!(store.get_operand(2).is_none());
+    /// assert_eq!(branch.get_operand(0).unwrap().right(), Some(exit));
+    /// ```
+    pub fn get_operand(&self, index: u32) -> Option<Either<BasicValueEnum, BasicBlock>> {
+        if index >= self.get_num_operands() {
+            return None;
+        }
+
+        let operand = unsafe {
+            LLVMGetOperand(self.as_value_ref(), index)
+        };
+
+        if operand.is_null() {
+            return None;
+        }
+
+        let is_basic_block = unsafe {
+            LLVMValueIsBasicBlock(operand) == 1
+        };
+
+        if is_basic_block {
+            let basic_block = unsafe {
+                LLVMValueAsBasicBlock(operand)
+            };
+
+            return BasicBlock::new(basic_block).map(Either::Right);
+        }
+
+        let type_kind = unsafe {
+            LLVMGetTypeKind(LLVMTypeOf(operand))
+        };
+
+        match type_kind {
+            LLVMTypeKind::LLVMMetadataTypeKind |
+            LLVMTypeKind::LLVMTokenTypeKind |
+            LLVMTypeKind::LLVMLabelTypeKind |
+            LLVMTypeKind::LLVMX86_MMXTypeKind |
+            LLVMTypeKind::LLVMVoidTypeKind |
+            LLVMTypeKind::LLVMFunctionTypeKind => None,
+            _ => Some(Either::Left(BasicValueEnum::new(operand))),
+        }
+    }
+
+    /// Replaces the operand at the given index with `value`, returning `false` if the index is out of range.
+    /// LLVM does not check that `value` has a type this instruction accepts, so this may produce
+    /// invalid IR, which can be detected with `FunctionValue::verify` or `Module::verify`.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use inkwell::AddressSpace;
+    /// use inkwell::context::Context;
+    ///
+    /// let context = Context::create();
+    /// let module = context.create_module("my_mod");
+    /// let builder = context.create_builder();
+    /// let i32_type = context.i32_type();
+    /// let i32_ptr_type = i32_type.ptr_type(AddressSpace::Generic);
+    /// let fn_type = context.void_type().fn_type(&[&i32_ptr_type], false);
+    /// let fn_value = module.add_function("my_fn", &fn_type, None);
+    /// let entry = fn_value.append_basic_block("entry");
+    /// let ptr = fn_value.get_first_param().unwrap().into_pointer_value();
+    /// let two = i32_type.const_int(2, false);
+    ///
+    /// builder.position_at_end(&entry);
+    ///
+    /// let store = builder.build_store(&ptr, &i32_type.const_int(1, false));
+    ///
+    /// assert!(store.set_operand(0, &two));
+    /// assert!(!store.set_operand(2, &two));
+    /// assert_eq!(store.get_operand(0).unwrap().left().unwrap().into_int_value(), two);
+    /// ```
+    pub fn set_operand(&self, index: u32, value: &BasicValue) -> bool {
+        if index >= self.get_num_operands() {
+            return false;
+        }
+
+        unsafe {
+            LLVMSetOperand(self.as_value_ref(), index, value.as_value_ref())
+        }
+
+        true
+    }
+
+    /// Determines whether or not this `InstructionValue` has any metadata attached to it.
+    pub fn has_metadata(&self) -> bool {
+        self.instruction_value.has_metadata()
+    }
+
+    /// Gets the `MetadataValue` of the given kind attached to this `InstructionValue`, if any.
+    pub fn get_metadata(&self, kind_id: u32) -> Option<MetadataValue> {
+        self.instruction_value.get_metadata(kind_id)
+    }
+
+    /// Attaches a `MetadataValue` node of the given kind to this `InstructionValue`, replacing any
+    /// existing one of that kind. Kind ids, such as those for `!dbg` or `!tbaa`, can be obtained through
+    /// `Context::get_kind_id`.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use inkwell::AddressSpace;
+    /// use inkwell::context::Context;
+    ///
+    /// let context = Context::create();
+    /// let module = context.create_module("my_mod");
+    /// let builder = context.create_builder();
+    /// let i32_type = context.i32_type();
+    /// let i32_ptr_type = i32_type.ptr_type(AddressSpace::Generic);
+    /// let fn_type = context.void_type().fn_type(&[&i32_ptr_type], false);
+    /// let fn_value = module.add_function("my_fn", &fn_type, None);
+    /// let entry = fn_value.append_basic_block("entry");
+    /// let ptr = fn_value.get_first_param().unwrap().into_pointer_value();
+    ///
+    /// builder.position_at_end(&entry);
+    ///
+    /// let store = builder.build_store(&ptr, &i32_type.const_int(1, false));
+    /// let my_kind_id = context.get_kind_id("my_kind");
+    /// let md_node = context.metadata_node(&[i32_type.const_int(42, false).into()]);
+    ///
+    /// assert!(!store.has_metadata());
+    ///
+    /// store.set_metadata(&md_node, my_kind_id);
+    ///
+    /// assert!(store.has_metadata());
+    /// assert_eq!(store.get_metadata(my_kind_id), Some(md_node));
+    /// ```
+    pub fn set_metadata(&self, metadata: &MetadataValue, kind_id: u32) {
+        self.instruction_value.set_metadata(metadata, kind_id)
+    }
+
+    // TODO: Fast-math flags (nnan, ninf, nsz, arcp, contract, afn, reassoc and fast) on float instructions.
+    // The C API has no way to get or set them before LLVMGetFastMathFlags and LLVMSetFastMathFlags were
+    // added in LLVM 18, so they can only be represented (likely as a bitflags struct) once that is supported
+
     // SubTypes: Only apply to memory access instructions
     /// Returns whether or not a memory access instruction is volatile.
     pub fn get_volatile(&self) -> bool {
@@ -330,6 +512,83 @@ impl InstructionValue {
             LLVMSetVolatile(self.as_value_ref(), volatile as i32)
         }
     }
+
+    // SubTypes: Only apply to call and invoke instructions
+    /// Adds an `Attribute` to a particular location of a call site, such as one built
+    /// by `Builder::build_call`. This does not affect the called `FunctionValue` itself.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use inkwell::attributes::{Attribute, AttributeLoc};
+    /// use inkwell::context::Context;
+    ///
+    /// let context = Context::create();
+    /// let module = context.create_module("my_mod");
+    /// let builder = context.create_builder();
+    /// let i32_type = context.i32_type();
+    /// let fn_type = i32_type.fn_type(&[], false);
+    /// let callee = module.add_function("callee", &fn_type, None);
+    /// let caller = module.add_function("caller", &fn_type, None);
+    /// let entry = caller.append_basic_block("entry");
+    ///
+    /// builder.position_at_end(&entry);
+    ///
+    /// let call_site = builder.build_call(&callee, &[], "call", false)
+    ///                        .left()
+    ///                        .and_then(|value| value.as_instruction())
+    ///                        .unwrap();
+    /// let kind_id = Attribute::get_named_enum_kind_id("noinline");
+    /// let noinline = context.create_enum_attribute(kind_id, 0);
+    ///
+    /// call_site.add_call_site_attribute(AttributeLoc::Function, noinline);
+    ///
+    /// assert_eq!(call_site.count_call_site_attributes(AttributeLoc::Function), 1);
+    /// assert_eq!(callee.count_attributes(AttributeLoc::Function), 0);
+    /// ```
+    #[cfg(not(any(feature = "llvm3-6", feature = "llvm3-7", feature = "llvm3-8")))]
+    pub fn add_call_site_attribute(&self, loc: AttributeLoc, attribute: Attribute) {
+        self.assert_call_site();
+
+        unsafe {
+            LLVMAddCallSiteAttribute(self.as_value_ref(), loc.get_index(), attribute.attribute)
+        }
+    }
+
+    // SubTypes: Only apply to call and invoke instructions
+    /// Counts the number of `Attribute`s at a particular location of a call site.
+    #[cfg(not(any(feature = "llvm3-6", feature = "llvm3-7", feature = "llvm3-8")))]
+    pub fn count_call_site_attributes(&self, loc: AttributeLoc) -> u32 {
+        self.assert_call_site();
+
+        unsafe {
+            LLVMGetCallSiteAttributeCount(self.as_value_ref(), loc.get_index())
+        }
+    }
+
+    // SubTypes: Only apply to call and invoke instructions
+    /// Gets an enum `Attribute` of the given kind id at a particular location of a call site, if it exists.
+    #[cfg(not(any(feature = "llvm3-6", feature = "llvm3-7", feature = "llvm3-8")))]
+    pub fn get_call_site_enum_attribute(&self, loc: AttributeLoc, kind_id: u32) -> Option<Attribute> {
+        self.assert_call_site();
+
+        let ptr = unsafe {
+            LLVMGetCallSiteEnumAttribute(self.as_value_ref(), loc.get_index(), kind_id)
+        };
+
+        if ptr.is_null() {
+            return None;
+        }
+
+        Some(Attribute::new(ptr))
+    }
+
+    #[cfg(not(any(feature = "llvm3-6", feature = "llvm3-7", feature = "llvm3-8")))]
+    fn assert_call_site(&self) {
+        let opcode = self.get_opcode();
+
+        assert!(opcode == InstructionOpcode::Call || opcode == InstructionOpcode::Invoke, "Call site attributes require a call or invoke instruction");
+    }
 }
 
 impl Clone for InstructionValue {
@@ -1,4 +1,4 @@
-use llvm_sys::core::{LLVMAddIncoming, LLVMCountIncoming, LLVMGetIncomingBlock, LLVMGetIncomingValue};
+use llvm_sys::core::{LLVMAddIncoming, LLVMCountIncoming, LLVMGetIncomingBlock, LLVMGetIncomingValue, LLVMTypeOf};
 use llvm_sys::prelude::{LLVMBasicBlockRef, LLVMValueRef};
 
 use std::ffi::CStr;
@@ -23,7 +23,61 @@ impl PhiValue {
         }
     }
 
+    /// Adds incoming edges to this `PhiValue`. Each edge pairs the value the phi takes on with the
+    /// predecessor `BasicBlock` control flows in from.
+    ///
+    /// # Panics
+    ///
+    /// Panics if any incoming value's type does not match the type of this `PhiValue`.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use inkwell::context::Context;
+    ///
+    /// let context = Context::create();
+    /// let module = context.create_module("my_mod");
+    /// let builder = context.create_builder();
+    /// let i32_type = context.i32_type();
+    /// let bool_type = context.bool_type();
+    /// let fn_type = i32_type.fn_type(&[&bool_type], false);
+    /// let fn_value = module.add_function("select_one", &fn_type, None);
+    /// let entry = fn_value.append_basic_block("entry");
+    /// let then_block = fn_value.append_basic_block("then");
+    /// let merge_block = fn_value.append_basic_block("merge");
+    /// let cond = fn_value.get_first_param().unwrap().into_int_value();
+    ///
+    /// builder.position_at_end(&entry);
+    /// builder.build_conditional_branch(&cond, &then_block, &merge_block);
+    /// builder.position_at_end(&then_block);
+    /// builder.build_unconditional_branch(&merge_block);
+    /// builder.position_at_end(&merge_block);
+    ///
+    /// let i32_one = i32_type.const_int(1, false);
+    /// let i32_two = i32_type.const_int(2, false);
+    /// let phi = builder.build_phi(&i32_type, "result");
+    ///
+    /// phi.add_incoming(&[(&i32_one, &entry), (&i32_two, &then_block)]);
+    ///
+    /// builder.build_return(Some(&phi.as_basic_value()));
+    ///
+    /// assert_eq!(phi.count_incoming(), 2);
+    /// assert_eq!(phi.get_incoming(1).unwrap().1, then_block);
+    /// assert!(fn_value.verify(false));
+    /// ```
     pub fn add_incoming(&self, incoming: &[(&BasicValue, &BasicBlock)]) {
+        let phi_type = unsafe {
+            LLVMTypeOf(self.as_value_ref())
+        };
+
+        for &(value, _) in incoming {
+            let value_type = unsafe {
+                LLVMTypeOf(value.as_value_ref())
+            };
+
+            assert_eq!(value_type, phi_type, "Incoming value type must match the phi's type");
+        }
+
         let (mut values, mut basic_blocks): (Vec<LLVMValueRef>, Vec<LLVMBasicBlockRef>) = {
             incoming.iter()
                     .map(|&(v, bb)| (v.as_value_ref(), bb.basic_block))
@@ -35,12 +89,14 @@ impl PhiValue {
         }
     }
 
+    /// Counts the number of incoming edges this `PhiValue` has.
     pub fn count_incoming(&self) -> u32 {
         unsafe {
             LLVMCountIncoming(self.as_value_ref())
         }
     }
 
+    /// Gets the value and predecessor `BasicBlock` of the incoming edge at `index`, if it exists.
     pub fn get_incoming(&self, index: u32) -> Option<(BasicValueEnum, BasicBlock)> {
         if index >= self.count_incoming() {
             return None;
@@ -0,0 +1,135 @@
+extern crate inkwell;
+
+use self::inkwell::attributes::{Attribute, AttributeLoc};
+use self::inkwell::context::Context;
+
+#[test]
+fn test_enum_attribute_kinds() {
+    assert_eq!(Attribute::get_named_enum_kind_id("foobar"), 0);
+
+    let noinline_kind_id = Attribute::get_named_enum_kind_id("noinline");
+    let alwaysinline_kind_id = Attribute::get_named_enum_kind_id("alwaysinline");
+    let readonly_kind_id = Attribute::get_named_enum_kind_id("readonly");
+    let sret_kind_id = Attribute::get_named_enum_kind_id("sret");
+
+    assert_ne!(noinline_kind_id, 0);
+    assert_ne!(alwaysinline_kind_id, 0);
+    assert_ne!(readonly_kind_id, 0);
+    assert_ne!(sret_kind_id, 0);
+    assert_ne!(noinline_kind_id, alwaysinline_kind_id);
+    assert!(sret_kind_id <= Attribute::get_last_enum_kind_id());
+}
+
+#[test]
+fn test_enum_and_string_attributes() {
+    let context = Context::create();
+    let kind_id = Attribute::get_named_enum_kind_id("align");
+    let enum_attribute = context.create_enum_attribute(kind_id, 16);
+    let string_attribute = context.create_string_attribute("my_key", "my_val");
+
+    assert!(enum_attribute.is_enum());
+    assert!(!enum_attribute.is_string());
+    assert_eq!(enum_attribute.get_enum_kind_id(), kind_id);
+    assert_eq!(enum_attribute.get_enum_value(), 16);
+
+    assert!(string_attribute.is_string());
+    assert!(!string_attribute.is_enum());
+    assert_eq!(string_attribute.get_string_kind(), b"my_key");
+    assert_eq!(string_attribute.get_string_value(), b"my_val");
+
+    // Attributes are uniqued within a Context
+    assert_eq!(enum_attribute, context.create_enum_attribute(kind_id, 16));
+    assert_ne!(enum_attribute, context.create_enum_attribute(kind_id, 8));
+}
+
+#[test]
+fn test_function_attributes() {
+    let context = Context::create();
+    let module = context.create_module("my_mod");
+    let i32_type = context.i32_type();
+    let i32_ptr_type = i32_type.ptr_type(0.into());
+    let void_type = context.void_type();
+    let fn_type = void_type.fn_type(&[&i32_ptr_type, &i32_ptr_type], false);
+    let fn_value = module.add_function("my_fn", &fn_type, None);
+
+    let noinline_kind_id = Attribute::get_named_enum_kind_id("noinline");
+    let sret_kind_id = Attribute::get_named_enum_kind_id("sret");
+    let readonly_kind_id = Attribute::get_named_enum_kind_id("readonly");
+    let noinline = context.create_enum_attribute(noinline_kind_id, 0);
+    let sret = context.create_enum_attribute(sret_kind_id, 0);
+    let readonly = context.create_enum_attribute(readonly_kind_id, 0);
+    let string_attribute = context.create_string_attribute("my_key", "my_val");
+
+    assert_eq!(fn_value.count_attributes(AttributeLoc::Function), 0);
+    assert_eq!(fn_value.count_attributes(AttributeLoc::Param(0)), 0);
+    assert_eq!(fn_value.count_attributes(AttributeLoc::Return), 0);
+
+    fn_value.add_attribute(AttributeLoc::Function, noinline);
+    fn_value.add_attribute(AttributeLoc::Function, string_attribute);
+    fn_value.add_attribute(AttributeLoc::Param(0), sret);
+    fn_value.add_attribute(AttributeLoc::Param(1), readonly);
+
+    assert_eq!(fn_value.count_attributes(AttributeLoc::Function), 2);
+    assert_eq!(fn_value.count_attributes(AttributeLoc::Param(0)), 1);
+    assert_eq!(fn_value.count_attributes(AttributeLoc::Param(1)), 1);
+    assert_eq!(fn_value.count_attributes(AttributeLoc::Return), 0);
+    assert_eq!(fn_value.get_enum_attribute(AttributeLoc::Function, noinline_kind_id), Some(noinline));
+    assert_eq!(fn_value.get_enum_attribute(AttributeLoc::Param(0), sret_kind_id), Some(sret));
+    assert_eq!(fn_value.get_enum_attribute(AttributeLoc::Param(1), sret_kind_id), None);
+    assert_eq!(fn_value.get_string_attribute(AttributeLoc::Function, "my_key"), Some(string_attribute));
+    assert_eq!(fn_value.get_string_attribute(AttributeLoc::Function, "other_key"), None);
+
+    let ir = fn_value.print_to_string().to_string();
+
+    assert!(ir.contains("sret"));
+    assert!(ir.contains("readonly"));
+}
+
+#[test]
+fn test_call_site_attributes() {
+    let context = Context::create();
+    let module = context.create_module("my_mod");
+    let builder = context.create_builder();
+    let void_type = context.void_type();
+    let fn_type = void_type.fn_type(&[], false);
+    let callee = module.add_function("callee", &fn_type, None);
+    let caller = module.add_function("caller", &fn_type, None);
+    let entry = caller.append_basic_block("entry");
+
+    builder.position_at_end(&entry);
+
+    let call_site = builder.build_call(&callee, &[], "call", false).right().unwrap();
+
+    builder.build_return(None);
+
+    let kind_id = Attribute::get_named_enum_kind_id("alwaysinline");
+    let alwaysinline = context.create_enum_attribute(kind_id, 0);
+
+    assert_eq!(call_site.count_call_site_attributes(AttributeLoc::Function), 0);
+
+    call_site.add_call_site_attribute(AttributeLoc::Function, alwaysinline);
+
+    assert_eq!(call_site.count_call_site_attributes(AttributeLoc::Function), 1);
+    assert_eq!(call_site.get_call_site_enum_attribute(AttributeLoc::Function, kind_id), Some(alwaysinline));
+    assert_eq!(callee.count_attributes(AttributeLoc::Function), 0);
+    assert!(caller.verify(false));
+}
+
+#[test]
+#[should_panic]
+fn test_call_site_attribute_on_non_call() {
+    let context = Context::create();
+    let module = context.create_module("my_mod");
+    let builder = context.create_builder();
+    let void_type = context.void_type();
+    let fn_type = void_type.fn_type(&[], false);
+    let fn_value = module.add_function("my_fn", &fn_type, None);
+    let entry = fn_value.append_basic_block("entry");
+
+    builder.position_at_end(&entry);
+
+    let ret = builder.build_return(None);
+    let noinline = context.create_enum_attribute(Attribute::get_named_enum_kind_id("noinline"), 0);
+
+    ret.add_call_site_attribute(AttributeLoc::Function, noinline);
+}
@@ -134,6 +134,32 @@ fn test_get_terminator() {
     assert_eq!(basic_block.get_last_instruction(), basic_block.get_terminator());
 }
 
+#[test]
+fn test_get_terminator_unterminated() {
+    let context = Context::create();
+    let module = context.create_module("test");
+    let builder = context.create_builder();
+
+    let i32_type = context.i32_type();
+    let fn_type = i32_type.fn_type(&[], false);
+
+    let function = module.add_function("testing", &fn_type, None);
+    let basic_block = context.append_basic_block(&function, "entry");
+
+    builder.position_at_end(&basic_block);
+    builder.build_alloca(i32_type, "ptr");
+
+    // The last instruction is not a terminator, so the block is not yet well formed
+    assert_eq!(basic_block.get_last_instruction().unwrap().get_opcode(), InstructionOpcode::Alloca);
+    assert!(basic_block.get_terminator().is_none());
+    assert!(!function.verify(false));
+
+    builder.build_return(Some(&i32_type.const_int(0, false)));
+
+    assert_eq!(basic_block.get_terminator().unwrap().get_opcode(), InstructionOpcode::Return);
+    assert!(function.verify(false));
+}
+
 #[test]
 fn test_no_parent() {
     let context = Context::create();
@@ -167,3 +193,33 @@ fn test_no_parent() {
 
     assert!(basic_block.get_parent().is_none());
 }
+
+#[test]
+#[should_panic]
+fn test_move_orphaned_basic_block() {
+    let context = Context::create();
+    let module = context.create_module("test");
+    let fn_type = context.void_type().fn_type(&[], false);
+    let function = module.add_function("testing", &fn_type, None);
+    let basic_block = context.append_basic_block(&function, "entry");
+    let basic_block2 = context.append_basic_block(&function, "next");
+
+    basic_block.remove_from_function();
+    basic_block.move_after(&basic_block2);
+}
+
+#[test]
+#[should_panic]
+fn test_delete_orphaned_basic_block() {
+    let context = Context::create();
+    let module = context.create_module("test");
+    let fn_type = context.void_type().fn_type(&[], false);
+    let function = module.add_function("testing", &fn_type, None);
+    let basic_block = context.append_basic_block(&function, "entry");
+
+    basic_block.remove_from_function();
+
+    unsafe {
+        basic_block.delete();
+    }
+}
@@ -1,13 +1,14 @@
 extern crate inkwell;
 
-use self::inkwell::{AddressSpace, OptimizationLevel};
+use self::inkwell::{AddressSpace, AtomicOrdering, AtomicRMWBinOp, OptimizationLevel};
 use self::inkwell::context::Context;
 use self::inkwell::builder::Builder;
 use self::inkwell::targets::{InitializationConfig, Target};
 use self::inkwell::execution_engine::Symbol;
-use self::inkwell::types::BasicType;
+use self::inkwell::types::{BasicType, VectorType};
+use self::inkwell::values::InstructionOpcode;
 
-use std::ffi::CString;
+use std::ffi::{CStr, CString};
 use std::ptr::null;
 
 #[test]
@@ -290,6 +291,307 @@ fn test_switch() {
     }
 }
 
+#[test]
+fn test_switch_without_cases() {
+    let context = Context::create();
+    let module = context.create_module("switch");
+    let builder = context.create_builder();
+    let i8_type = context.i8_type();
+    let fn_type = i8_type.fn_type(&[&i8_type], false);
+    let fn_value = module.add_function("switch", &fn_type, None);
+    let entry = fn_value.append_basic_block("entry");
+    let else_ = fn_value.append_basic_block("else");
+    let value = fn_value.get_first_param().unwrap().into_int_value();
+
+    builder.position_at_end(&entry);
+
+    let switch = builder.build_switch(&value, &else_, &[]);
+
+    assert_eq!(switch.get_opcode(), InstructionOpcode::Switch);
+
+    builder.position_at_end(&else_);
+    builder.build_return(Some(&value));
+
+    assert!(fn_value.verify(true));
+
+    let execution_engine = module.create_interpreter_execution_engine().unwrap();
+
+    for &input in &[0, 7, 255] {
+        let arg = i8_type.create_generic_value(input, false);
+        let result = unsafe { execution_engine.run_function(&fn_value, &[&arg]) }.unwrap();
+
+        assert_eq!(result.as_int(false), input);
+    }
+}
+
+#[test]
+#[should_panic]
+fn test_switch_non_constant_case() {
+    let context = Context::create();
+    let module = context.create_module("switch");
+    let builder = context.create_builder();
+    let i8_type = context.i8_type();
+    let fn_type = i8_type.fn_type(&[&i8_type], false);
+    let fn_value = module.add_function("switch", &fn_type, None);
+    let entry = fn_value.append_basic_block("entry");
+    let else_ = fn_value.append_basic_block("else");
+    let value = fn_value.get_first_param().unwrap().into_int_value();
+
+    builder.position_at_end(&entry);
+    builder.build_switch(&value, &else_, &[(&value, &else_)]);
+}
+
+#[test]
+fn test_indirect_branch() {
+    Target::initialize_native(&InitializationConfig::default()).expect("Failed to initialize native target");
+
+    let context = Context::create();
+    let module = context.create_module("indirect_branch");
+    let builder = context.create_builder();
+    let execution_engine = module.create_jit_execution_engine(OptimizationLevel::None).unwrap();
+
+    // Here we're going to create a function which looks roughly like:
+    // fn dispatch(val: bool) -> u8 {
+    //     let target = if val { &&one } else { &&two };
+    //     goto *target;
+    //     one: return 1;
+    //     two: return 2;
+    // }
+
+    let i8_type = context.i8_type();
+    let bool_type = context.bool_type();
+    let fn_type = i8_type.fn_type(&[&bool_type], false);
+    let fn_value = module.add_function("dispatch", &fn_type, None);
+    let entry = fn_value.append_basic_block("entry");
+    let then = fn_value.append_basic_block("then");
+    let else_ = fn_value.append_basic_block("else");
+    let dispatch = fn_value.append_basic_block("dispatch");
+    let one = fn_value.append_basic_block("one");
+    let two = fn_value.append_basic_block("two");
+    let one_address = fn_value.get_block_address(&one).unwrap();
+    let two_address = fn_value.get_block_address(&two).unwrap();
+
+    assert!(fn_value.get_block_address(&entry).is_none());
+
+    builder.position_at_end(&entry);
+
+    let target = builder.build_alloca(one_address.get_type(), "target");
+    let value = fn_value.get_first_param().unwrap().into_int_value();
+
+    builder.build_conditional_branch(&value, &then, &else_);
+
+    builder.position_at_end(&then);
+    builder.build_store(&target, &one_address);
+    builder.build_unconditional_branch(&dispatch);
+
+    builder.position_at_end(&else_);
+    builder.build_store(&target, &two_address);
+    builder.build_unconditional_branch(&dispatch);
+
+    builder.position_at_end(&dispatch);
+
+    let address = builder.build_load(&target, "address").into_pointer_value();
+    let indirect_branch = builder.build_indirect_branch(&address, &[&one, &two]);
+
+    assert_eq!(indirect_branch.get_opcode(), InstructionOpcode::IndirectBr);
+
+    builder.position_at_end(&one);
+    builder.build_return(Some(&i8_type.const_int(1, false)));
+
+    builder.position_at_end(&two);
+    builder.build_return(Some(&i8_type.const_int(2, false)));
+
+    assert!(fn_value.verify(true));
+
+    unsafe {
+        let dispatch: Symbol<unsafe extern "C" fn(bool) -> u8> = execution_engine.get_function("dispatch").unwrap();
+
+        assert_eq!(dispatch(true), 1);
+        assert_eq!(dispatch(false), 2);
+    }
+}
+
+#[test]
+#[should_panic]
+fn test_indirect_branch_other_function() {
+    let context = Context::create();
+    let module = context.create_module("indirect_branch");
+    let builder = context.create_builder();
+    let void_type = context.void_type();
+    let fn_type = void_type.fn_type(&[], false);
+    let fn_value = module.add_function("my_fn", &fn_type, None);
+    let other_fn_value = module.add_function("other_fn", &fn_type, None);
+    let entry = fn_value.append_basic_block("entry");
+    let _other_entry = other_fn_value.append_basic_block("entry");
+    let other_block = other_fn_value.append_basic_block("other");
+    let address = other_fn_value.get_block_address(&other_block).unwrap();
+
+    assert!(fn_value.get_block_address(&other_block).is_none());
+
+    builder.position_at_end(&entry);
+    builder.build_indirect_branch(&address, &[&other_block]);
+}
+
+#[test]
+fn test_vector_lanes() {
+    Target::initialize_native(&InitializationConfig::default()).expect("Failed to initialize native target");
+
+    let context = Context::create();
+    let module = context.create_module("vector_lanes");
+    let builder = context.create_builder();
+    let execution_engine = module.create_jit_execution_engine(OptimizationLevel::None).unwrap();
+
+    // Here we're going to create a function which looks roughly like:
+    // fn lane(a: i32, b: i32, index: i32) -> i32 {
+    //     let left = [a, a, a, a];
+    //     let right = [b, b, b, b];
+    //     [left[0], right[1], left[2], right[3]][index]
+    // }
+
+    let i32_type = context.i32_type();
+    let i32_vec_type = i32_type.vec_type(4);
+    let fn_type = i32_type.fn_type(&[&i32_type, &i32_type, &i32_type], false);
+    let fn_value = module.add_function("lane", &fn_type, None);
+    let entry = fn_value.append_basic_block("entry");
+    let a = fn_value.get_nth_param(0).unwrap().into_int_value();
+    let b = fn_value.get_nth_param(1).unwrap().into_int_value();
+    let index = fn_value.get_nth_param(2).unwrap().into_int_value();
+    let i32_zero = i32_type.const_int(0, false);
+    let splat_mask = i32_vec_type.const_null();
+    let mask = VectorType::const_vector(&[
+        i32_type.const_int(0, false),
+        i32_type.const_int(5, false),
+        i32_type.const_int(2, false),
+        i32_type.const_int(7, false),
+    ]);
+
+    builder.position_at_end(&entry);
+
+    let left = builder.build_insert_element(&i32_vec_type.get_undef(), &a, &i32_zero, "left").into_vector_value();
+    let left = builder.build_shuffle_vector(&left, &i32_vec_type.get_undef(), &splat_mask, "left_splat");
+    let right = builder.build_insert_element(&i32_vec_type.get_undef(), &b, &i32_zero, "right").into_vector_value();
+    let right = builder.build_shuffle_vector(&right, &i32_vec_type.get_undef(), &splat_mask, "right_splat");
+    let shuffled = builder.build_shuffle_vector(&left, &right, &mask, "shuffled");
+
+    assert_eq!(shuffled.get_type(), i32_vec_type);
+
+    let lane = builder.build_extract_element(&shuffled, &index, "lane");
+
+    builder.build_return(Some(&lane));
+
+    assert!(fn_value.verify(true));
+
+    unsafe {
+        let lane: Symbol<unsafe extern "C" fn(i32, i32, i32) -> i32> = execution_engine.get_function("lane").unwrap();
+
+        assert_eq!(lane(1, 2, 0), 1);
+        assert_eq!(lane(1, 2, 1), 2);
+        assert_eq!(lane(1, 2, 2), 1);
+        assert_eq!(lane(1, 2, 3), 2);
+    }
+}
+
+#[test]
+#[should_panic]
+fn test_shuffle_vector_non_constant_mask() {
+    let context = Context::create();
+    let module = context.create_module("vector_lanes");
+    let builder = context.create_builder();
+    let i32_type = context.i32_type();
+    let i32_vec_type = i32_type.vec_type(4);
+    let fn_type = i32_vec_type.fn_type(&[&i32_vec_type], false);
+    let fn_value = module.add_function("shuffle", &fn_type, None);
+    let entry = fn_value.append_basic_block("entry");
+    let vector = fn_value.get_first_param().unwrap().into_vector_value();
+
+    builder.position_at_end(&entry);
+    builder.build_shuffle_vector(&vector, &vector, &vector, "shuffled");
+}
+
+#[test]
+#[should_panic]
+fn test_shuffle_vector_non_i32_mask() {
+    let context = Context::create();
+    let module = context.create_module("vector_lanes");
+    let builder = context.create_builder();
+    let i32_type = context.i32_type();
+    let i32_vec_type = i32_type.vec_type(4);
+    let fn_type = i32_vec_type.fn_type(&[&i32_vec_type], false);
+    let fn_value = module.add_function("shuffle", &fn_type, None);
+    let entry = fn_value.append_basic_block("entry");
+    let vector = fn_value.get_first_param().unwrap().into_vector_value();
+    let mask = context.i64_type().vec_type(4).const_null();
+
+    builder.position_at_end(&entry);
+    builder.build_shuffle_vector(&vector, &vector, &mask, "shuffled");
+}
+
+#[test]
+fn test_gep_variants() {
+    Target::initialize_native(&InitializationConfig::default()).expect("Failed to initialize native target");
+
+    let context = Context::create();
+    let module = context.create_module("gep");
+    let builder = context.create_builder();
+    let execution_engine = module.create_jit_execution_engine(OptimizationLevel::None).unwrap();
+
+    // Here we're going to create a function which looks roughly like:
+    // fn sum(pair: &(i32, i64), array: &[i32], index: i64) -> i64 {
+    //     pair.0 as i64 + pair.1 + array[index] as i64
+    // }
+
+    let i32_type = context.i32_type();
+    let i64_type = context.i64_type();
+    let i32_ptr_type = i32_type.ptr_type(AddressSpace::Generic);
+    let struct_type = context.struct_type(&[i32_type.into(), i64_type.into()], false);
+    let struct_ptr_type = struct_type.ptr_type(AddressSpace::Generic);
+    let fn_type = i64_type.fn_type(&[&struct_ptr_type, &i32_ptr_type, &i64_type], false);
+    let fn_value = module.add_function("sum", &fn_type, None);
+    let entry = fn_value.append_basic_block("entry");
+    let pair_ptr = fn_value.get_nth_param(0).unwrap().into_pointer_value();
+    let array_ptr = fn_value.get_nth_param(1).unwrap().into_pointer_value();
+    let index = fn_value.get_nth_param(2).unwrap().into_int_value();
+
+    builder.position_at_end(&entry);
+
+    assert!(builder.build_struct_gep(&pair_ptr, 2, "third_ptr").is_none());
+    assert!(builder.build_struct_gep(&array_ptr, 0, "not_a_struct").is_none());
+
+    let opaque_ptr = context.opaque_struct_type("opaque").ptr_type(AddressSpace::Generic).const_null();
+
+    assert!(builder.build_struct_gep(&opaque_ptr, 0, "opaque_field").is_none());
+
+    let first_ptr = builder.build_struct_gep(&pair_ptr, 0, "first_ptr").unwrap();
+    let second_ptr = builder.build_struct_gep(&pair_ptr, 1, "second_ptr").unwrap();
+    let element_ptr = unsafe { builder.build_in_bounds_gep(&array_ptr, &[index], "element_ptr") };
+
+    assert!(element_ptr.print_to_string().to_string().contains("getelementptr inbounds"));
+
+    let first = builder.build_load(&first_ptr, "first").into_int_value();
+    let first = builder.build_int_s_extend(first, i64_type, "first_ext");
+    let second = builder.build_load(&second_ptr, "second").into_int_value();
+    let element = builder.build_load(&element_ptr, "element").into_int_value();
+    let element = builder.build_int_s_extend(element, i64_type, "element_ext");
+    let sum = builder.build_int_add(first, second, "sum");
+    let sum = builder.build_int_add(sum, element, "sum");
+
+    builder.build_return(Some(&sum));
+
+    assert!(fn_value.verify(true));
+
+    #[repr(C)]
+    struct Pair(i32, i64);
+
+    unsafe {
+        let sum: Symbol<unsafe extern "C" fn(*const Pair, *const i32, i64) -> i64> = execution_engine.get_function("sum").unwrap();
+        let pair = Pair(1, 20);
+        let array = [100, 200, 300];
+
+        assert_eq!(sum(&pair, array.as_ptr(), 0), 121);
+        assert_eq!(sum(&pair, array.as_ptr(), 2), 321);
+    }
+}
+
 #[test]
 fn test_bit_shifts() {
     Target::initialize_native(&InitializationConfig::default()).expect("Failed to initialize native target");
@@ -499,6 +801,80 @@ fn test_vector_convert_ops() {
     assert!(fn_value.verify(true));
 }
 
+#[test]
+fn test_float_int_casts() {
+    Target::initialize_native(&InitializationConfig::default()).expect("Failed to initialize native target");
+
+    let context = Context::create();
+    let module = context.create_module("float_int_casts");
+    let builder = context.create_builder();
+    let execution_engine = module.create_jit_execution_engine(OptimizationLevel::None).unwrap();
+    let i32_type = context.i32_type();
+    let f32_type = context.f32_type();
+    let f64_type = context.f64_type();
+
+    // Casts an f64 through f32, i32, u32 and back, which is lossless for small integral values
+    let fn_type = i32_type.fn_type(&[&f64_type], false);
+    let fn_value = module.add_function("round_trip", &fn_type, None);
+    let entry = fn_value.append_basic_block("entry");
+    let param = fn_value.get_first_param().unwrap().into_float_value();
+
+    builder.position_at_end(&entry);
+
+    let truncated = builder.build_float_trunc(param, f32_type, "truncated");
+    let extended = builder.build_float_ext(truncated, f64_type, "extended");
+    let signed = builder.build_float_to_signed_int(extended, i32_type, "signed");
+    let unsigned_float = builder.build_unsigned_int_to_float(signed, f64_type, "unsigned_float");
+    let unsigned = builder.build_float_to_unsigned_int(unsigned_float, i32_type, "unsigned");
+    let signed_float = builder.build_signed_int_to_float(unsigned, f32_type, "signed_float");
+    let result = builder.build_float_to_signed_int(signed_float, i32_type, "result");
+
+    builder.build_return(Some(&result));
+
+    assert!(fn_value.verify(true));
+
+    unsafe {
+        let round_trip: Symbol<unsafe extern "C" fn(f64) -> i32> = execution_engine.get_function("round_trip").unwrap();
+
+        assert_eq!(round_trip(42.9), 42);
+        assert_eq!(round_trip(-3.5), -3);
+    }
+}
+
+#[test]
+#[should_panic]
+fn test_float_trunc_to_larger_type() {
+    let context = Context::create();
+    let module = context.create_module("float_trunc");
+    let builder = context.create_builder();
+    let f32_type = context.f32_type();
+    let f64_type = context.f64_type();
+    let fn_type = f64_type.fn_type(&[&f32_type], false);
+    let fn_value = module.add_function("trunc", &fn_type, None);
+    let entry = fn_value.append_basic_block("entry");
+    let param = fn_value.get_first_param().unwrap().into_float_value();
+
+    builder.position_at_end(&entry);
+    builder.build_float_trunc(param, f64_type, "truncated");
+}
+
+#[test]
+#[should_panic]
+fn test_float_to_int_vector_size_mismatch() {
+    let context = Context::create();
+    let module = context.create_module("float_to_int");
+    let builder = context.create_builder();
+    let f32_vec_type = context.f32_type().vec_type(2);
+    let i32_vec_type = context.i32_type().vec_type(3);
+    let fn_type = i32_vec_type.fn_type(&[&f32_vec_type], false);
+    let fn_value = module.add_function("float_to_int", &fn_type, None);
+    let entry = fn_value.append_basic_block("entry");
+    let param = fn_value.get_first_param().unwrap().into_vector_value();
+
+    builder.position_at_end(&entry);
+    builder.build_float_to_signed_int(param, i32_vec_type, "casted");
+}
+
 #[test]
 fn test_vector_binary_ops() {
     let context = Context::create();
@@ -577,3 +953,454 @@ fn test_vector_pointer_ops() {
     builder.build_return(Some(&is_null_vec));
     assert!(fn_value.verify(true));
 }
+
+#[test]
+fn test_atomics() {
+    Target::initialize_native(&InitializationConfig::default()).expect("Failed to initialize native target");
+
+    let context = Context::create();
+    let module = context.create_module("atomics");
+    let builder = context.create_builder();
+    let i32_type = context.i32_type();
+    let i32_ptr_type = i32_type.ptr_type(AddressSpace::Generic);
+
+    // fn fetch_max(ptr: *mut i32, val: i32) -> i32
+    let fn_type = i32_type.fn_type(&[&i32_ptr_type, &i32_type], false);
+    let fetch_max_fn = module.add_function("fetch_max", &fn_type, None);
+    let entry = fetch_max_fn.append_basic_block("entry");
+
+    builder.position_at_end(&entry);
+
+    let ptr = fetch_max_fn.get_first_param().unwrap().into_pointer_value();
+    let value = fetch_max_fn.get_last_param().unwrap().into_int_value();
+    let previous = builder.build_atomic_rmw(AtomicRMWBinOp::Max, &ptr, &value, AtomicOrdering::SequentiallyConsistent);
+
+    assert_eq!(previous.get_type(), i32_type);
+
+    builder.build_return(Some(&previous));
+
+    // fn compare_exchange(ptr: *mut i32, cmp: i32, new: i32) -> i32, returning whether or not
+    // the exchange succeeded
+    let fn_type = i32_type.fn_type(&[&i32_ptr_type, &i32_type, &i32_type], false);
+    let cmpxchg_fn = module.add_function("compare_exchange", &fn_type, None);
+    let entry = cmpxchg_fn.append_basic_block("entry");
+
+    builder.position_at_end(&entry);
+
+    let ptr = cmpxchg_fn.get_nth_param(0).unwrap().into_pointer_value();
+    let cmp = cmpxchg_fn.get_nth_param(1).unwrap().into_int_value();
+    let new = cmpxchg_fn.get_nth_param(2).unwrap().into_int_value();
+    let result = builder.build_cmpxchg(&ptr, &cmp, &new, AtomicOrdering::AcquireRelease, AtomicOrdering::Acquire);
+    let old = builder.build_extract_value(&result, 0, "old").unwrap().into_int_value();
+    let success = builder.build_extract_value(&result, 1, "success").unwrap().into_int_value();
+    let ret = builder.build_int_z_extend(success, i32_type, "ret");
+
+    assert_eq!(old.get_type(), i32_type);
+    assert_eq!(success.get_type(), context.bool_type());
+
+    builder.build_return(Some(&ret));
+
+    assert!(module.verify().is_ok());
+
+    let execution_engine = module.create_jit_execution_engine(OptimizationLevel::None).unwrap();
+
+    unsafe {
+        let fetch_max: Symbol<unsafe extern "C" fn(*mut i32, i32) -> i32> = execution_engine.get_function("fetch_max").unwrap();
+        let compare_exchange: Symbol<unsafe extern "C" fn(*mut i32, i32, i32) -> i32> = execution_engine.get_function("compare_exchange").unwrap();
+        let mut value = 5;
+
+        assert_eq!(fetch_max(&mut value, 3), 5);
+        assert_eq!(value, 5);
+        assert_eq!(fetch_max(&mut value, 8), 5);
+        assert_eq!(value, 8);
+
+        assert_eq!(compare_exchange(&mut value, 7, 1), 0);
+        assert_eq!(value, 8);
+        assert_eq!(compare_exchange(&mut value, 8, 1), 1);
+        assert_eq!(value, 1);
+    }
+}
+
+#[test]
+fn test_fence() {
+    let context = Context::create();
+    let module = context.create_module("fences");
+    let builder = context.create_builder();
+    let fn_type = context.void_type().fn_type(&[], false);
+    let fn_value = module.add_function("barriers", &fn_type, None);
+    let entry = fn_value.append_basic_block("entry");
+
+    builder.position_at_end(&entry);
+
+    let acquire = builder.build_fence(AtomicOrdering::Acquire, false, "");
+    let release = builder.build_fence(AtomicOrdering::Release, true, "");
+
+    builder.build_fence(AtomicOrdering::SequentiallyConsistent, false, "");
+    builder.build_return(None);
+
+    assert!(module.verify().is_ok());
+    assert_eq!(acquire.get_opcode(), InstructionOpcode::Fence);
+    assert_eq!(release.get_opcode(), InstructionOpcode::Fence);
+
+    let ir = module.print_to_string().to_string();
+
+    assert!(ir.contains("fence acquire"));
+    assert!(ir.contains("fence syncscope(\"singlethread\") release"));
+    assert!(ir.contains("fence seq_cst"));
+}
+
+#[test]
+fn test_insert_extract_value() {
+    Target::initialize_native(&InitializationConfig::default()).expect("Failed to initialize native target");
+
+    let context = Context::create();
+    let module = context.create_module("aggregates");
+    let builder = context.create_builder();
+    let i32_type = context.i32_type();
+    let i64_type = context.i64_type();
+    let struct_type = context.struct_type(&[i32_type.into(), i64_type.into()], false);
+    let array_type = i32_type.array_type(3);
+
+    // fn sum(a: i32, b: i32) -> i32, which goes through a struct and an array
+    let fn_type = i32_type.fn_type(&[&i32_type, &i32_type], false);
+    let fn_value = module.add_function("sum", &fn_type, None);
+    let entry = fn_value.append_basic_block("entry");
+
+    builder.position_at_end(&entry);
+
+    let a = fn_value.get_first_param().unwrap();
+    let b = fn_value.get_last_param().unwrap();
+    let struct_value = builder.build_insert_value(&struct_type.get_undef(), &a, 0, "struct").unwrap();
+
+    assert!(struct_value.is_struct_value());
+    assert!(builder.build_insert_value(&struct_type.get_undef(), &a, 2, "struct").is_none());
+    assert!(builder.build_extract_value(&struct_value, 2, "oob").is_none());
+
+    let array_value = builder.build_insert_value(&array_type.const_null(), &b, 2, "array").unwrap();
+
+    assert!(array_value.is_array_value());
+    assert!(builder.build_insert_value(&array_value, &b, 3, "array").is_none());
+    assert!(builder.build_extract_value(&array_value, 3, "oob").is_none());
+
+    let a = builder.build_extract_value(&struct_value, 0, "a").unwrap().into_int_value();
+    let b = builder.build_extract_value(&array_value, 2, "b").unwrap().into_int_value();
+    let zero = builder.build_extract_value(&array_value, 0, "zero").unwrap().into_int_value();
+
+    assert_eq!(builder.build_extract_value(&struct_value, 1, "undef").unwrap().get_type(), i64_type.into());
+
+    let sum = builder.build_int_add(a, b, "sum");
+    let sum = builder.build_int_add(sum, zero, "sum");
+
+    builder.build_return(Some(&sum));
+
+    assert!(module.verify().is_ok());
+
+    let execution_engine = module.create_jit_execution_engine(OptimizationLevel::None).unwrap();
+
+    unsafe {
+        let sum: Symbol<unsafe extern "C" fn(i32, i32) -> i32> = execution_engine.get_function("sum").unwrap();
+
+        assert_eq!(sum(2, 3), 5);
+        assert_eq!(sum(-7, 4), -3);
+    }
+}
+
+#[cfg(target_arch = "x86_64")]
+#[test]
+fn test_inline_asm() {
+    Target::initialize_native(&InitializationConfig::default()).expect("Failed to initialize native target");
+
+    let context = Context::create();
+    let module = context.create_module("inline_asm");
+    let builder = context.create_builder();
+    let execution_engine = module.create_jit_execution_engine(OptimizationLevel::None).unwrap();
+
+    let i64_type = context.i64_type();
+    let fn_type = i64_type.fn_type(&[&i64_type], false);
+    let fn_value = module.add_function("add_one", &fn_type, None);
+    let entry = fn_value.append_basic_block("entry");
+    let asm = context.create_inline_asm(&fn_type, "lea 1($1), $0", "=r,r", false, false);
+    let param = fn_value.get_first_param().unwrap();
+
+    builder.position_at_end(&entry);
+
+    let result = builder.build_indirect_call(&asm, &[&param], "result", false).left().unwrap();
+
+    builder.build_return(Some(&result));
+
+    assert!(fn_value.verify(true));
+
+    unsafe {
+        let add_one: Symbol<unsafe extern "C" fn(u64) -> u64> = execution_engine.get_function("add_one").unwrap();
+
+        assert_eq!(add_one(41), 42);
+    }
+}
+
+#[test]
+#[should_panic]
+fn test_indirect_call_non_function_pointer() {
+    let context = Context::create();
+    let module = context.create_module("indirect_call");
+    let builder = context.create_builder();
+    let void_type = context.void_type();
+    let i32_type = context.i32_type();
+    let fn_type = void_type.fn_type(&[], false);
+    let fn_value = module.add_function("my_fn", &fn_type, None);
+    let entry = fn_value.append_basic_block("entry");
+
+    builder.position_at_end(&entry);
+
+    let ptr = builder.build_alloca(i32_type, "ptr");
+
+    builder.build_indirect_call(&ptr, &[], "", false);
+}
+
+#[test]
+fn test_select() {
+    Target::initialize_native(&InitializationConfig::default()).expect("Failed to initialize native target");
+
+    let context = Context::create();
+    let module = context.create_module("select");
+    let builder = context.create_builder();
+    let execution_engine = module.create_jit_execution_engine(OptimizationLevel::None).unwrap();
+    let i32_type = context.i32_type();
+    let bool_type = context.bool_type();
+
+    // fn choose(cond: bool, a: i32, b: i32) -> i32 { if cond { a } else { b } }
+    let fn_type = i32_type.fn_type(&[&bool_type, &i32_type, &i32_type], false);
+    let fn_value = module.add_function("choose", &fn_type, None);
+    let entry = fn_value.append_basic_block("entry");
+    let cond = fn_value.get_nth_param(0).unwrap().into_int_value();
+    let a = fn_value.get_nth_param(1).unwrap();
+    let b = fn_value.get_nth_param(2).unwrap();
+
+    builder.position_at_end(&entry);
+
+    let select = builder.build_select(cond, &a, &b, "select");
+
+    assert_eq!(select.as_instruction().unwrap().get_opcode(), InstructionOpcode::Select);
+
+    builder.build_return(Some(&select));
+
+    assert!(fn_value.verify(true));
+
+    // Vector select, choosing each element individually
+    let i32_vec_type = i32_type.vec_type(2);
+    let bool_vec_type = bool_type.vec_type(2);
+    let fn_type = i32_vec_type.fn_type(&[&bool_vec_type, &i32_vec_type, &i32_vec_type], false);
+    let vec_fn_value = module.add_function("choose_vec", &fn_type, None);
+    let entry = vec_fn_value.append_basic_block("entry");
+    let cond = vec_fn_value.get_nth_param(0).unwrap().into_vector_value();
+    let a = vec_fn_value.get_nth_param(1).unwrap();
+    let b = vec_fn_value.get_nth_param(2).unwrap();
+
+    builder.position_at_end(&entry);
+
+    let select = builder.build_select(cond, &a, &b, "select");
+
+    builder.build_return(Some(&select));
+
+    assert!(vec_fn_value.verify(true));
+
+    unsafe {
+        let choose: Symbol<unsafe extern "C" fn(bool, i32, i32) -> i32> = execution_engine.get_function("choose").unwrap();
+
+        assert_eq!(choose(true, 1, 2), 1);
+        assert_eq!(choose(false, 1, 2), 2);
+    }
+}
+
+#[test]
+#[should_panic]
+fn test_select_non_bool_condition() {
+    let context = Context::create();
+    let module = context.create_module("select");
+    let builder = context.create_builder();
+    let i32_type = context.i32_type();
+    let fn_type = i32_type.fn_type(&[&i32_type], false);
+    let fn_value = module.add_function("select", &fn_type, None);
+    let entry = fn_value.append_basic_block("entry");
+    let param = fn_value.get_first_param().unwrap().into_int_value();
+
+    builder.position_at_end(&entry);
+    builder.build_select(param, &param, &param, "select");
+}
+
+#[test]
+#[should_panic]
+fn test_select_mismatched_values() {
+    let context = Context::create();
+    let module = context.create_module("select");
+    let builder = context.create_builder();
+    let bool_type = context.bool_type();
+    let i32_type = context.i32_type();
+    let i64_type = context.i64_type();
+    let fn_type = i32_type.fn_type(&[&bool_type], false);
+    let fn_value = module.add_function("select", &fn_type, None);
+    let entry = fn_value.append_basic_block("entry");
+    let cond = fn_value.get_first_param().unwrap().into_int_value();
+
+    builder.position_at_end(&entry);
+    builder.build_select(cond, &i32_type.const_int(1, false), &i64_type.const_int(2, false), "select");
+}
+
+#[test]
+fn test_entry_alloca() {
+    let context = Context::create();
+    let module = context.create_module("entry_alloca");
+    let builder = context.create_builder();
+    let i32_type = context.i32_type();
+    let fn_type = context.void_type().fn_type(&[], false);
+    let fn_value = module.add_function("my_fn", &fn_type, None);
+    let entry = fn_value.append_basic_block("entry");
+    let body = fn_value.append_basic_block("body");
+
+    // The entry block is still empty here
+    builder.position_at_end(&body);
+
+    let first = builder.build_entry_alloca(i32_type, "first");
+
+    assert_eq!(entry.get_first_instruction(), first.as_instruction());
+    assert_eq!(builder.get_insert_block().as_ref(), Some(&body));
+
+    builder.position_at_end(&entry);
+
+    let branch = builder.build_unconditional_branch(&body);
+
+    // Positioned before an instruction, which must stay where it is
+    builder.position_before(&branch);
+
+    let second = builder.build_entry_alloca(i32_type, "second");
+    let store = builder.build_store(&second, &i32_type.const_int(1, false));
+
+    assert_eq!(entry.get_first_instruction(), second.as_instruction());
+    assert_eq!(first.as_instruction().unwrap().get_next_instruction(), Some(store));
+    assert_eq!(store.get_next_instruction(), Some(branch));
+
+    builder.position_at_end(&body);
+    builder.build_return(None);
+
+    assert!(fn_value.verify(true));
+}
+
+#[test]
+#[should_panic]
+fn test_entry_alloca_unpositioned() {
+    let context = Context::create();
+    let builder = context.create_builder();
+
+    builder.build_entry_alloca(context.i32_type(), "alloca");
+}
+
+#[test]
+fn test_malloc_and_free() {
+    Target::initialize_native(&InitializationConfig::default()).expect("Failed to initialize native target");
+
+    let context = Context::create();
+    let module = context.create_module("malloc");
+    let builder = context.create_builder();
+    let execution_engine = module.create_jit_execution_engine(OptimizationLevel::None).unwrap();
+    let i64_type = context.i64_type();
+
+    // Allocates n u64s on both the heap and the stack, writes to the last element of each and sums them
+    let fn_type = i64_type.fn_type(&[&i64_type], false);
+    let fn_value = module.add_function("last_of_both", &fn_type, None);
+    let entry = fn_value.append_basic_block("entry");
+    let len = fn_value.get_first_param().unwrap().into_int_value();
+    let one = i64_type.const_int(1, false);
+
+    builder.position_at_end(&entry);
+
+    let heap_array = builder.build_array_malloc(i64_type, len, "heap_array");
+    let stack_array = builder.build_array_alloca(i64_type, len, "stack_array");
+    let last_index = builder.build_int_sub(len, one, "last_index");
+    let heap_last = unsafe { builder.build_gep(&heap_array, &[last_index], "heap_last") };
+    let stack_last = unsafe { builder.build_gep(&stack_array, &[last_index], "stack_last") };
+
+    builder.build_store(&heap_last, &len);
+    builder.build_store(&stack_last, &one);
+
+    let heap_value = builder.build_load(&heap_last, "heap_value").into_int_value();
+    let stack_value = builder.build_load(&stack_last, "stack_value").into_int_value();
+    let sum = builder.build_int_add(heap_value, stack_value, "sum");
+
+    builder.build_free(&heap_array);
+    builder.build_return(Some(&sum));
+
+    assert!(fn_value.verify(true));
+
+    unsafe {
+        let last_of_both: Symbol<unsafe extern "C" fn(u64) -> u64> = execution_engine.get_function("last_of_both").unwrap();
+
+        assert_eq!(last_of_both(4), 5);
+        assert_eq!(last_of_both(100), 101);
+    }
+}
+
+#[test]
+#[should_panic]
+fn test_malloc_unpositioned() {
+    let context = Context::create();
+    let builder = context.create_builder();
+
+    builder.build_malloc(context.i64_type(), "malloc");
+}
+
+#[test]
+#[should_panic]
+fn test_malloc_unsized_type() {
+    let context = Context::create();
+    let module = context.create_module("malloc");
+    let builder = context.create_builder();
+    let opaque_type = context.opaque_struct_type("opaque");
+    let fn_type = context.void_type().fn_type(&[], false);
+    let fn_value = module.add_function("my_fn", &fn_type, None);
+    let entry = fn_value.append_basic_block("entry");
+
+    builder.position_at_end(&entry);
+    builder.build_malloc(opaque_type, "malloc");
+}
+
+#[test]
+fn test_global_string_ptr() {
+    Target::initialize_native(&InitializationConfig::default()).expect("Failed to initialize native target");
+
+    let context = Context::create();
+    let module = context.create_module("global_string");
+    let builder = context.create_builder();
+    let i8_ptr_type = context.i8_type().ptr_type(AddressSpace::Generic);
+    let fn_type = i8_ptr_type.fn_type(&[], false);
+    let fn_value = module.add_function("get_greeting", &fn_type, None);
+    let entry = fn_value.append_basic_block("entry");
+
+    builder.position_at_end(&entry);
+
+    let greeting = builder.build_global_string_ptr("Hello, World!", "greeting");
+
+    builder.build_return(Some(&greeting));
+
+    let global = module.get_global("greeting").unwrap();
+
+    assert!(global.is_constant());
+    assert_ne!(greeting, global.as_pointer_value());
+    assert!(module.print_to_string().to_string().contains("@greeting = private unnamed_addr constant [14 x i8] c\"Hello, World!\\00\""));
+    assert!(module.verify().is_ok());
+
+    let execution_engine = module.create_jit_execution_engine(OptimizationLevel::None).unwrap();
+
+    unsafe {
+        let get_greeting: Symbol<unsafe extern "C" fn() -> *const i8> = execution_engine.get_function("get_greeting").unwrap();
+
+        assert_eq!(CStr::from_ptr(get_greeting()).to_str(), Ok("Hello, World!"));
+    }
+}
+
+#[test]
+#[should_panic(expected = "Builder must be positioned in a BasicBlock to build a global string")]
+fn test_global_string_ptr_unpositioned() {
+    let context = Context::create();
+    let builder = context.create_builder();
+
+    builder.build_global_string_ptr("Hello, World!", "greeting");
+}
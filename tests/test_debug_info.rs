@@ -0,0 +1,60 @@
+extern crate inkwell;
+
+use self::inkwell::context::Context;
+use self::inkwell::debug_info::{DWARFEmissionKind, DWARFSourceLanguage, debug_metadata_version};
+
+#[test]
+fn test_compile_unit() {
+    let context = Context::create();
+    let module = context.create_module("my_module");
+    let debug_info_builder = module.create_debug_info_builder(true);
+
+    assert!(debug_metadata_version() > 0);
+    assert_eq!(module.get_debug_metadata_version(), 0);
+
+    let file = debug_info_builder.create_file("main.rs", "/home/user/project");
+    let compile_unit = debug_info_builder.create_compile_unit(DWARFSourceLanguage::Rust, &file, "my_compiler", false, "", 0, "", DWARFEmissionKind::Full, 0, false, false);
+
+    assert_ne!(file.as_debug_info_scope(), compile_unit.as_debug_info_scope());
+    assert_eq!(module.get_debug_metadata_version(), debug_metadata_version());
+
+    debug_info_builder.finalize();
+    debug_info_builder.finalize();
+
+    let ir = module.print_to_string().to_string();
+
+    assert!(ir.contains("!llvm.dbg.cu"));
+    assert!(ir.contains("DW_LANG_Rust"));
+    assert!(ir.contains("!DIFile(filename: \"main.rs\", directory: \"/home/user/project\")"));
+    assert!(ir.contains("!llvm.module.flags"));
+    assert!(ir.contains("!\"Debug Info Version\""));
+
+    assert!(module.strip_debug_info());
+    assert!(!module.print_to_string().to_string().contains("!llvm.dbg.cu"));
+}
+
+#[test]
+fn test_current_debug_location() {
+    let context = Context::create();
+    let module = context.create_module("my_module");
+    let builder = context.create_builder();
+    let debug_info_builder = module.create_debug_info_builder(true);
+    let file = debug_info_builder.create_file("main.rs", "/home/user/project");
+    let compile_unit = debug_info_builder.create_compile_unit(DWARFSourceLanguage::Rust, &file, "my_compiler", false, "", 0, "", DWARFEmissionKind::LineTablesOnly, 0, false, false);
+    let location = debug_info_builder.create_debug_location(3, 14, &compile_unit.as_debug_info_scope(), None);
+    let inlined_location = debug_info_builder.create_debug_location(15, 9, &compile_unit.as_debug_info_scope(), Some(&location));
+
+    assert!(builder.get_current_debug_location().is_none());
+
+    builder.set_current_debug_location(&location);
+
+    assert_eq!(builder.get_current_debug_location(), Some(location));
+
+    builder.set_current_debug_location(&inlined_location);
+
+    assert_eq!(builder.get_current_debug_location(), Some(inlined_location));
+
+    builder.unset_current_debug_location();
+
+    assert!(builder.get_current_debug_location().is_none());
+}
@@ -1,11 +1,18 @@
 extern crate inkwell;
 
-use self::inkwell::{AddressSpace, OptimizationLevel, IntPredicate};
+use self::inkwell::{AddressSpace, CallConv, OptimizationLevel, IntPredicate};
 use self::inkwell::context::Context;
-use self::inkwell::execution_engine::{ExecutionEngine, FunctionLookupError};
-use self::inkwell::targets::{InitializationConfig, Target};
+use self::inkwell::module::Linkage;
+use self::inkwell::memory_buffer::MemoryBuffer;
+use self::inkwell::execution_engine::{AddModuleError, ExecutionEngine, ExecutionEngineCreationError, FunctionLookupError, JitFunctionError, MCJITMemoryManager, RemoveModuleError, RunFunctionError};
+use self::inkwell::targets::{CodeModel, InitializationConfig, Target};
+use self::inkwell::values::GenericValue;
 
+use std::cell::RefCell;
+use std::error::Error;
 use std::ffi::CString;
+use std::mem::{align_of, size_of};
+use std::rc::Rc;
 
 type Thunk = unsafe extern "C" fn();
 
@@ -45,6 +52,50 @@ fn test_get_function_address() {
             FunctionLookupError::FunctionNotFound);
 
         assert!(execution_engine.get_function::<Thunk>("func").is_ok());
+
+        assert_eq!(execution_engine.get_function::<unsafe extern "C" fn(u32)>("func").unwrap_err(),
+            FunctionLookupError::SignatureMismatch { expected: 0, found: 1 });
+
+        assert_eq!(execution_engine.get_function_address("errors"), Err(FunctionLookupError::FunctionNotFound));
+
+        let address = execution_engine.get_function_address("func").unwrap();
+
+        assert_ne!(address, 0);
+        assert_eq!(execution_engine.get_function::<Thunk>("func").unwrap().as_raw(), address);
+    }
+
+    let module = context.create_module("var_args");
+    let i32_type = context.i32_type();
+    let fn_value = module.add_function("var_args", &void_type.fn_type(&[&i32_type], true), None);
+    let basic_block = context.append_basic_block(&fn_value, "entry");
+
+    builder.position_at_end(&basic_block);
+    builder.build_return(None);
+
+    let execution_engine = module.create_jit_execution_engine(OptimizationLevel::None).unwrap();
+
+    unsafe {
+        assert_eq!(execution_engine.get_function::<Thunk>("var_args").unwrap_err(),
+            FunctionLookupError::SignatureMismatch { expected: 1, found: 0 });
+        assert!(execution_engine.get_function::<unsafe extern "C" fn(u32)>("var_args").is_ok());
+        assert!(execution_engine.get_function::<unsafe extern "C" fn(u32, u64)>("var_args").is_ok());
+    }
+
+    let module = context.create_module("call_conv");
+    let fn_value = module.add_function("fast", &fn_type, None);
+    let basic_block = context.append_basic_block(&fn_value, "entry");
+
+    fn_value.set_call_conventions(CallConv::Fast as u32);
+
+    builder.position_at_end(&basic_block);
+    builder.build_return(None);
+
+    let execution_engine = module.create_jit_execution_engine(OptimizationLevel::None).unwrap();
+
+    unsafe {
+        assert_eq!(execution_engine.get_function::<Thunk>("fast").unwrap_err(),
+            FunctionLookupError::CallConvMismatch { found: CallConv::Fast as u32 });
+        assert!(execution_engine.get_function_address("fast").is_ok());
     }
 }
 
@@ -94,16 +145,18 @@ fn test_jit_execution_engine() {
 
     let execution_engine = module.create_jit_execution_engine(OptimizationLevel::None).expect("Could not create Execution Engine");
 
+    assert!(execution_engine.is_jit());
+
     let main = execution_engine.get_function_value("main").expect("Could not find main in ExecutionEngine");
 
     let ret = unsafe {
-        execution_engine.run_function_as_main(&main, &["input", "bar"])
+        execution_engine.run_function_as_main(&main, &["input", "bar"]).unwrap()
     };
 
     assert_eq!(ret, 1, "unexpected main return code: {}", ret);
 
     let ret = unsafe {
-        execution_engine.run_function_as_main(&main, &["input", "bar", "baz"])
+        execution_engine.run_function_as_main(&main, &["input", "bar", "baz"]).unwrap()
     };
 
     assert_eq!(ret, 42, "unexpected main return code: {}", ret);
@@ -118,6 +171,221 @@ fn test_jit_execution_engine() {
 //     let ee = module.create_jit_execution_engine(OptimizationLevel::None); // Segfault?
 // }
 
+#[test]
+fn test_jit_execution_engine_with_options() {
+    Target::initialize_native(&InitializationConfig::default()).expect("Failed to initialize native target");
+
+    let context = Context::create();
+    let module = context.create_module("test");
+    let builder = context.create_builder();
+    let i64_type = context.i64_type();
+    let fn_type = i64_type.fn_type(&[&i64_type, &i64_type], false);
+    let fn_value = module.add_function("add", &fn_type, None);
+    let entry = context.append_basic_block(&fn_value, "entry");
+
+    builder.position_at_end(&entry);
+
+    let lhs = fn_value.get_first_param().unwrap().into_int_value();
+    let rhs = fn_value.get_last_param().unwrap().into_int_value();
+    let sum = builder.build_int_add(lhs, rhs, "sum");
+
+    builder.build_return(Some(&sum));
+
+    let execution_engine = module.jit_engine_builder()
+        .opt_level(OptimizationLevel::Aggressive)
+        .code_model(CodeModel::Large)
+        .no_frame_pointer_elim(true)
+        .enable_fast_isel(true)
+        .verify_module(true)
+        .create()
+        .unwrap();
+
+    assert!(execution_engine.is_jit());
+
+    unsafe {
+        let add = execution_engine.get_function::<unsafe extern "C" fn(u64, u64) -> u64>("add").unwrap();
+
+        assert_eq!(add(40, 2), 42);
+    }
+}
+
+#[derive(Debug, Default)]
+struct MemoryManagerStats {
+    code_sections: u32,
+    data_sections: u32,
+    finalized: bool,
+    dropped: bool,
+    // Start and end addresses of every allocation
+    ranges: Vec<(usize, usize)>,
+}
+
+// Hands out plain heap memory, which is enough as long as the compiled code is never run
+struct HeapMemoryManager {
+    buffers: Vec<Vec<u8>>,
+    stats: Rc<RefCell<MemoryManagerStats>>,
+}
+
+impl HeapMemoryManager {
+    fn allocate(&mut self, size: usize, alignment: u32) -> *mut u8 {
+        let alignment = alignment.max(1) as usize;
+        let mut buffer = vec![0u8; size + alignment];
+        let offset = (alignment - buffer.as_ptr() as usize % alignment) % alignment;
+        let ptr = unsafe { buffer.as_mut_ptr().offset(offset as isize) };
+
+        self.stats.borrow_mut().ranges.push((ptr as usize, ptr as usize + size));
+        self.buffers.push(buffer);
+
+        ptr
+    }
+}
+
+impl MCJITMemoryManager for HeapMemoryManager {
+    fn allocate_code_section(&mut self, size: usize, alignment: u32, _section_id: u32, _section_name: &str) -> *mut u8 {
+        self.stats.borrow_mut().code_sections += 1;
+        self.allocate(size, alignment)
+    }
+
+    fn allocate_data_section(&mut self, size: usize, alignment: u32, _section_id: u32, _section_name: &str, _is_read_only: bool) -> *mut u8 {
+        self.stats.borrow_mut().data_sections += 1;
+        self.allocate(size, alignment)
+    }
+
+    fn finalize_memory(&mut self) -> Result<(), String> {
+        self.stats.borrow_mut().finalized = true;
+
+        Ok(())
+    }
+}
+
+impl Drop for HeapMemoryManager {
+    fn drop(&mut self) {
+        self.stats.borrow_mut().dropped = true;
+    }
+}
+
+#[test]
+fn test_jit_execution_engine_with_memory_manager() {
+    Target::initialize_native(&InitializationConfig::default()).expect("Failed to initialize native target");
+
+    let context = Context::create();
+    let module = context.create_module("test");
+    let builder = context.create_builder();
+    let i64_type = context.i64_type();
+    let global = module.add_global(&i64_type, None, "answer");
+
+    global.set_initializer(&i64_type.const_int(42, false));
+
+    let fn_type = i64_type.fn_type(&[], false);
+    let fn_value = module.add_function("get_answer", &fn_type, None);
+    let entry = context.append_basic_block(&fn_value, "entry");
+
+    builder.position_at_end(&entry);
+
+    let answer = builder.build_load(&global.as_pointer_value(), "answer");
+
+    builder.build_return(Some(&answer));
+
+    let stats = Rc::new(RefCell::new(MemoryManagerStats::default()));
+    let memory_manager = HeapMemoryManager {
+        buffers: Vec::new(),
+        stats: stats.clone(),
+    };
+    let execution_engine = module.jit_engine_builder().memory_manager(memory_manager).create().unwrap();
+    let address = unsafe { execution_engine.get_global_address("answer") }.unwrap();
+
+    {
+        let stats = stats.borrow();
+
+        assert!(stats.code_sections > 0);
+        assert!(stats.data_sections > 0);
+        assert!(stats.finalized);
+        assert!(!stats.dropped);
+        assert!(stats.ranges.iter().any(|&(start, end)| start <= address && address < end));
+    }
+
+    assert_eq!(unsafe { *(address as *const i64) }, 42);
+
+    drop(execution_engine);
+    drop(module);
+
+    assert!(stats.borrow().dropped);
+}
+
+struct PanickingMemoryManager(HeapMemoryManager);
+
+impl MCJITMemoryManager for PanickingMemoryManager {
+    fn allocate_code_section(&mut self, size: usize, alignment: u32, section_id: u32, section_name: &str) -> *mut u8 {
+        self.0.allocate_code_section(size, alignment, section_id, section_name)
+    }
+
+    fn allocate_data_section(&mut self, size: usize, alignment: u32, section_id: u32, section_name: &str, is_read_only: bool) -> *mut u8 {
+        self.0.allocate_data_section(size, alignment, section_id, section_name, is_read_only)
+    }
+
+    fn finalize_memory(&mut self) -> Result<(), String> {
+        panic!("finalize_memory failed")
+    }
+}
+
+#[test]
+fn test_jit_execution_engine_with_panicking_memory_manager() {
+    Target::initialize_native(&InitializationConfig::default()).expect("Failed to initialize native target");
+
+    let context = Context::create();
+    let module = context.create_module("test");
+    let i64_type = context.i64_type();
+    let global = module.add_global(&i64_type, None, "answer");
+
+    global.set_initializer(&i64_type.const_int(42, false));
+
+    let stats = Rc::new(RefCell::new(MemoryManagerStats::default()));
+    let memory_manager = PanickingMemoryManager(HeapMemoryManager {
+        buffers: Vec::new(),
+        stats: stats.clone(),
+    });
+    let execution_engine = module.jit_engine_builder().memory_manager(memory_manager).create().unwrap();
+
+    // The panic is caught rather than unwinding into LLVM, which ignores finalization failures
+    let address = unsafe { execution_engine.get_global_address("answer") }.unwrap();
+
+    assert_eq!(unsafe { *(address as *const i64) }, 42);
+}
+
+#[test]
+fn test_jit_engine_builder_drops_unused_memory_manager() {
+    let context = Context::create();
+    let module = context.create_module("test");
+    let stats = Rc::new(RefCell::new(MemoryManagerStats::default()));
+    let memory_manager = HeapMemoryManager {
+        buffers: Vec::new(),
+        stats: stats.clone(),
+    };
+
+    drop(module.jit_engine_builder().memory_manager(memory_manager));
+
+    assert!(stats.borrow().dropped);
+}
+
+#[test]
+fn test_jit_execution_engine_verifies_module() {
+    Target::initialize_native(&InitializationConfig::default()).expect("Failed to initialize native target");
+
+    let context = Context::create();
+    let module = context.create_module("test");
+    let fn_type = context.void_type().fn_type(&[], false);
+    let fn_value = module.add_function("no_terminator", &fn_type, None);
+
+    context.append_basic_block(&fn_value, "entry");
+
+    match module.jit_engine_builder().verify_module(true).create() {
+        Err(ExecutionEngineCreationError::InvalidModule(string)) => assert!(string.to_string().contains("no_terminator")),
+        result => panic!("Expected an InvalidModule error, found {:?}", result),
+    }
+
+    // The module shouldn't have been handed over to an EE
+    assert!(context.create_module("test2").create_interpreter_execution_engine().unwrap().add_module(&module).is_ok());
+}
+
 #[test]
 fn test_execution_engine() {
     let context = Context::create();
@@ -135,9 +403,44 @@ fn test_interpreter_execution_engine() {
 
     Target::initialize_native(&InitializationConfig::default()).expect("Failed to initialize native target");
 
-    assert!(module.create_interpreter_execution_engine().is_ok());
+    let execution_engine = module.create_interpreter_execution_engine().unwrap();
+
+    assert!(!execution_engine.is_jit());
+    assert_eq!(execution_engine.get_function_value("main"), Err(FunctionLookupError::JITNotEnabled));
+}
+
+#[test]
+fn test_get_function_definition() {
+    Target::initialize_native(&InitializationConfig::default()).expect("Failed to initialize native target");
+
+    let context = Context::create();
+    let module = context.create_module("main_module");
+    let builder = context.create_builder();
+    let fn_type = context.void_type().fn_type(&[], false);
+    let defined_fn = module.add_function("defined", &fn_type, None);
+    let entry = context.append_basic_block(&defined_fn, "entry");
+
+    builder.position_at_end(&entry);
+    builder.build_return(None);
+
+    module.add_function("declared", &fn_type, None);
+
+    let execution_engine = module.create_jit_execution_engine(OptimizationLevel::None).unwrap();
+
+    assert_eq!(execution_engine.get_function_definition("defined"), Ok(defined_fn));
+    assert_eq!(execution_engine.get_function_definition("declared"), Err(FunctionLookupError::DeclarationOnly));
+    assert_eq!(execution_engine.get_function_definition("missing"), Err(FunctionLookupError::FunctionNotFound));
 }
 
+#[test]
+fn test_execution_engine_equality() {
+    let context = Context::create();
+    let execution_engine = context.create_module("test").create_interpreter_execution_engine().unwrap();
+    let execution_engine2 = context.create_module("test2").create_interpreter_execution_engine().unwrap();
+
+    assert_eq!(execution_engine, execution_engine.clone());
+    assert_ne!(execution_engine, execution_engine2);
+}
 
 #[test]
 fn test_add_remove_module() {
@@ -147,7 +450,7 @@ fn test_add_remove_module() {
     let module = context.create_module("test");
     let ee = module.create_jit_execution_engine(OptimizationLevel::default()).unwrap();
 
-    assert!(ee.add_module(&module).is_err());
+    assert_eq!(ee.add_module(&module), Err(AddModuleError::ModuleAlreadyOwned));
 
     let module2 = context.create_module("mod2");
 
@@ -155,6 +458,937 @@ fn test_add_remove_module() {
     assert!(ee.add_module(&module2).is_ok());
     assert!(ee.remove_module(&module).is_ok());
     assert!(ee.remove_module(&module2).is_ok());
+
+    let module3 = context.create_module("mod3");
+    let ee2 = context.create_module("mod4").create_jit_execution_engine(OptimizationLevel::default()).unwrap();
+
+    assert!(ee.add_module(&module3).is_ok());
+    assert_eq!(ee2.add_module(&module3), Err(AddModuleError::ModuleAlreadyOwned));
+    assert_eq!(ee2.modules().count(), 1);
+    assert!(ee.remove_module(&module3).is_ok());
+}
+
+#[test]
+fn test_remove_module_by_name() {
+    Target::initialize_native(&InitializationConfig::default()).expect("Failed to initialize native target");
+
+    let context = Context::create();
+    let builder = context.create_builder();
+    let ee = context.create_module("main").create_jit_execution_engine(OptimizationLevel::None).unwrap();
+    let plugin = context.create_module("plugin");
+    let fn_type = context.void_type().fn_type(&[], false);
+    let fn_value = plugin.add_function("plugin_fn", &fn_type, None);
+    let entry = context.append_basic_block(&fn_value, "entry");
+
+    builder.position_at_end(&entry);
+    builder.build_return(None);
+
+    ee.add_module(&plugin).unwrap();
+
+    drop(plugin);
+
+    assert_eq!(ee.modules().count(), 2);
+    assert_eq!(ee.remove_module_by_name("missing").unwrap_err(), RemoveModuleError::ModuleNotOwned);
+
+    let plugin = ee.remove_module_by_name("plugin").unwrap();
+
+    assert!(plugin.get_function("plugin_fn").is_some());
+    assert_eq!(ee.modules().count(), 1);
+    assert_eq!(ee.remove_module_by_name("plugin").unwrap_err(), RemoveModuleError::ModuleNotOwned);
+    assert!(ee.add_module(&plugin).is_ok());
+}
+
+#[test]
+fn test_remove_module_by_name_with_other_handles() {
+    Target::initialize_native(&InitializationConfig::default()).expect("Failed to initialize native target");
+
+    let context = Context::create();
+    let ee = context.create_module("main").create_jit_execution_engine(OptimizationLevel::None).unwrap();
+    let plugin = context.create_module("plugin");
+    let fn_type = context.void_type().fn_type(&[], false);
+
+    plugin.add_function("plugin_fn", &fn_type, None);
+    ee.add_module(&plugin).unwrap();
+
+    let plugin2 = ee.remove_module_by_name("plugin").unwrap();
+
+    // Both handles share the same, no longer owned, module
+    assert_eq!(ee.remove_module(&plugin), Err(RemoveModuleError::ModuleNotOwned));
+
+    // Linking would destroy the module out from under the other handle
+    assert!(context.create_module("other").link_in_module(plugin2).is_err());
+
+    assert!(plugin.get_function("plugin_fn").is_some());
+    assert!(ee.add_module(&plugin).is_ok());
+}
+
+#[test]
+fn test_modules_share_state() {
+    Target::initialize_native(&InitializationConfig::default()).expect("Failed to initialize native target");
+
+    let context = Context::create();
+    let module = context.create_module("test");
+    let ee = module.create_jit_execution_engine(OptimizationLevel::None).unwrap();
+
+    ee.modules().next().unwrap().freeze();
+
+    assert!(module.is_frozen());
+
+    drop(module);
+    drop(context);
+
+    // The EE keeps the module's context alive, and remembers that it was frozen
+    let module = ee.modules().next().unwrap();
+
+    assert!(module.is_frozen());
+    assert!(module.print_to_string().to_string().contains("ModuleID = 'test'"));
+}
+
+#[test]
+fn test_modules() {
+    Target::initialize_native(&InitializationConfig::default()).expect("Failed to initialize native target");
+
+    let context = Context::create();
+    let module = context.create_module("test");
+    let module2 = context.create_module("mod2");
+    let ee = module.create_jit_execution_engine(OptimizationLevel::None).unwrap();
+
+    assert_eq!(ee.modules().count(), 1);
+
+    ee.add_module(&module2).unwrap();
+
+    let modules: Vec<_> = ee.clone().modules().map(|module| module.print_to_string().to_string()).collect();
+
+    assert_eq!(modules, vec![module.print_to_string().to_string(), module2.print_to_string().to_string()]);
+
+    ee.remove_module(&module).unwrap();
+
+    assert_eq!(ee.modules().count(), 1);
+    assert_eq!(ee.modules().next().unwrap().print_to_string(), module2.print_to_string());
+}
+
+#[test]
+fn test_function_values() {
+    let context = Context::create();
+    let builder = context.create_builder();
+    let module = context.create_module("test");
+    let module2 = context.create_module("test2");
+    let fn_type = context.void_type().fn_type(&[], false);
+    let defined_fn = module.add_function("defined", &fn_type, None);
+    let entry = context.append_basic_block(&defined_fn, "entry");
+
+    builder.position_at_end(&entry);
+    builder.build_return(None);
+
+    let declared_fn = module.add_function("declared", &fn_type, None);
+    let other_fn = module2.add_function("other", &fn_type, None);
+    let ee = context.create_module("empty").create_interpreter_execution_engine().unwrap();
+
+    assert_eq!(ee.function_values().count(), 0);
+
+    ee.add_module(&module).unwrap();
+    ee.add_module(&module2).unwrap();
+
+    let functions: Vec<_> = ee.function_values().collect();
+
+    assert_eq!(functions, vec![defined_fn, declared_fn, other_fn]);
+    assert_eq!(functions.iter().filter(|function| function.count_basic_blocks() > 0).count(), 1);
+}
+
+#[test]
+fn test_function_linkage_visibility() {
+    Target::initialize_native(&InitializationConfig::default()).expect("Failed to initialize native target");
+
+    let context = Context::create();
+    let module = context.create_module("test");
+    let builder = context.create_builder();
+    let i32_type = context.i32_type();
+    let fn_type = i32_type.fn_type(&[], false);
+    let helper_fn = module.add_function("helper", &fn_type, None);
+    let entry = context.append_basic_block(&helper_fn, "entry");
+
+    builder.position_at_end(&entry);
+    builder.build_return(Some(&i32_type.const_int(7, false)));
+
+    helper_fn.set_linkage(Linkage::InternalLinkage);
+
+    let entry_fn = module.add_function("entry_point", &fn_type, Some(Linkage::ExternalLinkage));
+    let entry = context.append_basic_block(&entry_fn, "entry");
+
+    builder.position_at_end(&entry);
+
+    let result = builder.build_call(&helper_fn, &[], "result", false).left().unwrap();
+
+    builder.build_return(Some(&result));
+
+    assert_eq!(helper_fn.get_linkage(), Linkage::InternalLinkage);
+    assert_eq!(entry_fn.get_linkage(), Linkage::ExternalLinkage);
+
+    let ee = module.create_jit_execution_engine(OptimizationLevel::None).unwrap();
+
+    unsafe {
+        assert_eq!(ee.get_function_address("helper"), Err(FunctionLookupError::FunctionNotFound));
+        assert_eq!(ee.get_function::<unsafe extern "C" fn() -> i32>("helper").unwrap_err(), FunctionLookupError::FunctionNotFound);
+
+        let entry_point = ee.get_function::<unsafe extern "C" fn() -> i32>("entry_point").unwrap();
+
+        assert_eq!(entry_point(), 7);
+    }
+}
+
+#[test]
+fn test_get_function_unchecked() {
+    Target::initialize_native(&InitializationConfig::default()).expect("Failed to initialize native target");
+
+    let context = Context::create();
+    let module = context.create_module("test");
+    let builder = context.create_builder();
+    let i64_type = context.i64_type();
+    let fn_type = i64_type.fn_type(&[&i64_type], false);
+    let fn_value = module.add_function("increment", &fn_type, None);
+    let entry = context.append_basic_block(&fn_value, "entry");
+
+    builder.position_at_end(&entry);
+
+    let param = fn_value.get_first_param().unwrap().into_int_value();
+    let incremented = builder.build_int_add(param, i64_type.const_int(1, false), "incremented");
+
+    builder.build_return(Some(&incremented));
+
+    let interpreter = context.create_module("empty").create_interpreter_execution_engine().unwrap();
+
+    unsafe {
+        assert_eq!(interpreter.get_function_unchecked::<unsafe extern "C" fn(u64) -> u64>("increment").unwrap_err(), FunctionLookupError::JITNotEnabled);
+    }
+
+    let ee = module.create_jit_execution_engine(OptimizationLevel::None).unwrap();
+
+    unsafe {
+        let checked = ee.get_function::<unsafe extern "C" fn(u64) -> u64>("increment").unwrap();
+        let unchecked = ee.get_function_unchecked::<unsafe extern "C" fn(u64) -> u64>("increment").unwrap();
+
+        assert_eq!(unchecked.as_raw(), checked.as_raw());
+        assert_eq!(unchecked(41), 42);
+    }
+}
+
+#[test]
+fn test_symbol_into_raw_send() {
+    Target::initialize_native(&InitializationConfig::default()).expect("Failed to initialize native target");
+
+    let context = Context::create();
+    let module = context.create_module("test");
+    let builder = context.create_builder();
+    let i32_type = context.i32_type();
+    let fn_type = i32_type.fn_type(&[&i32_type], false);
+    let fn_value = module.add_function("double", &fn_type, None);
+    let entry = context.append_basic_block(&fn_value, "entry");
+
+    builder.position_at_end(&entry);
+
+    let param = fn_value.get_first_param().unwrap().into_int_value();
+    let doubled = builder.build_int_add(param, param, "doubled");
+
+    builder.build_return(Some(&doubled));
+
+    let ee = module.create_jit_execution_engine(OptimizationLevel::None).unwrap();
+    let address = unsafe {
+        let symbol = ee.get_function::<unsafe extern "C" fn(i32) -> i32>("double").unwrap();
+        let raw = symbol.as_raw();
+
+        assert_eq!(symbol.into_raw_send(), raw);
+
+        raw
+    };
+
+    let results: Vec<i32> = (0..4).map(|i| {
+        std::thread::spawn(move || {
+            let double: unsafe extern "C" fn(i32) -> i32 = unsafe { std::mem::transmute(address) };
+
+            unsafe { double(i) }
+        })
+    }).map(|handle| handle.join().unwrap()).collect();
+
+    assert_eq!(results, vec![0, 2, 4, 6]);
+}
+
+#[test]
+fn test_free_fn_machine_code() {
+    Target::initialize_native(&InitializationConfig::default()).expect("Failed to initialize native target");
+
+    let context = Context::create();
+    let module = context.create_module("test");
+    let builder = context.create_builder();
+    let fn_type = context.void_type().fn_type(&[], false);
+    let freed_fn = module.add_function("freed", &fn_type, None);
+    let entry = context.append_basic_block(&freed_fn, "entry");
+
+    builder.position_at_end(&entry);
+    builder.build_return(None);
+
+    let kept_fn = module.add_function("kept", &fn_type, None);
+    let entry = context.append_basic_block(&kept_fn, "entry");
+
+    builder.position_at_end(&entry);
+    builder.build_return(None);
+
+    let ee = module.create_jit_execution_engine(OptimizationLevel::None).unwrap();
+    let ee2 = ee.clone();
+
+    unsafe {
+        assert!(ee.get_function_address("freed").is_ok());
+
+        assert_eq!(ee.free_fn_machine_code(&freed_fn), Ok(()));
+        assert_eq!(ee.free_fn_machine_code(&freed_fn), Ok(()));
+
+        assert_eq!(ee.get_function_address("freed"), Err(FunctionLookupError::FunctionFreed));
+        assert_eq!(ee2.get_function::<unsafe extern "C" fn()>("freed").unwrap_err(), FunctionLookupError::FunctionFreed);
+        assert_eq!(ee.get_function_unchecked::<unsafe extern "C" fn()>("freed").unwrap_err(), FunctionLookupError::FunctionFreed);
+        assert!(ee.get_function_address("kept").is_ok());
+    }
+
+    // Functions from modules the EE doesn't own are rejected
+    let other_module = context.create_module("other");
+    let other_fn = other_module.add_function("freed", &fn_type, None);
+
+    assert_eq!(ee.free_fn_machine_code(&other_fn), Err(FunctionLookupError::FunctionNotOwned));
+
+    // Once its module is removed and added back, it is compiled anew
+    ee.remove_module(&module).unwrap();
+    ee.add_module(&module).unwrap();
+
+    unsafe {
+        assert!(ee.get_function_address("freed").is_ok());
+    }
+}
+
+#[test]
+fn test_get_data_layout_string() {
+    Target::initialize_native(&InitializationConfig::default()).expect("Failed to initialize native target");
+
+    let context = Context::create();
+    let module = context.create_module("test");
+    let ee = module.create_jit_execution_engine(OptimizationLevel::None).unwrap();
+    let data_layout = ee.get_data_layout_string();
+
+    assert!(!data_layout.to_bytes().is_empty());
+    assert_eq!(&*data_layout, ee.get_target_data().get_data_layout().as_str());
+
+    let interpreter = context.create_module("test2").create_interpreter_execution_engine().unwrap();
+
+    assert_eq!(&*interpreter.get_data_layout_string(), interpreter.get_target_data().get_data_layout().as_str());
+}
+
+#[test]
+fn test_jit_sets_module_data_layout() {
+    Target::initialize_native(&InitializationConfig::default()).expect("Failed to initialize native target");
+
+    let context = Context::create();
+    let module = context.create_module("test");
+
+    assert!(module.get_data_layout().as_str().to_bytes().is_empty());
+
+    let ee = module.create_jit_execution_engine(OptimizationLevel::None).unwrap();
+
+    assert_eq!(*module.get_data_layout(), ee.get_target_data().get_data_layout());
+
+    // Holding on to the data layout while creating another EE shouldn't panic
+    let module2 = context.create_module("test2");
+    let data_layout = module2.get_data_layout();
+    let ee2 = module2.create_jit_execution_engine(OptimizationLevel::None).unwrap();
+
+    drop(data_layout);
+
+    assert_eq!(*module2.get_data_layout(), ee2.get_target_data().get_data_layout());
+}
+
+#[test]
+fn test_get_target_machine() {
+    Target::initialize_native(&InitializationConfig::default()).expect("Failed to initialize native target");
+
+    let context = Context::create();
+    let module = context.create_module("test");
+    let ee = module.create_jit_execution_engine(OptimizationLevel::None).unwrap();
+    let target_machine = ee.get_target_machine().unwrap();
+
+    assert!(!target_machine.get_triple().to_bytes().is_empty());
+
+    let ee2 = ee.clone();
+
+    drop(ee);
+
+    assert!(ee2.get_target_machine().is_some());
+
+    let module = context.create_module("test2");
+    let ee = module.create_interpreter_execution_engine().unwrap();
+
+    assert!(ee.get_target_machine().is_none());
+}
+
+#[test]
+fn test_finalize_object() {
+    Target::initialize_native(&InitializationConfig::default()).expect("Failed to initialize native target");
+
+    let context = Context::create();
+    let module = context.create_module("test");
+    let builder = context.create_builder();
+    let i32_type = context.i32_type();
+    let fn_type = i32_type.fn_type(&[], false);
+
+    // Neither declarations nor functions MCJIT doesn't export should trip up finalization
+    module.add_function("external", &fn_type, None);
+
+    let internal = module.add_function("internal", &fn_type, Some(Linkage::InternalLinkage));
+    let entry = context.append_basic_block(&internal, "entry");
+
+    builder.position_at_end(&entry);
+    builder.build_return(Some(&i32_type.const_int(0, false)));
+
+    let fn_value = module.add_function("answer", &fn_type, None);
+    let entry = context.append_basic_block(&fn_value, "entry");
+
+    builder.position_at_end(&entry);
+    builder.build_return(Some(&i32_type.const_int(42, false)));
+
+    let ee = module.create_jit_execution_engine(OptimizationLevel::None).unwrap();
+
+    assert_eq!(ee.finalize_object(), Ok(()));
+
+    unsafe {
+        let answer = ee.get_function::<unsafe extern "C" fn() -> i32>("answer").unwrap();
+
+        assert_eq!(answer(), 42);
+    }
+
+    let empty_ee = context.create_module("empty").create_jit_execution_engine(OptimizationLevel::None).unwrap();
+
+    assert_eq!(empty_ee.finalize_object(), Ok(()));
+
+    let interpreter_ee = context.create_module("interpreter").create_interpreter_execution_engine().unwrap();
+
+    assert_eq!(interpreter_ee.finalize_object(), Ok(()));
+}
+
+#[test]
+fn test_get_global_address() {
+    Target::initialize_native(&InitializationConfig::default()).expect("Failed to initialize native target");
+
+    let context = Context::create();
+    let module = context.create_module("test");
+    let builder = context.create_builder();
+    let i32_type = context.i32_type();
+    let global = module.add_global(&i32_type, None, "counter");
+
+    global.set_initializer(&i32_type.const_int(1, false));
+
+    let fn_type = i32_type.fn_type(&[], false);
+    let fn_value = module.add_function("get_counter", &fn_type, None);
+    let entry = context.append_basic_block(&fn_value, "entry");
+
+    builder.position_at_end(&entry);
+
+    let counter = builder.build_load(&global.as_pointer_value(), "counter");
+
+    builder.build_return(Some(&counter));
+
+    let ee = module.create_jit_execution_engine(OptimizationLevel::None).unwrap();
+
+    unsafe {
+        assert_eq!(ee.get_global_address("missing"), Err(FunctionLookupError::FunctionNotFound));
+
+        let counter = ee.get_global_address("counter").unwrap() as *mut i32;
+
+        assert_eq!(*counter, 1);
+
+        *counter = 42;
+
+        let get_counter = ee.get_function::<unsafe extern "C" fn() -> i32>("get_counter").unwrap();
+
+        assert_eq!(get_counter(), 42);
+    }
+}
+
+#[test]
+fn test_run_function_as_main_errors() {
+    Target::initialize_native(&InitializationConfig::default()).expect("Failed to initialize native target");
+
+    let context = Context::create();
+    let module = context.create_module("main_module");
+    let builder = context.create_builder();
+    let i32_type = context.i32_type();
+    let i64_type = context.i64_type();
+    let void_type = context.void_type();
+    let i8_ptr_ptr_type = context.i8_type().ptr_type(AddressSpace::Generic).ptr_type(AddressSpace::Generic);
+    let fn_types = [
+        void_type.fn_type(&[], false),
+        i64_type.fn_type(&[], false),
+        i32_type.fn_type(&[&i64_type], false),
+        i32_type.fn_type(&[&i32_type, &i32_type], false),
+        i32_type.fn_type(&[&i32_type, &i8_ptr_ptr_type, &i8_ptr_ptr_type, &i32_type], false),
+    ];
+    let bad_mains: Vec<_> = fn_types.iter().enumerate().map(|(i, fn_type)| {
+        let bad_main = module.add_function(&format!("bad_main{}", i), fn_type, None);
+        let entry = context.append_basic_block(&bad_main, "entry");
+
+        builder.position_at_end(&entry);
+        builder.build_unreachable();
+
+        bad_main
+    }).collect();
+    let main_type = i32_type.fn_type(&[&i32_type, &i8_ptr_ptr_type], false);
+    let main = module.add_function("main", &main_type, None);
+    let entry = context.append_basic_block(&main, "entry");
+
+    builder.position_at_end(&entry);
+    builder.build_return(Some(&main.get_first_param().unwrap()));
+
+    let other_main = context.create_module("other").add_function("main", &main_type, None);
+    let execution_engine = module.create_jit_execution_engine(OptimizationLevel::None).unwrap();
+
+    unsafe {
+        for bad_main in &bad_mains {
+            assert_eq!(execution_engine.run_function_as_main(bad_main, &[]), Err(RunFunctionError::InvalidMainSignature));
+            assert_eq!(execution_engine.run_function_as_main_with_ctors(bad_main, &[], true), Err(RunFunctionError::InvalidMainSignature));
+        }
+
+        assert_eq!(execution_engine.run_function_as_main(&other_main, &[]), Err(RunFunctionError::FunctionNotOwned));
+        assert_eq!(execution_engine.run_function_as_main(&main, &["main", "arg"]), Ok(2));
+    }
+}
+
+#[test]
+fn test_run_function_as_main_with_ctors() {
+    Target::initialize_native(&InitializationConfig::default()).expect("Failed to initialize native target");
+
+    let context = Context::create();
+    let module = context.create_module("main_module");
+    let builder = context.create_builder();
+    let i32_type = context.i32_type();
+    let fn_type = i32_type.fn_type(&[], false);
+    let main = module.add_function("main", &fn_type, None);
+    let entry = context.append_basic_block(&main, "entry");
+
+    builder.position_at_end(&entry);
+    builder.build_return(Some(&i32_type.const_int(3, false)));
+
+    let execution_engine = module.create_jit_execution_engine(OptimizationLevel::None).unwrap();
+
+    // There's no llvm.global_ctors in this module, so no constructors are ever run
+    unsafe {
+        assert_eq!(execution_engine.run_function_as_main_with_ctors(&main, &["main"], false), Ok((3, false)));
+        assert_eq!(execution_engine.run_function_as_main_with_ctors(&main, &["main"], true), Ok((3, false)));
+    }
+}
+
+#[test]
+fn test_run_function_as_main_with_global_ctors() {
+    Target::initialize_native(&InitializationConfig::default()).expect("Failed to initialize native target");
+
+    let ir = r#"
+        @counter = global i32 0
+        @llvm.global_ctors = appending global [1 x { i32, void ()*, i8* }] [{ i32, void ()*, i8* } { i32 65535, void ()* @init, i8* null }]
+
+        define void @init() {
+        entry:
+          %value = load i32, i32* @counter
+          %incremented = add i32 %value, 1
+          store i32 %incremented, i32* @counter
+          ret void
+        }
+
+        define i32 @main() {
+        entry:
+          %value = load i32, i32* @counter
+          ret i32 %value
+        }
+    "#;
+
+    let context = Context::create();
+    let memory_buffer = MemoryBuffer::create_from_memory_range(ir, "ctors");
+    let module = context.create_module_from_ir(memory_buffer).unwrap();
+    let main = module.get_function("main").unwrap();
+    let execution_engine = module.create_jit_execution_engine(OptimizationLevel::None).unwrap();
+
+    unsafe {
+        assert_eq!(execution_engine.run_function_as_main_with_ctors(&main, &["main"], false), Ok((0, false)));
+        assert_eq!(execution_engine.run_function_as_main_with_ctors(&main, &["main"], true), Ok((1, true)));
+    }
+}
+
+#[test]
+fn test_add_global_mappings() {
+    Target::initialize_native(&InitializationConfig::default()).expect("Failed to initialize native target");
+
+    extern fn add_one(a: i32) -> i32 {
+        a + 1
+    }
+
+    extern fn double(a: i32) -> i32 {
+        a * 2
+    }
+
+    let context = Context::create();
+    let module = context.create_module("test");
+    let builder = context.create_builder();
+    let i32_type = context.i32_type();
+    let fn_type = i32_type.fn_type(&[&i32_type], false);
+    let add_one_fn = module.add_function("add_one", &fn_type, None);
+    let double_fn = module.add_function("double", &fn_type, None);
+    let fn_value = module.add_function("add_one_then_double", &fn_type, None);
+    let entry = context.append_basic_block(&fn_value, "entry");
+
+    builder.position_at_end(&entry);
+
+    let param = fn_value.get_first_param().unwrap();
+    let plus_one = builder.build_call(&add_one_fn, &[&param], "plus_one", false).left().unwrap();
+    let doubled = builder.build_call(&double_fn, &[&plus_one], "doubled", false).left().unwrap();
+
+    builder.build_return(Some(&doubled));
+
+    let ee = module.create_jit_execution_engine(OptimizationLevel::None).unwrap();
+
+    ee.add_global_mappings(&[
+        (&add_one_fn, add_one as usize),
+        (&double_fn, double as usize),
+    ]);
+
+    unsafe {
+        let add_one_then_double = ee.get_function::<unsafe extern "C" fn(i32) -> i32>("add_one_then_double").unwrap();
+
+        assert_eq!(add_one_then_double(4), 10);
+    }
+}
+
+#[test]
+fn test_target_data_queries() {
+    Target::initialize_native(&InitializationConfig::default()).expect("Failed to initialize native target");
+
+    let context = Context::create();
+    let module = context.create_module("test");
+    let execution_engine = module.create_jit_execution_engine(OptimizationLevel::None).unwrap();
+    let target_data = execution_engine.get_target_data();
+    let i8_type = context.i8_type();
+    let i64_type = context.i64_type();
+    let f64_type = context.f64_type();
+    let struct_type = context.struct_type(&[i8_type.into(), i64_type.into()], false);
+
+    assert_eq!(target_data.get_pointer_byte_size(None) as usize, size_of::<usize>());
+    assert_eq!(target_data.get_store_size(&i8_type), 1);
+    assert_eq!(target_data.get_store_size(&f64_type), size_of::<f64>() as u64);
+    assert_eq!(target_data.get_store_size(&struct_type), size_of::<(u8, u64)>() as u64);
+    assert_eq!(target_data.get_abi_alignment(&i64_type) as usize, align_of::<u64>());
+    assert_eq!(target_data.get_abi_alignment(&struct_type) as usize, align_of::<(u8, u64)>());
+}
+
+#[test]
+fn test_jit_function() {
+    Target::initialize_native(&InitializationConfig::default()).expect("Failed to initialize native target");
+
+    let context = Context::create();
+    let module = context.create_module("test");
+    let builder = context.create_builder();
+    let i32_type = context.i32_type();
+    let fn_type = i32_type.fn_type(&[&i32_type], false);
+    let fn_value = module.add_function("negate", &fn_type, None);
+    let entry = context.append_basic_block(&fn_value, "entry");
+
+    builder.position_at_end(&entry);
+
+    let param = fn_value.get_first_param().unwrap().into_int_value();
+    let negated = builder.build_int_neg(&param, "negated");
+
+    builder.build_return(Some(&negated));
+
+    unsafe {
+        let (execution_engine, negate) = module.jit_function::<unsafe extern "C" fn(i32) -> i32>("negate", OptimizationLevel::None).unwrap();
+
+        assert!(execution_engine.is_jit());
+        assert!(module.is_frozen());
+        assert_eq!(negate(5), -5);
+
+        drop(execution_engine);
+
+        assert_eq!(negate(-3), 3);
+    }
+
+    let module = context.create_module("test2");
+
+    unsafe {
+        let err = module.jit_function::<unsafe extern "C" fn(i32) -> i32>("negate", OptimizationLevel::None).unwrap_err();
+
+        assert_eq!(err, JitFunctionError::LookupError(FunctionLookupError::FunctionNotFound));
+        assert_eq!(err.source().unwrap().to_string(), FunctionLookupError::FunctionNotFound.to_string());
+        assert!(FunctionLookupError::FunctionNotFound.source().is_none());
+    }
+}
+
+#[test]
+fn test_run_function_errors() {
+    let context = Context::create();
+    let module = context.create_module("test");
+    let builder = context.create_builder();
+    let i32_type = context.i32_type();
+    let fn_type = i32_type.fn_type(&[&i32_type], false);
+    let fn_value = module.add_function("identity", &fn_type, None);
+    let entry = context.append_basic_block(&fn_value, "entry");
+
+    builder.position_at_end(&entry);
+    builder.build_return(Some(&fn_value.get_first_param().unwrap()));
+
+    let other_module = context.create_module("other");
+    let other_fn_value = other_module.add_function("other", &fn_type, None);
+
+    let execution_engine = module.create_interpreter_execution_engine().unwrap();
+    let arg = i32_type.create_generic_value(7, false);
+
+    unsafe {
+        assert_eq!(execution_engine.run_function(&fn_value, &[]).unwrap_err(), RunFunctionError::IncorrectArgumentCount);
+        assert_eq!(execution_engine.run_function(&other_fn_value, &[&arg]).unwrap_err(), RunFunctionError::FunctionNotOwned);
+        assert_eq!(execution_engine.run_function(&fn_value, &[&arg]).unwrap().as_int(false), 7);
+    }
+
+    execution_engine.remove_module(&module).unwrap();
+
+    unsafe {
+        assert_eq!(execution_engine.run_function(&fn_value, &[&arg]).unwrap_err(), RunFunctionError::FunctionNotOwned);
+    }
+}
+
+#[test]
+fn test_run_function_with_limit() {
+    Target::initialize_native(&InitializationConfig::default()).expect("Failed to initialize native target");
+
+    let ir = r#"
+        define i64 @sum(i64 %n) {
+        entry:
+          br label %loop
+
+        loop:
+          %i = phi i64 [ 0, %entry ], [ %next, %loop ]
+          %acc = phi i64 [ 0, %entry ], [ %acc2, %loop ]
+          %acc2 = add i64 %acc, %i
+          %next = add i64 %i, 1
+          %done = icmp eq i64 %next, %n
+          br i1 %done, label %exit, label %loop
+
+        exit:
+          ret i64 %acc2
+        }
+
+        define i64 @fib(i64 %n) {
+        entry:
+          %small = icmp ult i64 %n, 2
+          br i1 %small, label %base, label %recurse
+
+        base:
+          ret i64 %n
+
+        recurse:
+          %a = sub i64 %n, 1
+          %b = sub i64 %n, 2
+          %fib_a = call i64 @fib(i64 %a)
+          %fib_b = call i64 @fib(i64 %b)
+          %result = add i64 %fib_a, %fib_b
+          ret i64 %result
+        }
+    "#;
+
+    let context = Context::create();
+    let memory_buffer = MemoryBuffer::create_from_memory_range(ir, "limit");
+    let module = context.create_module_from_ir(memory_buffer).unwrap();
+    let sum = module.get_function("sum").unwrap();
+    let fib = module.get_function("fib").unwrap();
+    let execution_engine = module.create_interpreter_execution_engine().unwrap();
+    let i64_type = context.i64_type();
+    let three = i64_type.create_generic_value(3, false);
+    let twenty = i64_type.create_generic_value(20, false);
+    let max = i64_type.create_generic_value(u64::max_value(), false);
+
+    unsafe {
+        // Runs 1 + 3 * 6 + 1 instructions
+        assert_eq!(execution_engine.run_function_with_limit(&sum, &[&three], 20).unwrap().as_int(false), 3);
+        assert_eq!(execution_engine.run_function_with_limit(&sum, &[&three], 19).unwrap_err(), RunFunctionError::InstructionLimitExceeded);
+        assert_eq!(execution_engine.run_function_with_limit(&sum, &[&max], 1_000_000).unwrap_err(), RunFunctionError::InstructionLimitExceeded);
+        assert_eq!(execution_engine.run_function_with_limit(&fib, &[&twenty], 1_000_000).unwrap().as_int(false), 6765);
+        assert_eq!(execution_engine.run_function_with_limit(&fib, &[&twenty], 1000).unwrap_err(), RunFunctionError::InstructionLimitExceeded);
+        assert_eq!(execution_engine.run_function_with_limit(&sum, &[], 20).unwrap_err(), RunFunctionError::IncorrectArgumentCount);
+
+        // The limit doesn't outlive the run
+        assert_eq!(execution_engine.run_function(&sum, &[&three]).unwrap().as_int(false), 3);
+    }
+
+    assert!(module.verify().is_ok());
+
+    let jit_module = context.create_module("jit");
+    let jit_sum = jit_module.add_function("sum", &i64_type.fn_type(&[&i64_type], false), None);
+    let jit_execution_engine = jit_module.create_jit_execution_engine(OptimizationLevel::None).unwrap();
+
+    unsafe {
+        assert_eq!(jit_execution_engine.run_function_with_limit(&jit_sum, &[&three], 20).unwrap_err(), RunFunctionError::JITNotSupported);
+    }
+}
+
+#[test]
+fn test_generic_value_int() {
+    let context = Context::create();
+    let module = context.create_module("test");
+    let builder = context.create_builder();
+    let i32_type = context.i32_type();
+    let fn_type = i32_type.fn_type(&[], false);
+    let fn_value = module.add_function("answer", &fn_type, None);
+    let entry = context.append_basic_block(&fn_value, "entry");
+
+    builder.position_at_end(&entry);
+    builder.build_return(Some(&i32_type.const_int(42, false)));
+
+    let execution_engine = module.create_interpreter_execution_engine().unwrap();
+    let result = unsafe { execution_engine.run_function(&fn_value, &[]) }.unwrap();
+
+    assert_eq!(result.int_width(), 32);
+    assert_eq!(result.as_int(true), 42);
+
+    let negative_one = i32_type.create_generic_value(-1i32 as u64, true);
+
+    assert_eq!(negative_one.int_width(), 32);
+    assert_eq!(negative_one.as_int(true), -1i64 as u64);
+    assert_eq!(negative_one.as_int(false), u32::max_value() as u64);
+}
+
+#[test]
+fn test_generic_value_into_scalars() {
+    let context = Context::create();
+    let module = context.create_module("test");
+    let builder = context.create_builder();
+    let f64_type = context.f64_type();
+    let fn_type = f64_type.fn_type(&[&f64_type], false);
+    let fn_value = module.add_function("double", &fn_type, None);
+    let entry = context.append_basic_block(&fn_value, "entry");
+
+    builder.position_at_end(&entry);
+
+    let param = fn_value.get_first_param().unwrap().into_float_value();
+    let doubled = builder.build_float_add(param, param, "doubled");
+
+    builder.build_return(Some(&doubled));
+
+    let execution_engine = module.create_interpreter_execution_engine().unwrap();
+    let arg = f64_type.create_generic_value(1.25);
+    let result = unsafe { execution_engine.run_function(&fn_value, &[&arg]) }.unwrap();
+
+    assert_eq!(result.into_float(&f64_type), 2.5);
+    assert_eq!(context.i64_type().create_generic_value(-3i64 as u64, true).into_int(true) as i64, -3);
+}
+
+#[test]
+fn test_generic_value_from_slice() {
+    let context = Context::create();
+    let module = context.create_module("test");
+    let builder = context.create_builder();
+    let i64_type = context.i64_type();
+    let f64_type = context.f64_type();
+    let f64_ptr_type = f64_type.ptr_type(AddressSpace::Generic);
+    let fn_type = f64_type.fn_type(&[&f64_ptr_type, &i64_type], false);
+    let fn_value = module.add_function("sum", &fn_type, None);
+    let entry = context.append_basic_block(&fn_value, "entry");
+
+    builder.position_at_end(&entry);
+
+    // Sums the first and last elements
+    let array_ptr = fn_value.get_first_param().unwrap().into_pointer_value();
+    let last_index = fn_value.get_last_param().unwrap().into_int_value();
+    let last_ptr = unsafe { builder.build_gep(&array_ptr, &[last_index], "last_ptr") };
+    let first = builder.build_load(&array_ptr, "first").into_float_value();
+    let last = builder.build_load(&last_ptr, "last").into_float_value();
+    let sum = builder.build_float_add(first, last, "sum");
+
+    builder.build_return(Some(&sum));
+
+    let execution_engine = module.create_interpreter_execution_engine().unwrap();
+    let mut data = [0.5f64, 1., 2., 4.];
+    let array_arg = unsafe { GenericValue::from_slice(&mut data) };
+    let index_arg = i64_type.create_generic_value(3, false);
+    let result = unsafe { execution_engine.run_function(&fn_value, &[&array_arg, &index_arg]) }.unwrap();
+
+    assert_eq!(result.as_float(&f64_type), 4.5);
+    assert_eq!(array_arg.as_pointer::<f64>() as *const f64, data.as_ptr());
+}
+
+#[test]
+fn test_generic_value_as_bytes() {
+    let context = Context::create();
+    let module = context.create_module("test");
+    let builder = context.create_builder();
+    let i8_type = context.i8_type();
+    let i32_type = context.i32_type();
+    let struct_type = context.struct_type(&[i32_type.into(), i8_type.into()], false);
+    let struct_ptr_type = struct_type.ptr_type(AddressSpace::Generic);
+    let fn_type = context.void_type().fn_type(&[&struct_ptr_type, &i32_type], false);
+    let fn_value = module.add_function("fill", &fn_type, None);
+    let entry = context.append_basic_block(&fn_value, "entry");
+
+    builder.position_at_end(&entry);
+
+    // Writes {value, 7} through the sret pointer
+    let out_ptr = fn_value.get_first_param().unwrap().into_pointer_value();
+    let value = fn_value.get_last_param().unwrap().into_int_value();
+    let first_ptr = builder.build_struct_gep(&out_ptr, 0, "first_ptr").unwrap();
+    let second_ptr = builder.build_struct_gep(&out_ptr, 1, "second_ptr").unwrap();
+
+    builder.build_store(&first_ptr, &value);
+    builder.build_store(&second_ptr, &i8_type.const_int(7, false));
+    builder.build_return(None);
+
+    #[repr(C)]
+    struct Out {
+        first: i32,
+        second: i8,
+    }
+
+    let execution_engine = module.create_interpreter_execution_engine().unwrap();
+    let mut out = Out { first: 0, second: 0 };
+    let out_arg = unsafe { GenericValue::create_generic_value_of_pointer(&mut out) };
+    let value_arg = i32_type.create_generic_value(-3i64 as u64, true);
+
+    unsafe {
+        execution_engine.run_function(&fn_value, &[&out_arg, &value_arg]).unwrap();
+
+        let bytes = out_arg.as_bytes(5);
+
+        assert_eq!(bytes[..4], (-3i32).to_ne_bytes());
+        assert_eq!(bytes[4], 7);
+    }
+
+    assert_eq!(out.first, -3);
+    assert_eq!(out.second, 7);
+}
+
+#[test]
+fn test_generic_value_pointer() {
+    let context = Context::create();
+    let module = context.create_module("test");
+    let builder = context.create_builder();
+    let i8_type = context.i8_type();
+    let i8_ptr_type = i8_type.ptr_type(AddressSpace::Generic);
+    let fn_type = i8_type.fn_type(&[&i8_ptr_type], false);
+    let fn_value = module.add_function("first_byte", &fn_type, None);
+    let entry = context.append_basic_block(&fn_value, "entry");
+
+    builder.position_at_end(&entry);
+
+    let ptr = fn_value.get_first_param().unwrap().into_pointer_value();
+    let first_byte = builder.build_load(&ptr, "first_byte");
+
+    builder.build_return(Some(&first_byte));
+
+    let mut bytes = [7u8, 8, 9];
+    let arg = unsafe { i8_ptr_type.create_generic_value(bytes.as_mut_ptr()) };
+
+    assert_eq!(arg.as_pointer::<u8>(), bytes.as_mut_ptr());
+
+    let execution_engine = module.create_interpreter_execution_engine().unwrap();
+    let result = unsafe { execution_engine.run_function(&fn_value, &[&arg]) }.unwrap();
+
+    assert_eq!(result.as_int(false), 7);
 }
 
 // REVIEW: Global state pollution access tests cause this to pass when run individually
@@ -3,6 +3,7 @@ extern crate inkwell;
 use self::inkwell::context::Context;
 use self::inkwell::memory_buffer::MemoryBuffer;
 use self::inkwell::module::Module;
+use self::inkwell::module::Linkage::*;
 use self::inkwell::OptimizationLevel;
 use self::inkwell::targets::{InitializationConfig, Target};
 
@@ -92,6 +93,32 @@ fn test_get_function() {
     assert_eq!(named_fn, some_fn);
 }
 
+#[test]
+fn test_get_functions() {
+    let context = Context::create();
+    let module = context.create_module("my_module");
+
+    assert!(module.get_functions().next().is_none());
+
+    let void_type = context.void_type();
+    let fn_type = void_type.fn_type(&[], false);
+    let first_fn = module.add_function("first_fn", &fn_type, None);
+    let second_fn = module.add_function("second_fn", &fn_type, None);
+    let third_fn = module.add_function("third_fn", &fn_type, None);
+
+    let mut functions = module.get_functions();
+
+    assert_eq!(functions.next(), Some(first_fn));
+    assert_eq!(functions.next(), Some(second_fn));
+    assert_eq!(functions.next(), Some(third_fn));
+    assert!(functions.next().is_none());
+    assert!(functions.next().is_none());
+
+    let names: Vec<_> = module.get_functions().map(|function| function.get_name().to_str().unwrap().to_string()).collect();
+
+    assert_eq!(names, vec!["first_fn", "second_fn", "third_fn"]);
+}
+
 #[test]
 fn test_module_owned_data_layout_disposed_safely() {
     let context = Context::create();
@@ -137,13 +164,72 @@ fn test_garbage_ir_fails_create_module_from_ir() {
 #[test]
 fn test_garbage_ir_fails_create_module_from_ir_copy() {
     let context = Context::create();
-    let memory_buffer = MemoryBuffer::create_from_memory_range_copy("garbage ir data", "my_ir");
+    let memory_buffer = MemoryBuffer::create_from_memory_range_copy(b"garbage ir data", "my_ir");
 
     assert_eq!(memory_buffer.get_size(), 15);
     assert_eq!(from_utf8(memory_buffer.as_slice()).unwrap(), "garbage ir data");
     assert!(context.create_module_from_ir(memory_buffer).is_err());
 }
 
+#[test]
+fn test_memory_buffer_from_file_and_bytes() {
+    let context = Context::create();
+    let module = context.create_module("my_module");
+    let bitcode = module.write_bitcode_to_memory();
+
+    // Bitcode contains nul bytes, which must be copied as is
+    assert!(bitcode.as_slice().contains(&0));
+
+    let buffer = MemoryBuffer::create_from_memory_range_copy(bitcode.as_slice(), "my_module.bc");
+
+    assert_eq!(buffer.as_slice(), bitcode.as_slice());
+    assert_eq!(buffer.get_size(), bitcode.get_size());
+
+    let mut path = temp_dir();
+
+    path.push("test_memory_buffer_from_file.bc");
+
+    assert!(module.write_bitcode_to_path(&path));
+
+    let file_buffer = MemoryBuffer::create_from_file(&path).unwrap();
+
+    assert_eq!(file_buffer.as_slice(), bitcode.as_slice());
+    assert!(context.create_module_from_bitcode(&file_buffer).is_ok());
+
+    remove_file(&path).unwrap();
+
+    let err = MemoryBuffer::create_from_file(&path).unwrap_err();
+
+    assert_eq!(*err, *CString::new("No such file or directory").unwrap());
+}
+
+#[test]
+fn test_create_module_from_textual_ir() {
+    Target::initialize_native(&InitializationConfig::default()).expect("Failed to initialize native target");
+
+    let context = Context::create();
+    let ir = "define i64 @square(i64 %x) {\nentry:\n  %squared = mul i64 %x, %x\n  ret i64 %squared\n}\n";
+    let memory_buffer = MemoryBuffer::create_from_memory_range_copy(ir.as_bytes(), "square.ll");
+    let module = context.create_module_from_ir(memory_buffer).unwrap();
+
+    assert_eq!(*module.get_context(), context);
+    assert!(module.verify().is_ok());
+
+    let ee = module.create_jit_execution_engine(OptimizationLevel::None).unwrap();
+
+    unsafe {
+        let square = ee.get_function::<unsafe extern "C" fn(u64) -> u64>("square").unwrap();
+
+        assert_eq!(square(12), 144);
+    }
+
+    // Undefined values are reported along with where they were used
+    let memory_buffer = MemoryBuffer::create_from_memory_range_copy(b"define void @f() {\n  ret i32 %nope\n}\n", "bad.ll");
+    let err = context.create_module_from_ir(memory_buffer).unwrap_err();
+
+    assert!(err.to_string().contains("bad.ll:2"), "{}", err);
+}
+
 #[test]
 fn test_get_type() {
     let context = Context::create();
@@ -294,6 +380,33 @@ fn test_clone() {
     assert_eq!(module.print_to_string(), module2.print_to_string());
 }
 
+#[test]
+fn test_clone_owned_by_ee() {
+    let context = Context::create();
+    let module = context.create_module("mod");
+    let void_type = context.void_type();
+    let fn_type = void_type.fn_type(&[], false);
+    let f = module.add_function("f", &fn_type, None);
+    let basic_block = f.append_basic_block("entry");
+    let builder = context.create_builder();
+
+    builder.position_at_end(&basic_block);
+    builder.build_return(None);
+
+    let _execution_engine = module.create_interpreter_execution_engine().unwrap();
+    let module2 = module.clone();
+
+    assert_eq!(module.get_context(), module2.get_context());
+    assert_eq!(module.print_to_string(), module2.print_to_string());
+
+    // The clone isn't owned by the first ExecutionEngine, so it can be given to another one
+    assert!(module2.create_interpreter_execution_engine().is_ok());
+
+    module2.add_function("g", &fn_type, None);
+
+    assert!(module.get_function("g").is_none());
+}
+
 #[test]
 fn test_print_to_file() {
     let context = Context::create();
@@ -307,7 +420,7 @@ fn test_print_to_file() {
     builder.position_at_end(&basic_block);
     builder.build_return(None);
 
-    let bad_path = Path::new("/");
+    let bad_path = Path::new("/does/not/exist/mod.ll");
 
     assert_eq!(*module.print_to_file(bad_path).unwrap_err(), *CString::new("No such file or directory").unwrap());
 
@@ -317,3 +430,204 @@ fn test_print_to_file() {
 
     assert!(module.print_to_file(&temp_path).is_ok());
 }
+
+#[test]
+fn test_print_to_string_display() {
+    let context = Context::create();
+    let module = context.create_module("mod");
+    let void_type = context.void_type();
+    let fn_type = void_type.fn_type(&[], false);
+
+    module.add_function("f", &fn_type, None);
+
+    let string = module.print_to_string();
+
+    assert_eq!(format!("{}", string), string.to_string());
+    assert_eq!(format!("{}", string), string.to_string_lossy());
+    assert!(format!("{}", string).starts_with("; ModuleID = 'mod'\n"));
+
+    let as_str: &str = string.as_ref();
+
+    assert_eq!(as_str, string.to_str().unwrap());
+}
+
+#[test]
+fn test_print_to_string_into_string() {
+    let context = Context::create();
+    let module = context.create_module("mod");
+    let expected = module.print_to_string().to_string();
+
+    assert_eq!(module.print_to_string().into_string(), Ok(expected.clone()));
+    assert_eq!(String::from(module.print_to_string()), expected);
+}
+
+#[test]
+fn test_freeze() {
+    let context = Context::create();
+    let module = context.create_module("mod");
+    let fn_type = context.void_type().fn_type(&[], false);
+
+    assert!(!module.is_frozen());
+
+    module.add_function("f", &fn_type, None);
+    module.freeze();
+
+    assert!(module.is_frozen());
+    assert!(module.get_function("f").is_some());
+    assert!(!module.clone().is_frozen());
+}
+
+#[test]
+#[should_panic]
+fn test_freeze_add_global() {
+    let context = Context::create();
+    let module = context.create_module("mod");
+
+    module.freeze();
+    module.add_global(&context.i32_type(), None, "global");
+}
+
+#[test]
+fn test_link_in_module() {
+    let context = Context::create();
+    let module = context.create_module("main");
+    let other = context.create_module("other");
+    let builder = context.create_builder();
+    let i32_type = context.i32_type();
+    let fn_type = i32_type.fn_type(&[], false);
+
+    module.add_function("answer", &fn_type, None);
+
+    let answer_fn = other.add_function("answer", &fn_type, None);
+    let entry = context.append_basic_block(&answer_fn, "entry");
+
+    builder.position_at_end(&entry);
+    builder.build_return(Some(&i32_type.const_int(42, false)));
+
+    let global = other.add_global(&i32_type, None, "global");
+
+    global.set_initializer(&i32_type.const_int(1, false));
+
+    assert!(module.link_in_module(other).is_ok());
+    assert_eq!(module.get_function("answer").unwrap().count_basic_blocks(), 1);
+    assert!(module.get_global("global").is_some());
+    assert!(module.verify().is_ok());
+
+    // Both modules define the same symbol
+    let duplicate = context.create_module("duplicate");
+    let answer_fn = duplicate.add_function("answer", &fn_type, None);
+    let entry = context.append_basic_block(&answer_fn, "entry");
+
+    builder.position_at_end(&entry);
+    builder.build_return(Some(&i32_type.const_int(0, false)));
+
+    let err = module.link_in_module(duplicate).unwrap_err();
+
+    assert!(err.to_string().contains("answer"), "{}", err);
+}
+
+#[test]
+fn test_link_in_module_errors() {
+    let context = Context::create();
+    let context2 = Context::create();
+    let module = context.create_module("main");
+
+    assert!(module.link_in_module(context2.create_module("other")).is_err());
+
+    let owned = context.create_module("owned");
+    let _ee = owned.create_interpreter_execution_engine().unwrap();
+
+    assert_eq!(*module.link_in_module(owned).unwrap_err(), *CString::new("Cannot link in a module owned by an ExecutionEngine").unwrap());
+}
+
+#[test]
+fn test_link_in_module_duplicate_definitions() {
+    let context = Context::create();
+    let builder = context.create_builder();
+    let i32_type = context.i32_type();
+    let fn_type = i32_type.fn_type(&[], false);
+
+    let define_helper = |module: &Module, linkage, value| {
+        let helper = module.add_function("helper", &fn_type, Some(linkage));
+        let entry = context.append_basic_block(&helper, "entry");
+
+        builder.position_at_end(&entry);
+        builder.build_return(Some(&i32_type.const_int(value, false)));
+    };
+
+    // Two external definitions of the same symbol conflict
+    let module = context.create_module("main");
+    let other = context.create_module("other");
+
+    define_helper(&module, ExternalLinkage, 1);
+    define_helper(&other, ExternalLinkage, 2);
+
+    assert!(module.link_in_module(other).is_err());
+
+    // Whereas ODR linkages keep a single definition
+    let module = context.create_module("main");
+    let other = context.create_module("other");
+
+    define_helper(&module, LinkOnceODRLinkage, 1);
+    define_helper(&other, LinkOnceODRLinkage, 1);
+
+    assert!(module.link_in_module(other).is_ok());
+    assert_eq!(module.get_functions().count(), 1);
+    assert!(module.verify().is_ok());
+}
+
+#[test]
+fn test_bitcode_round_trip() {
+    let context = Context::create();
+    let module = context.create_module("my_module");
+    let builder = context.create_builder();
+    let i64_type = context.i64_type();
+    let fn_type = i64_type.fn_type(&[&i64_type], false);
+    let fn_value = module.add_function("identity", &fn_type, None);
+    let entry = context.append_basic_block(&fn_value, "entry");
+
+    builder.position_at_end(&entry);
+    builder.build_return(Some(&fn_value.get_first_param().unwrap()));
+
+    let buffer = module.write_bitcode_to_memory();
+
+    assert_eq!(&buffer.as_slice()[..4], b"BC\xC0\xDE");
+
+    let module2 = context.create_module_from_bitcode(&buffer).unwrap();
+
+    assert_eq!(*module2.get_context(), context);
+    assert_eq!(module2.get_function("identity").unwrap().count_basic_blocks(), 1);
+    assert!(module2.verify().is_ok());
+
+    let garbage = MemoryBuffer::create_from_memory_range("not bitcode", "garbage");
+
+    assert!(context.create_module_from_bitcode(&garbage).is_err());
+}
+
+#[test]
+fn test_intrinsic_declaration() {
+    let context = Context::create();
+    let module = context.create_module("my_mod");
+    let builder = context.create_builder();
+    let f64_type = context.f64_type();
+    let i32_type = context.i32_type();
+    let sqrt_fn = module.add_function("llvm.sqrt.f64", &f64_type.fn_type(&[&f64_type], false), None);
+    let ctpop_fn = module.add_function("llvm.ctpop.i32", &i32_type.fn_type(&[&i32_type], false), None);
+    let fn_value = module.add_function("my_fn", &f64_type.fn_type(&[&f64_type], false), None);
+
+    assert_ne!(sqrt_fn.get_intrinsic_id(), 0);
+    assert_ne!(ctpop_fn.get_intrinsic_id(), 0);
+    assert_ne!(sqrt_fn.get_intrinsic_id(), ctpop_fn.get_intrinsic_id());
+    assert_eq!(fn_value.get_intrinsic_id(), 0);
+
+    let entry = fn_value.append_basic_block("entry");
+    let param = fn_value.get_first_param().unwrap();
+
+    builder.position_at_end(&entry);
+
+    let sqrt = builder.build_call(&sqrt_fn, &[&param], "sqrt", false).left().unwrap();
+
+    builder.build_return(Some(&sqrt));
+
+    assert!(module.verify().is_ok());
+}
@@ -1,14 +1,16 @@
 extern crate inkwell;
 
 use self::inkwell::context::Context;
+use self::inkwell::module::Module;
 use self::inkwell::passes::{PassManagerBuilder, PassManager, PassRegistry};
+use self::inkwell::values::{FunctionValue, InstructionOpcode};
 use self::inkwell::OptimizationLevel::Aggressive;
 
 #[test]
 fn test_init_all_passes_for_module() {
     let context = Context::create();
     let module = context.create_module("my_module");
-    let pass_manager = PassManager::create_for_module();
+    let pass_manager: PassManager<Module> = PassManager::create(());
 
     pass_manager.add_argument_promotion_pass();
     pass_manager.add_constant_merge_pass();
@@ -73,10 +75,20 @@ fn test_init_all_passes_for_module() {
         pass_manager.add_new_gvn_pass();
     }
 
-    assert!(!pass_manager.initialize());
-    assert!(!pass_manager.finalize());
+    pass_manager.run_on(&module);
+}
+
+#[test]
+fn test_init_all_passes_for_function() {
+    let context = Context::create();
+    let module = context.create_module("my_module");
+    let pass_manager: PassManager<FunctionValue> = PassManager::create(&module);
 
-    pass_manager.run_on_module(&module);
+    pass_manager.add_promote_memory_to_register_pass();
+    pass_manager.add_instruction_combining_pass();
+    pass_manager.add_reassociate_pass();
+    pass_manager.add_gvn_pass();
+    pass_manager.add_cfg_simplification_pass();
 
     assert!(!pass_manager.initialize());
     assert!(!pass_manager.finalize());
@@ -98,7 +110,7 @@ fn test_pass_manager_builder() {
     let context = Context::create();
     let module = context.create_module("my_module");
 
-    let fn_pass_manager = PassManager::create_for_function(&module);
+    let fn_pass_manager = PassManager::create(&module);
 
     pass_manager_builder.populate_function_pass_manager(&fn_pass_manager);
 
@@ -114,18 +126,18 @@ fn test_pass_manager_builder() {
     // TODO: Test with actual changes? Would be true in that case
     // REVIEW: Segfaults in 4.0
     #[cfg(not(feature = "llvm4-0"))]
-    assert!(!fn_pass_manager.run_on_function(&fn_value));
+    assert!(!fn_pass_manager.run_on(&fn_value));
 
-    let module_pass_manager = PassManager::create_for_module();
+    let module_pass_manager = PassManager::create(());
 
     pass_manager_builder.populate_module_pass_manager(&module_pass_manager);
 
     // TODOC: Seems to return true in 3.7 & 6.0, even though no changes were made.
     // In 3.6, 3.8, & 3.9 it returns false. Seems like a LLVM bug?
     #[cfg(not(any(feature = "llvm3-7", feature = "llvm6-0")))]
-    assert!(!module_pass_manager.run_on_module(&module));
+    assert!(!module_pass_manager.run_on(&module));
     #[cfg(any(feature = "llvm3-7", feature = "llvm6-0"))]
-    assert!(module_pass_manager.run_on_module(&module));
+    assert!(module_pass_manager.run_on(&module));
 
     // TODO: Populate LTO pass manager?
 }
@@ -147,3 +159,45 @@ fn test_pass_registry() {
     pass_registry.initialize_codegen();
     pass_registry.initialize_target();
 }
+
+#[test]
+fn test_run_function_passes() {
+    let context = Context::create();
+    let module = context.create_module("repl");
+    let builder = context.create_builder();
+    let fn_pass_manager: PassManager<FunctionValue> = PassManager::create(&module);
+
+    fn_pass_manager.add_promote_memory_to_register_pass();
+    fn_pass_manager.add_instruction_combining_pass();
+
+    assert!(!fn_pass_manager.initialize());
+
+    let i32_type = context.i32_type();
+    let fn_type = i32_type.fn_type(&[], false);
+    let i32_one = i32_type.const_int(1, false);
+    let first_fn = module.add_function("first", &fn_type, None);
+    let entry = first_fn.append_basic_block("entry");
+
+    builder.position_at_end(&entry);
+
+    let ptr = builder.build_alloca(i32_type, "ptr");
+
+    builder.build_store(&ptr, &i32_one);
+
+    let loaded = builder.build_load(&ptr, "loaded");
+
+    builder.build_return(Some(&loaded));
+
+    assert!(fn_pass_manager.run_on(&first_fn));
+    assert_eq!(first_fn.get_first_basic_block().unwrap().get_first_instruction().unwrap().get_opcode(), InstructionOpcode::Return);
+
+    // A later function can be optimized without touching the first one again
+    let second_fn = module.add_function("second", &fn_type, None);
+    let entry = second_fn.append_basic_block("entry");
+
+    builder.position_at_end(&entry);
+    builder.build_return(Some(&i32_one));
+
+    assert!(!fn_pass_manager.run_on(&second_fn));
+    assert!(!fn_pass_manager.finalize());
+}
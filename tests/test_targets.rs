@@ -2,9 +2,12 @@ extern crate inkwell;
 
 use self::inkwell::{AddressSpace, OptimizationLevel};
 use self::inkwell::context::Context;
-use self::inkwell::targets::{ByteOrdering, CodeModel, InitializationConfig, RelocMode, Target, TargetData, TargetMachine};
+use self::inkwell::targets::{ByteOrdering, CodeModel, FileType, InitializationConfig, RelocMode, Target, TargetData, TargetMachine};
 
+use std::env::temp_dir;
 use std::ffi::CString;
+use std::fs::{File, remove_file};
+use std::io::Read;
 
 // REVIEW: Inconsistently failing on different tries :(
 // #[test]
@@ -112,6 +115,51 @@ fn test_target_and_target_machine() {
     assert_eq!(*target_machine.get_feature_string(), *CString::new("+avx2").unwrap());
 }
 
+#[test]
+fn test_write_target_machine_to_memory_buffer_and_file() {
+    Target::initialize_x86(&InitializationConfig::default());
+
+    let target = Target::from_name("x86-64").unwrap();
+    let target_machine = target.create_target_machine("x86_64-pc-linux-gnu", "x86-64", "", OptimizationLevel::Default, RelocMode::Default, CodeModel::Default).unwrap();
+
+    let context = Context::create();
+    let module = context.create_module("my_module");
+    let builder = context.create_builder();
+    let i32_type = context.i32_type();
+    let fn_type = i32_type.fn_type(&[], false);
+    let fn_value = module.add_function("my_fn", &fn_type, None);
+    let entry = fn_value.append_basic_block("entry");
+
+    builder.position_at_end(&entry);
+    builder.build_return(Some(&i32_type.const_int(42, false)));
+
+    let asm_buffer = target_machine.write_to_memory_buffer(&module, FileType::Assembly).unwrap();
+    let asm = String::from_utf8_lossy(asm_buffer.as_slice());
+
+    assert!(asm.contains("my_fn:"));
+
+    let obj_buffer = target_machine.write_to_memory_buffer(&module, FileType::Object).unwrap();
+
+    // ELF magic number
+    assert_eq!(&obj_buffer.as_slice()[..4], b"\x7fELF");
+
+    let path = temp_dir().join("inkwell_test_write_to_file.o");
+
+    assert!(target_machine.write_to_file(&module, FileType::Object, &path).is_ok());
+
+    let mut contents = Vec::new();
+
+    File::open(&path).unwrap().read_to_end(&mut contents).unwrap();
+    remove_file(&path).unwrap();
+
+    assert_eq!(contents, obj_buffer.as_slice());
+
+    let bad_path = temp_dir().join("does_not_exist").join("main.o");
+    let err = target_machine.write_to_file(&module, FileType::Object, &bad_path).unwrap_err();
+
+    assert!(!err.to_bytes().is_empty());
+}
+
 #[test]
 fn test_default_target_triple() {
     let default_target_triple = TargetMachine::get_default_triple();
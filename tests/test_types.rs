@@ -4,7 +4,7 @@ use std::ffi::CString;
 
 use self::inkwell::AddressSpace;
 use self::inkwell::context::Context;
-use self::inkwell::types::{FloatType, IntType, StructType, VoidType};
+use self::inkwell::types::{FloatKind, FloatType, IntType, StructType, VoidType};
 
 #[test]
 fn test_struct_type() {
@@ -99,6 +99,33 @@ fn test_struct_type() {
     }
 }
 
+#[test]
+fn test_recursive_struct_type() {
+    let context = Context::create();
+    let i32_type = context.i32_type();
+    let node_type = context.opaque_struct_type("node");
+    let node_ptr_type = node_type.ptr_type(AddressSpace::Generic);
+
+    assert!(node_type.set_body(&[&i32_type, &node_ptr_type], false));
+
+    assert!(!node_type.is_opaque());
+    assert!(node_type.is_sized());
+    assert_eq!(node_type.count_fields(), 2);
+    assert_eq!(node_type.get_field_types(), vec![i32_type.into(), node_ptr_type.into()]);
+    assert_eq!(*node_type.print_to_string(), *CString::new("%node = type { i32, %node* }").unwrap());
+
+    // The body can only be set once
+    assert!(!node_type.set_body(&[&i32_type], false));
+    assert_eq!(node_type.count_fields(), 2);
+
+    let empty_struct = context.opaque_struct_type("empty");
+
+    assert!(empty_struct.set_body(&[], false));
+    assert!(!empty_struct.is_opaque());
+    assert!(empty_struct.is_sized());
+    assert_eq!(empty_struct.count_fields(), 0);
+}
+
 #[test]
 fn test_function_type() {
     let context = Context::create();
@@ -332,6 +359,74 @@ fn test_vec_type() {
     assert_eq!(vec_type.get_size(), 42);
 }
 
+#[test]
+fn test_float_bit_widths() {
+    let context = Context::create();
+
+    assert_eq!(context.f16_type().get_bit_width(), 16);
+    assert_eq!(context.f32_type().get_bit_width(), 32);
+    assert_eq!(context.f64_type().get_bit_width(), 64);
+    assert_eq!(context.x86_f80_type().get_bit_width(), 80);
+    assert_eq!(context.f128_type().get_bit_width(), 128);
+    assert_eq!(context.ppc_f128_type().get_bit_width(), 128);
+}
+
+#[test]
+fn test_float_kinds() {
+    let context = Context::create();
+    let float_types = [
+        context.f16_type(),
+        context.f32_type(),
+        context.f64_type(),
+        context.x86_f80_type(),
+        context.f128_type(),
+        context.ppc_f128_type(),
+    ];
+
+    for (i, float_type) in float_types.iter().enumerate() {
+        let predicates = [
+            float_type.is_f16(),
+            float_type.is_f32(),
+            float_type.is_f64(),
+            float_type.is_x86_f80(),
+            float_type.is_f128(),
+            float_type.is_ppc_f128(),
+        ];
+
+        for (j, &predicate) in predicates.iter().enumerate() {
+            assert_eq!(predicate, i == j);
+        }
+    }
+}
+
+#[test]
+fn test_float_in_context() {
+    let context = Context::create();
+    let context2 = Context::create();
+    let float_types = [
+        context.f16_type(),
+        context.f32_type(),
+        context.f64_type(),
+        context.x86_f80_type(),
+        context.f128_type(),
+        context.ppc_f128_type(),
+    ];
+    let float_types2 = [
+        context2.f16_type(),
+        context2.f32_type(),
+        context2.f64_type(),
+        context2.x86_f80_type(),
+        context2.f128_type(),
+        context2.ppc_f128_type(),
+    ];
+
+    for (float_type, float_type2) in float_types.iter().zip(float_types2.iter()) {
+        assert_eq!(float_type.in_context(&context2), *float_type2);
+        assert_eq!(float_type2.in_context(&context), *float_type);
+        assert_eq!(float_type.in_context(&context), *float_type);
+    }
+}
+
 #[test]
 fn test_type_copies() {
     let context = Context::create();
@@ -340,3 +435,51 @@ fn test_type_copies() {
 
     assert_eq!(i8_type, i8_type_copy);
 }
+
+#[test]
+fn test_float_kind() {
+    let context = Context::create();
+    let float_kinds = [
+        (context.f16_type(), FloatKind::F16),
+        (context.f32_type(), FloatKind::F32),
+        (context.f64_type(), FloatKind::F64),
+        (context.x86_f80_type(), FloatKind::X86F80),
+        (context.f128_type(), FloatKind::F128),
+        (context.ppc_f128_type(), FloatKind::PPCF128),
+    ];
+
+    for &(float_type, float_kind) in float_kinds.iter() {
+        assert_eq!(float_type.get_float_kind(), float_kind);
+        assert_ne!(float_type.get_float_kind(), FloatKind::Unknown);
+    }
+
+    assert_eq!(FloatType::f64_type().get_float_kind(), FloatKind::F64);
+}
+
+#[test]
+fn test_custom_width_int_type() {
+    let context = Context::create();
+    let i3_type = context.custom_width_int_type(3);
+    let i128_type = context.custom_width_int_type(128);
+    let max_type = context.custom_width_int_type((1 << 24) - 1);
+
+    assert_eq!(i3_type.get_bit_width(), 3);
+    assert_eq!(i128_type.get_bit_width(), 128);
+    assert_eq!(i128_type, context.i128_type());
+    assert_eq!(max_type.get_bit_width(), (1 << 24) - 1);
+    assert_eq!(IntType::custom_width_int_type(3).get_bit_width(), 3);
+}
+
+#[test]
+#[should_panic(expected = "IntType bit width must be between 1 and 2^24 - 1")]
+fn test_custom_width_int_type_zero_bits() {
+    let context = Context::create();
+
+    context.custom_width_int_type(0);
+}
+
+#[test]
+#[should_panic(expected = "IntType bit width must be between 1 and 2^24 - 1")]
+fn test_global_custom_width_int_type_too_wide() {
+    IntType::custom_width_int_type(1 << 24);
+}
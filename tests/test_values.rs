@@ -2,6 +2,7 @@ extern crate inkwell;
 
 use self::inkwell::{DLLStorageClass, FloatPredicate, GlobalVisibility, ThreadLocalMode, AddressSpace};
 use self::inkwell::context::Context;
+use self::inkwell::memory_buffer::MemoryBuffer;
 use self::inkwell::module::Linkage::*;
 use self::inkwell::types::{StructType, VectorType};
 use self::inkwell::values::InstructionOpcode::*;
@@ -340,18 +341,21 @@ fn test_verify_fn() {
 
     #[cfg(not(any(feature = "llvm3-9", feature = "llvm4-0", feature = "llvm5-0", feature = "llvm6-0")))]
     assert!(!function.verify(false));
-    // REVIEW: Why does 3.9, 4.0, & 5.0 return true here? LLVM bug? Bugfix?
+    // 3.9+ treat a function without a body as a valid declaration
     #[cfg(any(feature = "llvm3-9", feature = "llvm4-0", feature = "llvm5-0", feature = "llvm6-0"))]
     assert!(function.verify(false));
 
     let basic_block = context.append_basic_block(&function, "entry");
 
+    // The entry block is missing a terminator
+    assert!(!function.verify(false));
+    assert!(!function.verify(true));
+
     builder.position_at_end(&basic_block);
     builder.build_return(None);
 
     assert!(function.verify(false));
-
-    // TODO: Verify other verify modes
+    assert!(function.verify(true));
 }
 
 #[test]
@@ -622,6 +626,51 @@ fn test_metadata() {
     assert!(context_metadata_string.is_string());
 }
 
+#[test]
+fn test_instruction_metadata() {
+    let context = Context::create();
+    let module = context.create_module("my_mod");
+    let builder = context.create_builder();
+    let i32_type = context.i32_type();
+    let i32_ptr_type = i32_type.ptr_type(AddressSpace::Generic);
+    let fn_type = context.void_type().fn_type(&[&i32_ptr_type], false);
+    let fn_value = module.add_function("my_fn", &fn_type, None);
+    let entry = fn_value.append_basic_block("entry");
+    let ptr = fn_value.get_first_param().unwrap().into_pointer_value();
+
+    builder.position_at_end(&entry);
+
+    let load = builder.build_load(&ptr, "load").as_instruction().unwrap();
+    let store = builder.build_store(&ptr, &i32_type.const_int(1, false));
+    let ret = builder.build_return(None);
+
+    let kind_id = context.get_kind_id("my_kind");
+    let other_kind_id = context.get_kind_id("my_other_kind");
+    let md_node = context.metadata_node(&[i32_type.const_int(7, false).into()]);
+    let other_md_node = context.metadata_node(&[i32_type.const_int(8, false).into()]);
+
+    assert!(!load.has_metadata());
+    assert!(!store.has_metadata());
+    assert!(store.get_metadata(kind_id).is_none());
+
+    store.set_metadata(&md_node, kind_id);
+    load.set_metadata(&md_node, kind_id);
+    load.set_metadata(&other_md_node, other_kind_id);
+
+    assert!(store.has_metadata());
+    assert!(!ret.has_metadata());
+    assert_eq!(store.get_metadata(kind_id), Some(md_node));
+    assert!(store.get_metadata(other_kind_id).is_none());
+    assert_eq!(load.get_metadata(kind_id), Some(md_node));
+    assert_eq!(load.get_metadata(other_kind_id), Some(other_md_node));
+
+    // Replaces the existing node of the same kind
+    store.set_metadata(&other_md_node, kind_id);
+
+    assert_eq!(store.get_metadata(kind_id), Some(other_md_node));
+    assert!(fn_value.verify(false));
+}
+
 #[test]
 fn test_floats() {
     let context = Context::create();
@@ -697,6 +746,36 @@ fn test_floats() {
     assert!(f64_one.const_compare(FloatPredicate::UNO, &f64_two).is_null());
 }
 
+#[test]
+fn test_int_const_array() {
+    let context = Context::create();
+    let i8_type = context.i8_type();
+    let i8_array = i8_type.const_array(&[i8_type.const_int(1, false), i8_type.const_int(2, false)]);
+
+    assert_eq!(i8_array.get_type(), i8_type.array_type(2));
+    assert_eq!(*i8_array.print_to_string(), *CString::new("[2 x i8] c\"\\01\\02\"").unwrap());
+
+    let empty_array = i8_type.const_array(&[]);
+
+    assert_eq!(empty_array.get_type(), i8_type.array_type(0));
+}
+
+#[test]
+fn test_float_const_array() {
+    let context = Context::create();
+    let f64_type = context.f64_type();
+    let f64_one = f64_type.const_float(1.);
+    let f64_two = f64_type.const_float(2.);
+    let f64_array = f64_type.const_array(&[f64_one, f64_two]);
+
+    assert_eq!(f64_array.get_type(), f64_type.array_type(2));
+    assert_eq!(*f64_array.print_to_string(), *CString::new("[2 x double] [double 1.000000e+00, double 2.000000e+00]").unwrap());
+
+    let empty_array = f64_type.const_array(&[]);
+
+    assert_eq!(empty_array.get_type(), f64_type.array_type(0));
+}
+
 #[test]
 fn test_function_value_no_params() {
     let context = Context::create();
@@ -709,6 +788,7 @@ fn test_function_value_no_params() {
     // assert_eq!(fn_value.get_type(), fn_type);
     assert_eq!(fn_value.count_params(), 0);
     assert_eq!(fn_value.params().collect::<Vec<_>>().len(), 0);
+    assert!(fn_value.get_params().is_empty());
     assert!(fn_value.get_first_param().is_none());
     assert!(fn_value.get_last_param().is_none());
     assert!(fn_value.get_nth_param(0).is_none());
@@ -722,6 +802,40 @@ fn test_function_value_no_params() {
     assert!(!fn_value.is_undef());
 }
 
+#[test]
+fn test_function_value_params() {
+    let context = Context::create();
+    let module = context.create_module("my_mod");
+    let i32_type = context.i32_type();
+    let f32_type = context.f32_type();
+    let i32_ptr_type = i32_type.ptr_type(AddressSpace::Generic);
+    let fn_type = i32_type.fn_type(&[&i32_type, &f32_type, &i32_ptr_type], false);
+    let fn_value = module.add_function("params", &fn_type, None);
+    let params = fn_value.get_params();
+
+    assert_eq!(fn_value.count_params(), 3);
+    assert_eq!(params.len(), 3);
+    assert_eq!(fn_value.params().count(), 3);
+    assert!(params[0].is_int_value());
+    assert!(params[1].is_float_value());
+    assert!(params[2].is_pointer_value());
+    assert_eq!(fn_value.get_first_param().unwrap().into_int_value(), params[0].into_int_value());
+    assert_eq!(fn_value.get_nth_param(1).unwrap().into_float_value(), params[1].into_float_value());
+    assert_eq!(fn_value.get_last_param().unwrap().into_pointer_value(), params[2].into_pointer_value());
+    assert!(fn_value.get_nth_param(3).is_none());
+    assert!(fn_value.get_nth_param(u32::max_value()).is_none());
+
+    assert_eq!(*params[0].get_name(), *CString::new("").unwrap());
+
+    params[0].set_name("int_param");
+    params[1].set_name("float_param");
+    params[2].set_name("ptr_param");
+
+    assert_eq!(*params[0].get_name(), *CString::new("int_param").unwrap());
+    assert_eq!(*fn_value.get_nth_param(1).unwrap().get_name(), *CString::new("float_param").unwrap());
+    assert_eq!(*params[2].into_pointer_value().get_name(), *CString::new("ptr_param").unwrap());
+}
+
 #[test]
 fn test_int_from_string() {
     let context = Context::create();
@@ -745,6 +859,172 @@ fn test_int_from_string() {
     assert_eq!(*i8_val.print_to_string(), *CString::new("i8 -15").unwrap());
 }
 
+#[test]
+fn test_float_const_vector() {
+    let context = Context::create();
+    let f32_type = context.f32_type();
+    let f32_vec = f32_type.const_vector(&[f32_type.const_zero(), f32_type.const_float(1.5)]);
+
+    assert_eq!(f32_vec.get_type(), f32_type.vec_type(2));
+    assert_eq!(*f32_vec.print_to_string(), *CString::new("<2 x float> <float 0.000000e+00, float 1.500000e+00>").unwrap());
+}
+
+#[test]
+#[should_panic]
+fn test_float_const_vector_empty() {
+    let context = Context::create();
+
+    context.f32_type().const_vector(&[]);
+}
+
+#[test]
+fn test_float_const_splat() {
+    let context = Context::create();
+    let f64_type = context.f64_type();
+    let f64_splat = f64_type.const_splat(-1.25, 3);
+
+    assert_eq!(f64_splat.get_type(), f64_type.vec_type(3));
+    assert_eq!(*f64_splat.print_to_string(), *CString::new("<3 x double> <double -1.250000e+00, double -1.250000e+00, double -1.250000e+00>").unwrap());
+    assert_eq!(f64_type.const_splat(2., 1), f64_type.const_vector(&[f64_type.const_float(2.)]));
+}
+
+#[test]
+#[should_panic]
+fn test_float_const_splat_empty() {
+    let context = Context::create();
+
+    context.f32_type().const_splat(1., 0);
+}
+
+#[test]
+fn test_float_from_bits() {
+    let context = Context::create();
+    let f16_type = context.f16_type();
+    let f32_type = context.f32_type();
+    let f64_type = context.f64_type();
+
+    // const_float rounds to the nearest f32, so 0.1 and 0.1f32 produce the same constant
+    assert_eq!(f32_type.const_float(0.1), f32_type.const_float(0.1f32 as f64));
+    assert_eq!(f32_type.const_float_from_bits(0.1f32.to_bits() as u128), f32_type.const_float(0.1));
+    assert_eq!(f32_type.const_float_from_bits(0x3F80_0001), f32_type.const_float(1.0000001f32 as f64));
+    assert_ne!(f32_type.const_float_from_bits(0x3F80_0001), f32_type.const_float(1.));
+    assert_eq!(f64_type.const_float_from_bits(0.1f64.to_bits() as u128), f64_type.const_float(0.1));
+    assert_eq!(f16_type.const_float_from_bits(0xC000), f16_type.const_float(-2.));
+    assert!(f32_type.const_float_from_bits(0x7FC0_0000).print_to_string().to_string().contains("0x7FF8000000000000"));
+}
+
+#[test]
+fn test_float_from_bits_wide() {
+    let context = Context::create();
+    let f80_type = context.x86_f80_type();
+    let f128_type = context.f128_type();
+
+    assert_eq!(f80_type.const_float_from_bits(0x3FFF_8000_0000_0000_0000), f80_type.const_float(1.));
+    assert_eq!(f128_type.const_float_from_bits(0xC000_0000_0000_0000_0000_0000_0000_0000), f128_type.const_float(-2.));
+    // The smallest positive denormal isn't representable as an f64
+    assert_eq!(*f128_type.const_float_from_bits(1).print_to_string(), *CString::new("fp128 0xL00000000000000010000000000000000").unwrap());
+    // Bits above the type's width are ignored
+    assert_eq!(context.f32_type().const_float_from_bits(1 << 64 | 0x3F80_0000), context.f32_type().const_float(1.));
+}
+
+#[test]
+fn test_float_nan_infinity() {
+    let context = Context::create();
+    let f16_type = context.f16_type();
+    let f32_type = context.f32_type();
+    let f64_type = context.f64_type();
+    let f80_type = context.x86_f80_type();
+    let f128_type = context.f128_type();
+    let ppc_f128_type = context.ppc_f128_type();
+
+    assert_eq!(f16_type.const_nan(false), f16_type.const_float_from_bits(0x7E00));
+    assert_eq!(f16_type.const_nan(true), f16_type.const_float_from_bits(0x7C01));
+    assert_eq!(f16_type.const_infinity(true), f16_type.const_float_from_bits(0xFC00));
+    assert_eq!(f32_type.const_nan(true), f32_type.const_float_from_bits(0x7F80_0001));
+    assert_eq!(f64_type.const_nan(false), f64_type.const_float_from_bits(0x7FF8_0000_0000_0000));
+    assert_eq!(f64_type.const_nan(true), f64_type.const_float_from_bits(0x7FF0_0000_0000_0001));
+    assert_eq!(f64_type.const_infinity(false), f64_type.const_float(::std::f64::INFINITY));
+    assert_ne!(f64_type.const_nan(false), f64_type.const_nan(true));
+
+    assert_eq!(*f80_type.const_nan(true).print_to_string(), *CString::new("x86_fp80 0xK7FFF8000000000000001").unwrap());
+    assert_eq!(*f80_type.const_infinity(false).print_to_string(), *CString::new("x86_fp80 0xK7FFF8000000000000000").unwrap());
+    assert_eq!(*f128_type.const_nan(true).print_to_string(), *CString::new("fp128 0xL00000000000000017FFF000000000000").unwrap());
+    assert_eq!(*f128_type.const_infinity(true).print_to_string(), *CString::new("fp128 0xL0000000000000000FFFF000000000000").unwrap());
+    assert_eq!(*ppc_f128_type.const_nan(false).print_to_string(), *CString::new("ppc_fp128 0xM7FF80000000000000000000000000000").unwrap());
+    assert_ne!(ppc_f128_type.const_nan(true), ppc_f128_type.const_nan(false));
+}
+
+#[test]
+fn test_float_get_constant() {
+    let context = Context::create();
+    let module = context.create_module("test");
+    let builder = context.create_builder();
+    let f32_type = context.f32_type();
+    let f64_type = context.f64_type();
+    let f128_type = context.f128_type();
+    let fn_type = f64_type.fn_type(&[&f64_type], false);
+    let function = module.add_function("fn", &fn_type, None);
+    let entry = context.append_basic_block(&function, "entry");
+
+    builder.position_at_end(&entry);
+
+    let param = function.get_first_param().unwrap().into_float_value();
+    let sum = builder.build_float_add(param, f64_type.const_float(1.), "sum");
+
+    assert_eq!(param.get_constant(), None);
+    assert_eq!(sum.get_constant(), None);
+    assert!(!param.is_nan());
+    assert!(!param.is_infinity());
+    assert_eq!(f64_type.const_float(0.1).get_constant(), Some((0.1, false)));
+    assert_eq!(f32_type.const_float(0.1).get_constant(), Some((0.1f32 as f64, false)));
+    assert_eq!(f128_type.const_float(2.5).get_constant(), Some((2.5, false)));
+    assert_eq!(f64_type.const_float(2.).const_mul(&f64_type.const_float(3.)).get_constant(), Some((6., false)));
+    assert!(f32_type.const_infinity(false).is_infinity());
+    assert!(f128_type.const_infinity(true).is_infinity());
+    assert!(!f32_type.const_infinity(false).is_nan());
+    assert!(f32_type.const_nan(true).is_nan());
+    assert!(f128_type.const_nan(false).is_nan());
+}
+
+#[test]
+fn test_float_from_string() {
+    let context = Context::create();
+    let f32_type = context.f32_type();
+    let f64_type = context.f64_type();
+    let f64_val = f64_type.const_float_from_string("3.25").unwrap();
+
+    assert_eq!(*f64_val.print_to_string(), *CString::new("double 3.250000e+00").unwrap());
+
+    let f64_val = f64_type.const_float_from_string("-1e3").unwrap();
+
+    assert_eq!(*f64_val.print_to_string(), *CString::new("double -1.000000e+03").unwrap());
+
+    assert!(f64_type.const_float_from_string("").is_none());
+    assert!(f64_type.const_float_from_string("abc").is_none());
+    assert!(f64_type.const_float_from_string("1.2.3").is_none());
+    assert!(f64_type.const_float_from_string("1e400").is_none());
+    assert!(f32_type.const_float_from_string("1e39").is_none());
+    assert!(f32_type.const_float_from_string("1e38").is_some());
+
+    let f64_val = f64_type.const_float_from_string("0x1.8p1").unwrap();
+
+    assert_eq!(*f64_val.print_to_string(), *CString::new("double 3.000000e+00").unwrap());
+    assert!(f64_type.const_float_from_string("0x1.8").is_none());
+    assert!(f64_type.const_float_from_string("1e").is_none());
+    assert!(f64_type.const_float_from_string(".").is_none());
+    assert!(f64_type.const_float_from_string("-inf").is_some());
+    assert!(f64_type.const_float_from_string("Infinity").is_none());
+
+    // Ranges are checked per float kind, rather than approximated by f64
+    let f16_type = context.f16_type();
+
+    assert!(f16_type.const_float_from_string("6e4").is_some());
+    assert!(f16_type.const_float_from_string("1e5").is_none());
+    assert!(context.x86_f80_type().const_float_from_string("1e400").is_some());
+    assert!(context.f128_type().const_float_from_string("1e400").is_some());
+    assert!(context.f128_type().const_float_from_string("1e5000").is_none());
+}
+
 #[test]
 fn test_value_copies() {
     let context = Context::create();
@@ -800,9 +1080,8 @@ fn test_globals() {
     assert!(global.is_declaration());
     assert!(!global.has_unnamed_addr());
     assert!(!global.is_externally_initialized());
-    // REVIEW: Segfaults in 4.0, 5.0, & 6.0
-    #[cfg(not(any(feature = "llvm4-0", feature = "llvm5-0", feature = "llvm6-0")))]
     assert_eq!(global.get_section(), &*CString::new("").unwrap());
+    assert_eq!(global.get_alignment(), 0);
     assert_eq!(global.get_dll_storage_class(), DLLStorageClass::default());
     assert_eq!(global.get_visibility(), GlobalVisibility::default());
     assert_eq!(module.get_first_global().unwrap(), global);
@@ -816,6 +1095,7 @@ fn test_globals() {
     global.set_constant(true);
     global.set_visibility(GlobalVisibility::Hidden);
     global.set_section("not sure what goes here");
+    global.set_alignment(4);
 
     assert_eq!(global.get_dll_storage_class(), DLLStorageClass::Import);
     assert_eq!(global.get_initializer().unwrap().into_int_value(), i8_zero);
@@ -826,6 +1106,7 @@ fn test_globals() {
     assert!(global.is_constant());
     assert!(!global.is_declaration());
     assert_eq!(global.get_section(), &*CString::new("not sure what goes here").unwrap());
+    assert_eq!(global.get_alignment(), 4);
 
     global.set_dll_storage_class(DLLStorageClass::Export);
     global.set_thread_local(false);
@@ -871,6 +1152,48 @@ fn test_globals() {
     }
 }
 
+#[test]
+#[should_panic]
+fn test_global_alignment_not_power_of_two() {
+    let context = Context::create();
+    let module = context.create_module("my_mod");
+    let global = module.add_global(&context.i8_type(), None, "my_global");
+
+    global.set_alignment(3);
+}
+
+#[test]
+#[should_panic]
+fn test_global_initializer_type_mismatch() {
+    let context = Context::create();
+    let module = context.create_module("my_mod");
+    let i8_type = context.i8_type();
+    let i32_type = context.i32_type();
+    let global = module.add_global(&i8_type, None, "my_global");
+
+    global.set_initializer(&i32_type.const_int(1, false));
+}
+
+#[test]
+#[should_panic]
+fn test_global_initializer_non_constant() {
+    let context = Context::create();
+    let module = context.create_module("my_mod");
+    let builder = context.create_builder();
+    let i32_type = context.i32_type();
+    let fn_type = i32_type.fn_type(&[&i32_type], false);
+    let fn_value = module.add_function("my_fn", &fn_type, None);
+    let global = module.add_global(&i32_type, None, "my_global");
+    let entry = fn_value.append_basic_block("entry");
+    let param = fn_value.get_first_param().unwrap().into_int_value();
+
+    builder.position_at_end(&entry);
+
+    let sum = builder.build_int_add(param, i32_type.const_int(1, false), "sum");
+
+    global.set_initializer(&sum);
+}
+
 #[test]
 fn test_phi_values() {
     let context = Context::create();
@@ -915,6 +1238,27 @@ fn test_phi_values() {
     assert!(phi.get_incoming(2).is_none());
 }
 
+#[test]
+#[should_panic]
+fn test_phi_incoming_type_mismatch() {
+    let context = Context::create();
+    let builder = context.create_builder();
+    let module = context.create_module("my_mod");
+    let void_type = context.void_type();
+    let bool_type = context.bool_type();
+    let i32_type = context.i32_type();
+    let fn_type = void_type.fn_type(&[], false);
+    let fn_value = module.add_function("my_func", &fn_type, None);
+    let entry_block = fn_value.append_basic_block("entry");
+    let next_block = fn_value.append_basic_block("next");
+
+    builder.position_at_end(&next_block);
+
+    let phi = builder.build_phi(&bool_type, "phi");
+
+    phi.add_incoming(&[(&i32_type.const_int(1, false), &entry_block)]);
+}
+
 #[test]
 fn test_allocations() {
     let context = Context::create();
@@ -952,3 +1296,77 @@ fn test_allocations() {
 
     assert_eq!(*heap_array.get_type().print_to_string(), *CString::new("i32*").unwrap());
 }
+
+#[test]
+fn test_bool_consts() {
+    let context = Context::create();
+    let bool_type = context.bool_type();
+    let i32_type = context.i32_type();
+
+    assert_eq!(*bool_type.const_true().print_to_string(), *CString::new("i1 true").unwrap());
+    assert_eq!(*bool_type.const_false().print_to_string(), *CString::new("i1 false").unwrap());
+    assert_eq!(i32_type.const_true(), i32_type.const_int(1, false));
+    assert_eq!(i32_type.const_false(), i32_type.const_int(0, false));
+}
+
+#[test]
+fn test_instruction_operands() {
+    let context = Context::create();
+    let module = context.create_module("testing");
+    let builder = context.create_builder();
+    let i32_type = context.i32_type();
+    let bool_type = context.bool_type();
+    let fn_type = i32_type.fn_type(&[&i32_type, &bool_type], false);
+    let function = module.add_function("my_fn", &fn_type, None);
+    let entry = function.append_basic_block("entry");
+    let then_block = function.append_basic_block("then");
+    let else_block = function.append_basic_block("else");
+    let arg1 = function.get_first_param().unwrap().into_int_value();
+    let condition = function.get_nth_param(1).unwrap().into_int_value();
+    let one = i32_type.const_int(1, false);
+
+    builder.position_at_end(&entry);
+
+    let add = builder.build_int_add(arg1, one, "add");
+    let add_instruction = add.as_instruction().unwrap();
+    let call = builder.build_call(&function, &[&add, &condition], "call", false)
+                      .left()
+                      .and_then(|value| value.as_instruction())
+                      .unwrap();
+    let branch = builder.build_conditional_branch(&condition, &then_block, &else_block);
+
+    assert_eq!(add_instruction.get_opcode(), Add);
+    assert_eq!(add_instruction.get_num_operands(), 2);
+    assert_eq!(add_instruction.get_operand(0).unwrap().left().unwrap().into_int_value(), arg1);
+    assert_eq!(add_instruction.get_operand(1).unwrap().left().unwrap().into_int_value(), one);
+    assert!(add_instruction.get_operand(2).is_none());
+
+    // The called function comes after the arguments
+    assert_eq!(call.get_num_operands(), 3);
+    assert_eq!(call.get_operand(0).unwrap().left().unwrap().into_int_value(), add);
+    assert_eq!(call.get_operand(2).unwrap().left().unwrap().into_pointer_value().get_name(), function.get_name());
+
+    // LLVM stores the false destination before the true destination
+    assert_eq!(branch.get_opcode(), Br);
+    assert_eq!(branch.get_num_operands(), 3);
+    assert_eq!(branch.get_operand(0).unwrap().left().unwrap().into_int_value(), condition);
+    assert_eq!(branch.get_operand(1).unwrap().right(), Some(else_block));
+    assert_eq!(branch.get_operand(2).unwrap().right(), Some(then_block));
+
+    let two = i32_type.const_int(2, false);
+
+    assert!(add_instruction.set_operand(1, &two));
+    assert!(!add_instruction.set_operand(2, &two));
+    assert_eq!(add_instruction.get_operand(1).unwrap().left().unwrap().into_int_value(), two);
+
+    // Metadata operands aren't basic values, so they aren't returned rather than panicking
+    let ir = "declare void @use_metadata(metadata)\n\ndefine void @f() {\n  call void @use_metadata(metadata !{})\n  ret void\n}\n";
+    let memory_buffer = MemoryBuffer::create_from_memory_range_copy(ir.as_bytes(), "metadata.ll");
+    let module = context.create_module_from_ir(memory_buffer).unwrap();
+    let call = module.get_function("f").unwrap().get_first_basic_block().unwrap().get_first_instruction().unwrap();
+
+    assert_eq!(call.get_opcode(), Call);
+    assert_eq!(call.get_num_operands(), 2);
+    assert!(call.get_operand(0).is_none());
+    assert!(call.get_operand(1).unwrap().left().unwrap().is_pointer_value());
+}